@@ -0,0 +1,19 @@
+-- Append-only log of conversation events used to replay missed updates on reconnect
+CREATE TABLE sync_events (
+    seq BIGSERIAL PRIMARY KEY,
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    event_type VARCHAR(30) NOT NULL CHECK (event_type IN ('MessageCreated', 'MessageEdited', 'MessageDeleted', 'MessageRead')),
+    payload JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_sync_events_conversation_seq ON sync_events(conversation_id, seq);
+
+-- Last event each device has seen
+CREATE TABLE device_sync_cursors (
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    device_id VARCHAR(100) NOT NULL,
+    last_seq BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, device_id)
+);
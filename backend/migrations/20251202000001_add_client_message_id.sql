@@ -0,0 +1,5 @@
+-- Client-generated message IDs make SendMessage retries idempotent
+ALTER TABLE messages ADD COLUMN client_message_id VARCHAR(64);
+
+CREATE UNIQUE INDEX idx_messages_sender_client_id ON messages(sender_id, client_message_id)
+    WHERE client_message_id IS NOT NULL;
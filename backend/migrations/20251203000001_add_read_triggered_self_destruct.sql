@@ -0,0 +1,16 @@
+-- Self-destruct lifetime, so timers that start on read can be armed after the message is sent
+ALTER TABLE messages ADD COLUMN self_destruct_seconds BIGINT;
+ALTER TABLE messages ADD COLUMN self_destruct_on_read BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Expired messages are hard-deleted; replies keep existing without their target
+ALTER TABLE messages DROP CONSTRAINT messages_reply_to_id_fkey;
+ALTER TABLE messages ADD CONSTRAINT messages_reply_to_id_fkey
+    FOREIGN KEY (reply_to_id) REFERENCES messages(id) ON DELETE SET NULL;
+
+-- Expiry notifications are replayed to offline devices
+ALTER TABLE sync_events DROP CONSTRAINT sync_events_event_type_check;
+ALTER TABLE sync_events ADD CONSTRAINT sync_events_event_type_check
+    CHECK (event_type IN ('MessageCreated', 'MessageEdited', 'MessageDeleted', 'MessageRead', 'MessageExpired'));
+
+-- Lets expired message content be redacted from the sync log
+CREATE INDEX idx_sync_events_message_id ON sync_events ((COALESCE(payload->>'id', payload->>'message_id')));
@@ -0,0 +1,6 @@
+-- Group roles: every group has exactly one owner, admins can manage members
+ALTER TABLE conversation_participants ADD COLUMN role VARCHAR(20) NOT NULL DEFAULT 'Member'
+    CHECK (role IN ('Owner', 'Admin', 'Member'));
+
+CREATE UNIQUE INDEX idx_participants_owner ON conversation_participants(conversation_id)
+    WHERE role = 'Owner';
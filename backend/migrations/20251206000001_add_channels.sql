@@ -0,0 +1,7 @@
+-- Broadcast channels: admins post, subscribers only read
+ALTER TABLE conversations DROP CONSTRAINT conversations_type_check;
+ALTER TABLE conversations ADD CONSTRAINT conversations_type_check
+    CHECK (type IN ('Private', 'Group', 'Channel'));
+
+-- Channel posts count views instead of storing a read receipt per subscriber
+ALTER TABLE messages ADD COLUMN view_count BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Thread lookups and reply counts
+CREATE INDEX idx_messages_reply_to ON messages(reply_to_id, created_at) WHERE reply_to_id IS NOT NULL;
@@ -0,0 +1,4 @@
+-- Forward provenance: the message this one was forwarded from, and how many hops away
+-- from the original it is
+ALTER TABLE messages ADD COLUMN forwarded_from_id UUID REFERENCES messages(id) ON DELETE SET NULL;
+ALTER TABLE messages ADD COLUMN forward_count INTEGER NOT NULL DEFAULT 0;
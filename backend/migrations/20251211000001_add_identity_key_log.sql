@@ -0,0 +1,41 @@
+-- Append-only, hash-chained history of every identity key a user or device has published
+CREATE TABLE identity_key_log (
+    seq BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- NULL for the account-level key uploaded through /api/keys/upload
+    device_id VARCHAR(100),
+    identity_key TEXT NOT NULL,
+    prev_hash VARCHAR(64),
+    entry_hash VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_identity_key_log_user ON identity_key_log(user_id, seq);
+
+-- Each entry has at most one successor, so concurrent appends cannot fork a user's chain
+CREATE UNIQUE INDEX idx_identity_key_log_chain ON identity_key_log(user_id, COALESCE(prev_hash, ''));
+
+CREATE OR REPLACE FUNCTION reject_identity_key_log_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'identity_key_log is append-only';
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER identity_key_log_append_only BEFORE UPDATE ON identity_key_log
+    FOR EACH ROW EXECUTE FUNCTION reject_identity_key_log_update();
+
+ALTER TABLE sync_events DROP CONSTRAINT sync_events_event_type_check;
+ALTER TABLE sync_events ADD CONSTRAINT sync_events_event_type_check
+    CHECK (event_type IN ('MessageCreated', 'MessageEdited', 'MessageDeleted', 'MessageRead', 'MessageExpired', 'KeyChanged'));
+
+-- Seed each chain with the account key users already uploaded, hashed as KeyLogEntry::compute_hash does
+INSERT INTO identity_key_log (user_id, device_id, identity_key, prev_hash, entry_hash, created_at)
+SELECT id, NULL, public_key_x25519, NULL,
+       encode(sha256(convert_to(
+           E'\n' || id::text || E'\n' || E'\n' || public_key_x25519 || E'\n' || (EXTRACT(EPOCH FROM NOW()) * 1000000)::BIGINT::text,
+           'UTF8'
+       )), 'hex'),
+       NOW()
+FROM users
+WHERE public_key_x25519 IS NOT NULL;
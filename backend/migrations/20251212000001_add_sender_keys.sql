@@ -0,0 +1,27 @@
+-- Sender keys: each group has a key epoch that advances whenever its membership changes
+CREATE TABLE sender_key_epochs (
+    conversation_id UUID PRIMARY KEY REFERENCES conversations(id) ON DELETE CASCADE,
+    epoch INTEGER NOT NULL DEFAULT 0,
+    rotated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A sender's key for one epoch, encrypted pairwise for one recipient device
+CREATE TABLE sender_key_envelopes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    sender_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    sender_device_id VARCHAR(100) NOT NULL,
+    recipient_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    recipient_device_id VARCHAR(100) NOT NULL,
+    epoch INTEGER NOT NULL,
+    ciphertext TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (conversation_id, epoch, recipient_id, recipient_device_id, sender_id, sender_device_id)
+);
+
+ALTER TABLE sync_events DROP CONSTRAINT sync_events_event_type_check;
+ALTER TABLE sync_events ADD CONSTRAINT sync_events_event_type_check
+    CHECK (event_type IN (
+        'MessageCreated', 'MessageEdited', 'MessageDeleted', 'MessageRead', 'MessageExpired', 'KeyChanged',
+        'SenderKeyRotated', 'SenderKeyDistributed'
+    ));
@@ -0,0 +1,24 @@
+-- Opt-in backup vault: the server-held half of each user's backup key, released only
+-- against the passphrase-derived access key and wiped after too many wrong guesses
+CREATE TABLE backup_vaults (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    access_key_hash TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 10,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Versions of a user's encrypted archive stored in S3
+CREATE TABLE backups (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    object_key TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- NULL until the client confirms the upload finished
+    completed_at TIMESTAMPTZ,
+    UNIQUE (user_id, version)
+);
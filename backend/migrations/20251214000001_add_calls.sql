@@ -0,0 +1,20 @@
+-- One row per call attempt; the status follows Ringing -> Accepted -> Ended, or ends early as
+-- Rejected, Busy or Missed
+CREATE TABLE calls (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    caller_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    callee_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    is_video BOOLEAN NOT NULL DEFAULT FALSE,
+    status VARCHAR(20) NOT NULL DEFAULT 'Ringing'
+        CHECK (status IN ('Ringing', 'Accepted', 'Rejected', 'Busy', 'Missed', 'Ended')),
+    answered_device_id VARCHAR(100),
+    ring_expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    answered_at TIMESTAMPTZ,
+    ended_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_calls_ringing ON calls (ring_expires_at) WHERE status = 'Ringing';
+CREATE INDEX idx_calls_active_caller ON calls (caller_id) WHERE status IN ('Ringing', 'Accepted');
+CREATE INDEX idx_calls_active_callee ON calls (callee_id) WHERE status IN ('Ringing', 'Accepted');
@@ -0,0 +1,27 @@
+-- Multi-party calls in groups, hosted in an SFU room; at most one call runs per group
+CREATE TABLE group_calls (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    room_name VARCHAR(100) NOT NULL UNIQUE,
+    started_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    is_video BOOLEAN NOT NULL DEFAULT FALSE,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ended_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX idx_group_calls_active ON group_calls (conversation_id) WHERE ended_at IS NULL;
+
+CREATE TABLE group_call_participants (
+    call_id UUID NOT NULL REFERENCES group_calls(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    left_at TIMESTAMPTZ,
+    PRIMARY KEY (call_id, user_id)
+);
+
+ALTER TABLE sync_events DROP CONSTRAINT sync_events_event_type_check;
+ALTER TABLE sync_events ADD CONSTRAINT sync_events_event_type_check
+    CHECK (event_type IN (
+        'MessageCreated', 'MessageEdited', 'MessageDeleted', 'MessageRead', 'MessageExpired', 'KeyChanged',
+        'SenderKeyRotated', 'SenderKeyDistributed', 'GroupCallUpdated'
+    ));
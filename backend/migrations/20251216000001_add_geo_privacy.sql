@@ -0,0 +1,17 @@
+-- Nearby search is opt-in; locations are stored already fuzzed and expire after a while
+ALTER TABLE users ADD COLUMN discoverable BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE users ADD COLUMN location_updated_at TIMESTAMPTZ;
+
+-- Locations recorded before the opt-in existed were never consented to
+UPDATE users SET location = NULL;
+
+CREATE TABLE user_blocks (
+    blocker_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    blocked_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (blocker_id, blocked_id),
+    CHECK (blocker_id <> blocked_id)
+);
+
+CREATE INDEX idx_user_blocks_blocked ON user_blocks (blocked_id);
+CREATE INDEX idx_users_location_updated_at ON users (location_updated_at) WHERE location IS NOT NULL;
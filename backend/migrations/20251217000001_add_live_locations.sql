@@ -0,0 +1,33 @@
+-- Live location shared into a conversation for a limited time; at most one active session per user and chat
+CREATE TABLE live_location_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    stopped_at TIMESTAMPTZ,
+    last_latitude DOUBLE PRECISION,
+    last_longitude DOUBLE PRECISION,
+    last_updated_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX idx_live_location_sessions_active
+    ON live_location_sessions (conversation_id, user_id) WHERE stopped_at IS NULL;
+CREATE INDEX idx_live_location_sessions_expires_at
+    ON live_location_sessions (expires_at) WHERE stopped_at IS NULL;
+
+-- Track of every accepted update, so participants can replay where the user went
+CREATE TABLE live_location_points (
+    session_id UUID NOT NULL REFERENCES live_location_sessions(id) ON DELETE CASCADE,
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (session_id, recorded_at)
+);
+
+ALTER TABLE sync_events DROP CONSTRAINT sync_events_event_type_check;
+ALTER TABLE sync_events ADD CONSTRAINT sync_events_event_type_check
+    CHECK (event_type IN (
+        'MessageCreated', 'MessageEdited', 'MessageDeleted', 'MessageRead', 'MessageExpired', 'KeyChanged',
+        'SenderKeyRotated', 'SenderKeyDistributed', 'GroupCallUpdated', 'LiveLocationStarted', 'LiveLocationStopped'
+    ));
@@ -0,0 +1,11 @@
+-- Location-bound groups: anchored to a point and radius, discoverable and joinable from within range
+ALTER TABLE conversations ADD COLUMN geofence_center GEOGRAPHY(POINT);
+ALTER TABLE conversations ADD COLUMN geofence_radius_meters INTEGER;
+ALTER TABLE conversations ADD COLUMN geofence_remove_on_exit BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE conversations ADD CONSTRAINT conversations_geofence_check
+    CHECK ((geofence_center IS NULL) = (geofence_radius_meters IS NULL));
+
+-- Spatial index for discovery, like idx_users_location
+CREATE INDEX idx_conversations_geofence ON conversations USING GIST(geofence_center)
+    WHERE geofence_center IS NOT NULL;
@@ -0,0 +1,26 @@
+-- Paid subscription of a user, billed through the payment provider. A user has at most one;
+-- subscribing again after it expired replaces it. users.subscription_tier mirrors what it entitles to
+CREATE TABLE subscriptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    tier VARCHAR(20) NOT NULL CHECK (tier IN ('Monthly', 'Yearly')),
+    status VARCHAR(20) NOT NULL CHECK (status IN ('Active', 'PastDue', 'Canceled', 'Expired')),
+    provider_subscription_id VARCHAR(255) NOT NULL UNIQUE,
+    current_period_start TIMESTAMPTZ NOT NULL,
+    current_period_end TIMESTAMPTZ NOT NULL,
+    cancel_at_period_end BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_subscriptions_current_period_end
+    ON subscriptions (current_period_end) WHERE status <> 'Expired';
+
+-- Webhook deliveries already applied, so retried deliveries are not applied twice
+CREATE TABLE payment_webhook_events (
+    event_id VARCHAR(255) PRIMARY KEY,
+    processed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Tiers granted before billing existed were never paid for
+UPDATE users SET subscription_tier = 'Free' WHERE subscription_tier <> 'Free';
@@ -0,0 +1,2 @@
+-- Track points are purged once past their retention window
+CREATE INDEX idx_live_location_points_recorded_at ON live_location_points (recorded_at);
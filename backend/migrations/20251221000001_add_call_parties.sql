@@ -0,0 +1,18 @@
+-- Calls never ended on disconnect before, so rows still ringing or accepted at this point
+-- belong to sessions that are long gone
+UPDATE calls SET status = 'Missed', ended_at = NOW() WHERE status = 'Ringing';
+UPDATE calls SET status = 'Ended', ended_at = NOW() WHERE status = 'Accepted';
+
+-- Both parties of a call with the device they are on and when that device was last heard from;
+-- a user can be a party of at most one ringing or accepted call
+CREATE TABLE call_parties (
+    call_id UUID NOT NULL REFERENCES calls(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    device_id VARCHAR(100),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (call_id, user_id)
+);
+
+CREATE UNIQUE INDEX idx_call_parties_active ON call_parties (user_id) WHERE is_active;
+CREATE INDEX idx_call_parties_last_seen ON call_parties (last_seen_at) WHERE is_active;
@@ -0,0 +1,8 @@
+-- Upgrades reserved before the payment provider is charged; at most one per user at a time.
+-- The id is the idempotency key of the provider requests, so a retried upgrade never charges twice
+CREATE TABLE subscription_checkouts (
+    id UUID NOT NULL UNIQUE,
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    tier VARCHAR(20) NOT NULL CHECK (tier IN ('Monthly', 'Yearly')),
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,9 @@
+-- Envelopes of an epoch are kept for a while after the next rotation, so devices catching up
+-- on messages sent before it can still decrypt them
+ALTER TABLE sender_key_envelopes ADD COLUMN superseded_at TIMESTAMPTZ;
+
+CREATE INDEX idx_sender_key_envelopes_superseded
+    ON sender_key_envelopes (superseded_at) WHERE superseded_at IS NOT NULL;
+
+-- Sender key epoch a group message was encrypted under
+ALTER TABLE messages ADD COLUMN sender_key_epoch INTEGER;
@@ -0,0 +1,9 @@
+-- Prekey bundle claims per claimer and target in the current window, so one account cannot
+-- drain another user's one-time prekeys
+CREATE TABLE prekey_claim_limits (
+    claimer_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    target_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    window_started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    claims INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (claimer_id, target_id)
+);
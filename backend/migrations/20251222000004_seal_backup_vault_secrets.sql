@@ -0,0 +1,5 @@
+-- Vault secrets are stored sealed under a key derived from the access key, so the database
+-- alone no longer reveals them. Plaintext secrets of existing vaults are sealed on their next
+-- unlock, the first time the server sees the access key again
+ALTER TABLE backup_vaults ADD COLUMN sealed_secret TEXT;
+ALTER TABLE backup_vaults ALTER COLUMN secret DROP NOT NULL;
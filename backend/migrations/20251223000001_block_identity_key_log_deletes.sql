@@ -0,0 +1,15 @@
+-- History entries can neither be changed nor removed. Deleting the user still cascades, since
+-- the whole chain goes with the account
+CREATE OR REPLACE FUNCTION reject_identity_key_log_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' AND NOT EXISTS (SELECT 1 FROM users WHERE id = OLD.user_id) THEN
+        RETURN OLD;
+    END IF;
+    RAISE EXCEPTION 'identity_key_log is append-only';
+END;
+$$ language 'plpgsql';
+
+DROP TRIGGER identity_key_log_append_only ON identity_key_log;
+CREATE TRIGGER identity_key_log_append_only BEFORE UPDATE OR DELETE ON identity_key_log
+    FOR EACH ROW EXECUTE FUNCTION reject_identity_key_log_update();
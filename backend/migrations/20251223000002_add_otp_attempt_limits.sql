@@ -0,0 +1,6 @@
+-- OTP attempts per user in the current window, so a replacement OTP cannot be brute-forced
+CREATE TABLE otp_attempt_limits (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    window_started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts INTEGER NOT NULL DEFAULT 0
+);
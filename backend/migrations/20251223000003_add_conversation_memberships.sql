@@ -0,0 +1,34 @@
+-- When each user was a member of each conversation, so replaying the sync log only hands out
+-- events from the periods the user was in the conversation
+CREATE TABLE conversation_memberships (
+    id BIGSERIAL PRIMARY KEY,
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    left_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_conversation_memberships_user ON conversation_memberships(user_id, conversation_id);
+
+CREATE OR REPLACE FUNCTION record_conversation_membership()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO conversation_memberships (conversation_id, user_id, joined_at)
+        VALUES (NEW.conversation_id, NEW.user_id, NOW());
+        RETURN NEW;
+    END IF;
+
+    UPDATE conversation_memberships SET left_at = NOW()
+    WHERE conversation_id = OLD.conversation_id AND user_id = OLD.user_id AND left_at IS NULL;
+    RETURN OLD;
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER conversation_participants_membership AFTER INSERT OR DELETE ON conversation_participants
+    FOR EACH ROW EXECUTE FUNCTION record_conversation_membership();
+
+INSERT INTO conversation_memberships (conversation_id, user_id, joined_at)
+SELECT conversation_id, user_id, COALESCE(joined_at AT TIME ZONE 'UTC', NOW())
+FROM conversation_participants
+WHERE conversation_id IS NOT NULL AND user_id IS NOT NULL;
@@ -0,0 +1,17 @@
+-- Groups created before roles existed have no owner, so nobody can manage them. Promote the
+-- earliest admin of each, or the earliest member if there is none
+UPDATE conversation_participants p
+SET role = 'Owner'
+FROM (
+    SELECT DISTINCT ON (cp.conversation_id) cp.conversation_id, cp.user_id
+    FROM conversation_participants cp
+    JOIN conversations c ON c.id = cp.conversation_id
+    WHERE c.type IN ('Group', 'Channel')
+      AND NOT EXISTS (
+          SELECT 1 FROM conversation_participants o
+          WHERE o.conversation_id = cp.conversation_id AND o.role = 'Owner'
+      )
+    ORDER BY cp.conversation_id, cp.role = 'Admin' DESC, cp.joined_at ASC NULLS LAST, cp.user_id
+) earliest
+WHERE p.conversation_id = earliest.conversation_id
+  AND p.user_id = earliest.user_id;
@@ -0,0 +1,14 @@
+-- Sync seqs are handed out from a single counter row that stays locked until the inserting
+-- transaction commits, so an event only becomes visible after every event with a lower seq.
+-- BIGSERIAL values are taken at insert time and can commit out of order, which let a device
+-- ack past an event that was still in flight and never replay it.
+CREATE TABLE sync_event_counter (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    last_seq BIGINT NOT NULL
+);
+
+INSERT INTO sync_event_counter (last_seq)
+SELECT COALESCE(MAX(seq), 0) FROM sync_events;
+
+ALTER TABLE sync_events ALTER COLUMN seq DROP DEFAULT;
+DROP SEQUENCE sync_events_seq_seq;
@@ -0,0 +1,3 @@
+-- Vault access keys are now peppered with a server secret held outside the database. Existing
+-- vaults keep their unpeppered hash and seal until their next successful unlock.
+ALTER TABLE backup_vaults ADD COLUMN peppered BOOLEAN NOT NULL DEFAULT FALSE;
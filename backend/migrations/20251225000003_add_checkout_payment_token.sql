@@ -0,0 +1,3 @@
+-- Checkouts keep the payment token they charge, so a retry with another token or tier is not
+-- answered with the first request's idempotent result
+ALTER TABLE subscription_checkouts ADD COLUMN payment_token TEXT;
@@ -16,9 +16,26 @@ use crate::application::{
     AuthResponse, LoginRequest, LoginUser, RegisterRequest, RegisterUser, VerifyOtpRequest, VerifyOtp,
     UploadPublicKey, GetPublicKey, UploadPublicKeyRequest, PublicKeyResponse,
     GetUploadUrl, SubmitKyc, ReviewKyc, SendMessage,
-    UpdateLocation, FindNearbyUsers,
-    UpgradeSubscription,
+    EditMessage, DeleteMessage, MarkAsRead, GetMissedEvents, AcknowledgeSync,
+    SetDisappearingMessages, GetMessageHistory, GetThread,
+    PinMessage, UnpinMessage, GetPinnedMessages, StarMessage, UnstarMessage, GetStarredMessages,
+    ForwardMessage,
+    CreateGroup, GetGroup, AddGroupMembers, RemoveGroupMember, ChangeMemberRole,
+    TransferGroupOwnership, UpdateGroupInfo, UpdateGroupSettings,
+    CreateGroupInvite, ListGroupInvites, RevokeGroupInvite, PreviewGroupInvite,
+    JoinGroupViaInvite, ListJoinRequests, ReviewJoinRequest, FindNearbyGroups, JoinNearbyGroup, EnforceGeofences,
+    CreateChannel, SubscribeChannel, UnsubscribeChannel, GetChannelViews,
+    RegisterDeviceKeys, UploadOneTimePreKeys, GetPreKeyCount, ClaimPreKeyBundles, GetKeyHistory,
+    DistributeSenderKey, GetSenderKeys,
+    SetupBackupVault, UnlockBackupVault, CreateBackup, CompleteBackup, ListBackups, RestoreBackup,
+    DeleteBackup, DisableBackups,
+    StartCall, AnswerCall, EndCall, GetActiveCall, TrackCallPresence, GetIceServers,
+    JoinGroupCall, LeaveGroupCall, GetGroupCall,
+    UpdateLocation, FindNearbyUsers, SetDiscoverability, BlockUser, UnblockUser,
+    StartLiveLocation, UpdateLiveLocation, StopLiveLocation, GetLiveLocations,
+    UpgradeSubscription, GetSubscription, CancelSubscription, HandlePaymentWebhook,
     RegisterDeviceToken,
+    ServerEvent,
 };
 
 pub struct AppState {
@@ -31,11 +48,82 @@ pub struct AppState {
     pub submit_kyc: Arc<SubmitKyc>,
     pub review_kyc: Arc<ReviewKyc>,
     pub send_message: Arc<SendMessage>,
+    pub edit_message: Arc<EditMessage>,
+    pub delete_message: Arc<DeleteMessage>,
+    pub mark_as_read: Arc<MarkAsRead>,
+    pub get_missed_events: Arc<GetMissedEvents>,
+    pub acknowledge_sync: Arc<AcknowledgeSync>,
+    pub set_disappearing_messages: Arc<SetDisappearingMessages>,
+    pub get_message_history: Arc<GetMessageHistory>,
+    pub get_thread: Arc<GetThread>,
+    pub pin_message: Arc<PinMessage>,
+    pub unpin_message: Arc<UnpinMessage>,
+    pub get_pinned_messages: Arc<GetPinnedMessages>,
+    pub star_message: Arc<StarMessage>,
+    pub unstar_message: Arc<UnstarMessage>,
+    pub get_starred_messages: Arc<GetStarredMessages>,
+    pub forward_message: Arc<ForwardMessage>,
+    pub create_group: Arc<CreateGroup>,
+    pub get_group: Arc<GetGroup>,
+    pub add_group_members: Arc<AddGroupMembers>,
+    pub remove_group_member: Arc<RemoveGroupMember>,
+    pub change_member_role: Arc<ChangeMemberRole>,
+    pub transfer_group_ownership: Arc<TransferGroupOwnership>,
+    pub update_group_info: Arc<UpdateGroupInfo>,
+    pub update_group_settings: Arc<UpdateGroupSettings>,
+    pub create_group_invite: Arc<CreateGroupInvite>,
+    pub list_group_invites: Arc<ListGroupInvites>,
+    pub revoke_group_invite: Arc<RevokeGroupInvite>,
+    pub preview_group_invite: Arc<PreviewGroupInvite>,
+    pub join_group_via_invite: Arc<JoinGroupViaInvite>,
+    pub list_join_requests: Arc<ListJoinRequests>,
+    pub review_join_request: Arc<ReviewJoinRequest>,
+    pub find_nearby_groups: Arc<FindNearbyGroups>,
+    pub join_nearby_group: Arc<JoinNearbyGroup>,
+    pub enforce_geofences: Arc<EnforceGeofences>,
+    pub create_channel: Arc<CreateChannel>,
+    pub subscribe_channel: Arc<SubscribeChannel>,
+    pub unsubscribe_channel: Arc<UnsubscribeChannel>,
+    pub get_channel_views: Arc<GetChannelViews>,
+    pub register_device_keys: Arc<RegisterDeviceKeys>,
+    pub upload_one_time_prekeys: Arc<UploadOneTimePreKeys>,
+    pub get_prekey_count: Arc<GetPreKeyCount>,
+    pub claim_prekey_bundles: Arc<ClaimPreKeyBundles>,
+    pub get_key_history: Arc<GetKeyHistory>,
+    pub distribute_sender_key: Arc<DistributeSenderKey>,
+    pub get_sender_keys: Arc<GetSenderKeys>,
+    pub setup_backup_vault: Arc<SetupBackupVault>,
+    pub unlock_backup_vault: Arc<UnlockBackupVault>,
+    pub create_backup: Arc<CreateBackup>,
+    pub complete_backup: Arc<CompleteBackup>,
+    pub list_backups: Arc<ListBackups>,
+    pub restore_backup: Arc<RestoreBackup>,
+    pub delete_backup: Arc<DeleteBackup>,
+    pub disable_backups: Arc<DisableBackups>,
+    pub start_call: Arc<StartCall>,
+    pub answer_call: Arc<AnswerCall>,
+    pub end_call: Arc<EndCall>,
+    pub get_active_call: Arc<GetActiveCall>,
+    pub track_call_presence: Arc<TrackCallPresence>,
+    pub get_ice_servers: Arc<GetIceServers>,
+    pub join_group_call: Arc<JoinGroupCall>,
+    pub leave_group_call: Arc<LeaveGroupCall>,
+    pub get_group_call: Arc<GetGroupCall>,
     pub update_location: Arc<UpdateLocation>,
     pub find_nearby_users: Arc<FindNearbyUsers>,
+    pub set_discoverability: Arc<SetDiscoverability>,
+    pub block_user: Arc<BlockUser>,
+    pub unblock_user: Arc<UnblockUser>,
+    pub start_live_location: Arc<StartLiveLocation>,
+    pub update_live_location: Arc<UpdateLiveLocation>,
+    pub stop_live_location: Arc<StopLiveLocation>,
+    pub get_live_locations: Arc<GetLiveLocations>,
     pub upgrade_subscription: Arc<UpgradeSubscription>,
+    pub get_subscription: Arc<GetSubscription>,
+    pub cancel_subscription: Arc<CancelSubscription>,
+    pub handle_payment_webhook: Arc<HandlePaymentWebhook>,
     pub register_device_token: Arc<RegisterDeviceToken>,
-    pub tx: broadcast::Sender<String>,
+    pub tx: broadcast::Sender<ServerEvent>,
 }
 
 pub async fn register(
@@ -95,11 +183,15 @@ pub async fn upload_public_key(
     
     let user_id = current_user.id;
 
-    state
+    let events = state
         .upload_public_key
-        .execute(user_id, payload.public_key)
+        .execute(user_id, payload.public_key, payload.signature, payload.otp)
         .await?;
 
+    for event in events {
+        let _ = state.tx.send(ServerEvent::sync_event(&event));
+    }
+
     Ok(StatusCode::OK)
 }
 
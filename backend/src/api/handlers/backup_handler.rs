@@ -0,0 +1,111 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+    Extension,
+};
+use crate::api::middleware::auth_middleware::CurrentUser;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::{
+    BackupVaultRequest, SetupBackupVaultRequest, BackupSecretResponse, CreateBackupRequest, BackupResponse,
+    BackupUploadResponse, BackupDownloadResponse,
+};
+use crate::api::handlers::{AppError, AppState};
+
+pub async fn setup_backup_vault(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Json(payload): Json<SetupBackupVaultRequest>,
+) -> Result<Json<BackupSecretResponse>, AppError> {
+    payload.validate()?;
+
+    let secret = state
+        .setup_backup_vault
+        .execute(current_user.id, payload.access_key, payload.current_access_key, payload.password)
+        .await?;
+
+    Ok(Json(BackupSecretResponse { secret }))
+}
+
+pub async fn unlock_backup_vault(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Json(payload): Json<BackupVaultRequest>,
+) -> Result<Json<BackupSecretResponse>, AppError> {
+    payload.validate()?;
+
+    let secret = state
+        .unlock_backup_vault
+        .execute(current_user.id, payload.access_key)
+        .await?;
+
+    Ok(Json(BackupSecretResponse { secret }))
+}
+
+pub async fn create_backup(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Json(payload): Json<CreateBackupRequest>,
+) -> Result<Json<BackupUploadResponse>, AppError> {
+    payload.validate()?;
+
+    let (backup, upload_url) = state
+        .create_backup
+        .execute(current_user.id, payload.size_bytes)
+        .await?;
+
+    Ok(Json(BackupUploadResponse {
+        backup: backup.into(),
+        upload_url,
+    }))
+}
+
+pub async fn complete_backup(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(backup_id): Path<Uuid>,
+) -> Result<Json<BackupResponse>, AppError> {
+    let backup = state.complete_backup.execute(current_user.id, backup_id).await?;
+    Ok(Json(backup.into()))
+}
+
+pub async fn list_backups(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> Result<Json<Vec<BackupResponse>>, AppError> {
+    let backups = state.list_backups.execute(current_user.id).await?;
+    Ok(Json(backups.into_iter().map(Into::into).collect()))
+}
+
+pub async fn restore_backup(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(backup_id): Path<Uuid>,
+) -> Result<Json<BackupDownloadResponse>, AppError> {
+    let (backup, download_url) = state.restore_backup.execute(current_user.id, backup_id).await?;
+
+    Ok(Json(BackupDownloadResponse {
+        backup: backup.into(),
+        download_url,
+    }))
+}
+
+pub async fn delete_backup(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(backup_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state.delete_backup.execute(current_user.id, backup_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn disable_backups(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> Result<StatusCode, AppError> {
+    state.disable_backups.execute(current_user.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
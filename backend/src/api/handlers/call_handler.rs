@@ -0,0 +1,73 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+    Extension,
+};
+use crate::api::middleware::auth_middleware::CurrentUser;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::{
+    IceServersResponse, JoinGroupCallRequest, GroupCallResponse, GroupCallStatusResponse,
+    GroupCallJoinResponse, ServerEvent,
+};
+use crate::api::handlers::{AppError, AppState};
+
+pub async fn get_ice_servers(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> Result<Json<IceServersResponse>, AppError> {
+    let response = state.get_ice_servers.execute(current_user.id).await?;
+    Ok(Json(response))
+}
+
+pub async fn get_group_call(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<Json<GroupCallStatusResponse>, AppError> {
+    let call = state.get_group_call.execute(current_user.id, conversation_id).await?;
+
+    Ok(Json(GroupCallStatusResponse {
+        call: call.map(|(call, participants)| GroupCallResponse::new(call, participants)),
+    }))
+}
+
+pub async fn join_group_call(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(payload): Json<JoinGroupCallRequest>,
+) -> Result<Json<GroupCallJoinResponse>, AppError> {
+    let (join, events) = state
+        .join_group_call
+        .execute(current_user.id, conversation_id, payload.is_video)
+        .await?;
+
+    for event in events {
+        let _ = state.tx.send(ServerEvent::sync_event(&event));
+    }
+
+    Ok(Json(GroupCallJoinResponse {
+        call: GroupCallResponse::new(join.call, join.participants),
+        sfu_url: join.sfu_url,
+        token: join.token,
+        can_publish: join.grants.can_publish,
+        can_subscribe: join.grants.can_subscribe,
+    }))
+}
+
+pub async fn leave_group_call(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let events = state.leave_group_call.execute(current_user.id, conversation_id).await?;
+
+    for event in events {
+        let _ = state.tx.send(ServerEvent::sync_event(&event));
+    }
+
+    Ok(StatusCode::OK)
+}
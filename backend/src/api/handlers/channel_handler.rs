@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+    Extension,
+};
+use crate::api::middleware::auth_middleware::CurrentUser;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::{CreateChannelRequest, ChannelResponse, ChannelViewsRequest, MessageViewsResponse};
+use crate::api::handlers::{AppError, AppState};
+
+pub async fn create_channel(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Json(payload): Json<CreateChannelRequest>,
+) -> Result<(StatusCode, Json<ChannelResponse>), AppError> {
+    payload.validate()?;
+
+    let channel = state
+        .create_channel
+        .execute(current_user.id, payload.name)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(ChannelResponse {
+        id: channel.id,
+        name: channel.name,
+        avatar_url: channel.avatar_url,
+        created_at: channel.created_at,
+    })))
+}
+
+pub async fn subscribe_channel(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(channel_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state
+        .subscribe_channel
+        .execute(current_user.id, channel_id)
+        .await?;
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn unsubscribe_channel(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(channel_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state
+        .unsubscribe_channel
+        .execute(current_user.id, channel_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_channel_views(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(channel_id): Path<Uuid>,
+    Json(payload): Json<ChannelViewsRequest>,
+) -> Result<Json<Vec<MessageViewsResponse>>, AppError> {
+    payload.validate()?;
+
+    let views = state
+        .get_channel_views
+        .execute(current_user.id, channel_id, payload.message_ids)
+        .await?;
+
+    Ok(Json(
+        views
+            .into_iter()
+            .map(|(message_id, view_count)| MessageViewsResponse { message_id, view_count })
+            .collect(),
+    ))
+}
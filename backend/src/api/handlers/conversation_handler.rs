@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+    Extension,
+};
+use crate::api::middleware::auth_middleware::CurrentUser;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::{DisappearingMessagesRequest, ConversationSettingsResponse};
+use crate::api::handlers::{AppError, AppState};
+use crate::domain::entities::DisappearingMessages;
+
+pub async fn set_disappearing_messages(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(payload): Json<DisappearingMessagesRequest>,
+) -> Result<Json<ConversationSettingsResponse>, AppError> {
+    payload.validate()?;
+
+    let setting = payload.timer_seconds.map(|timer_seconds| DisappearingMessages {
+        timer_seconds,
+        start_on_read: payload.start_on_read,
+    });
+
+    let conversation = state
+        .set_disappearing_messages
+        .execute(current_user.id, conversation_id, setting)
+        .await?;
+
+    Ok(Json(ConversationSettingsResponse {
+        conversation_id: conversation.id,
+        settings: conversation.settings,
+    }))
+}
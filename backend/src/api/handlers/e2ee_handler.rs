@@ -0,0 +1,166 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+    Extension,
+};
+use crate::api::middleware::auth_middleware::CurrentUser;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::{
+    RegisterDeviceKeysRequest, UploadPreKeysRequest, PreKeyCountResponse, PreKeyBundlesResponse,
+    KeyHistoryResponse, DistributeSenderKeyRequest, SenderKeysParams, SenderKeysResponse,
+    PreKeysLowFrame, ServerEvent, KeyRotationProof,
+};
+use crate::api::handlers::{AppError, AppState};
+
+/// Device ids share the 100 character limit of sync cursors.
+fn validate_device_id(device_id: &str) -> Result<(), AppError> {
+    if device_id.is_empty() || device_id.len() > 100 {
+        return Err(AppError::ValidationError("Device id must be 1 to 100 characters".to_string()));
+    }
+    Ok(())
+}
+
+pub async fn register_device_keys(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(device_id): Path<String>,
+    Json(payload): Json<RegisterDeviceKeysRequest>,
+) -> Result<StatusCode, AppError> {
+    validate_device_id(&device_id)?;
+    payload.validate()?;
+
+    let events = state
+        .register_device_keys
+        .execute(
+            current_user.id,
+            device_id,
+            payload.identity_key,
+            payload.signed_prekey.into(),
+            payload.one_time_prekeys.into_iter().map(Into::into).collect(),
+            KeyRotationProof {
+                signature: payload.signature,
+                otp: payload.otp,
+            },
+        )
+        .await?;
+
+    for event in events {
+        let _ = state.tx.send(ServerEvent::sync_event(&event));
+    }
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn upload_prekeys(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(device_id): Path<String>,
+    Json(payload): Json<UploadPreKeysRequest>,
+) -> Result<Json<PreKeyCountResponse>, AppError> {
+    validate_device_id(&device_id)?;
+    payload.validate()?;
+
+    let remaining = state
+        .upload_one_time_prekeys
+        .execute(
+            current_user.id,
+            device_id.clone(),
+            payload.one_time_prekeys.into_iter().map(Into::into).collect(),
+        )
+        .await?;
+
+    Ok(Json(PreKeyCountResponse { device_id, remaining }))
+}
+
+pub async fn get_prekey_count(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(device_id): Path<String>,
+) -> Result<Json<PreKeyCountResponse>, AppError> {
+    validate_device_id(&device_id)?;
+
+    let remaining = state
+        .get_prekey_count
+        .execute(current_user.id, device_id.clone())
+        .await?;
+
+    Ok(Json(PreKeyCountResponse { device_id, remaining }))
+}
+
+pub async fn claim_prekey_bundles(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<PreKeyBundlesResponse>, AppError> {
+    let claimed = state
+        .claim_prekey_bundles
+        .execute(current_user.id, user_id)
+        .await?;
+
+    // Prompt the owner's devices to replenish before sessions fall back to signed prekeys only
+    for (device_id, remaining) in claimed.low_supply {
+        let _ = state.tx.send(ServerEvent::PreKeysLow(PreKeysLowFrame { user_id, device_id, remaining }));
+    }
+
+    Ok(Json(PreKeyBundlesResponse {
+        user_id,
+        devices: claimed.bundles.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn get_key_history(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<KeyHistoryResponse>, AppError> {
+    let entries = state
+        .get_key_history
+        .execute(user_id)
+        .await?;
+
+    Ok(Json(KeyHistoryResponse {
+        user_id,
+        entries: entries.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn distribute_sender_key(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(payload): Json<DistributeSenderKeyRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let event = state
+        .distribute_sender_key
+        .execute(current_user.id, conversation_id, payload)
+        .await?;
+
+    let _ = state.tx.send(ServerEvent::sync_event(&event));
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn get_sender_keys(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Query(params): Query<SenderKeysParams>,
+) -> Result<Json<SenderKeysResponse>, AppError> {
+    params.validate()?;
+
+    let (epoch, envelopes) = state
+        .get_sender_keys
+        .execute(current_user.id, conversation_id, &params.device_id, params.epoch)
+        .await?;
+
+    Ok(Json(SenderKeysResponse {
+        conversation_id,
+        epoch,
+        envelopes: envelopes.into_iter().map(Into::into).collect(),
+    }))
+}
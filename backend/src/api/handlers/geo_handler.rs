@@ -1,5 +1,6 @@
 use axum::{
-    extract::{State, Query, Extension},
+    extract::{Path, State, Query, Extension},
+    http::StatusCode,
     Json,
 };
 use crate::api::middleware::auth_middleware::CurrentUser;
@@ -8,11 +9,13 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::application::{
-    UpdateLocationRequest, FindNearbyRequest, UserLocationResponse,
-    UpdateLocation, FindNearbyUsers,
+    UpdateLocationRequest, FindNearbyRequest, DiscoverabilityRequest, NearbyCursor, NearbyUsersResponse,
+    StartLiveLocationRequest, LiveLocationResponse, LiveLocationTrackResponse, LocationPointResponse, ServerEvent,
 };
 use crate::api::handlers::{AppError, AppState};
 
+const DEFAULT_PAGE_SIZE: i64 = 50;
+
 pub async fn update_location(
     State(state): State<Arc<AppState>>,
     Extension(current_user): Extension<CurrentUser>,
@@ -27,19 +30,127 @@ pub async fn update_location(
         .execute(user_id, payload.latitude, payload.longitude)
         .await?;
 
+    // Geofences are checked against the exact position; only the fuzzed one is stored
+    let events = state
+        .enforce_geofences
+        .execute(user_id, payload.latitude, payload.longitude)
+        .await?;
+    for event in &events {
+        let _ = state.tx.send(ServerEvent::sync_event(event));
+    }
+
     Ok(Json(()))
 }
 
 pub async fn find_nearby(
     State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
     Query(payload): Query<FindNearbyRequest>,
-) -> Result<Json<Vec<UserLocationResponse>>, AppError> {
+) -> Result<Json<NearbyUsersResponse>, AppError> {
     payload.validate()?;
+    let limit = payload.limit.unwrap_or(DEFAULT_PAGE_SIZE);
 
     let users = state
         .find_nearby_users
-        .execute(payload.latitude, payload.longitude, payload.radius_km)
+        .execute(current_user.id, &payload, limit)
         .await?;
 
-    Ok(Json(users))
+    let next_cursor = if users.len() as i64 == limit {
+        users.last().map(|user| NearbyCursor {
+            after_distance_km: user.distance_km,
+            after_user_id: user.user_id,
+        })
+    } else {
+        None
+    };
+
+    Ok(Json(NearbyUsersResponse { users, next_cursor }))
+}
+
+pub async fn set_discoverability(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Json(payload): Json<DiscoverabilityRequest>,
+) -> Result<StatusCode, AppError> {
+    state
+        .set_discoverability
+        .execute(current_user.id, payload.discoverable)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn block_user(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state.block_user.execute(current_user.id, user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn unblock_user(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state.unblock_user.execute(current_user.id, user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn start_live_location(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(payload): Json<StartLiveLocationRequest>,
+) -> Result<(StatusCode, Json<LiveLocationResponse>), AppError> {
+    let (session, event) = state
+        .start_live_location
+        .execute(current_user.id, conversation_id, payload.duration_minutes)
+        .await?;
+
+    let _ = state.tx.send(ServerEvent::sync_event(&event));
+
+    Ok((StatusCode::CREATED, Json(LiveLocationResponse::from(&session))))
+}
+
+pub async fn get_live_locations(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<Json<Vec<LiveLocationResponse>>, AppError> {
+    let sessions = state
+        .get_live_locations
+        .execute(current_user.id, conversation_id)
+        .await?;
+
+    Ok(Json(sessions.iter().map(LiveLocationResponse::from).collect()))
+}
+
+pub async fn get_live_location_track(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(session_id): Path<Uuid>,
+) -> Result<Json<LiveLocationTrackResponse>, AppError> {
+    let (session, points) = state
+        .get_live_locations
+        .track(current_user.id, session_id)
+        .await?;
+
+    Ok(Json(LiveLocationTrackResponse {
+        session: LiveLocationResponse::from(&session),
+        points: points.iter().map(LocationPointResponse::from).collect(),
+    }))
+}
+
+pub async fn stop_live_location(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(session_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    if let Some(event) = state.stop_live_location.execute(current_user.id, session_id).await? {
+        let _ = state.tx.send(ServerEvent::sync_event(&event));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
 }
@@ -0,0 +1,393 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+    Extension,
+};
+use crate::api::middleware::auth_middleware::CurrentUser;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::{
+    CreateGroupRequest, AddGroupMembersRequest, ChangeMemberRoleRequest, TransferOwnershipRequest,
+    UpdateGroupInfoRequest, UpdateGroupSettingsRequest, GroupMemberResponse, GroupResponse,
+    RemoveGroupMemberParams, CreateGroupInviteRequest, GroupInviteResponse, InvitePreviewResponse,
+    JoinGroupResponse, JoinRequestResponse, ReviewJoinRequestRequest, GeofenceResponse,
+    NearbyGroupResponse,
+    JoinGroupResult, ServerEvent,
+};
+use crate::api::handlers::{AppError, AppState};
+use crate::domain::entities::{
+    Conversation, GeoPoint, Geofence, GroupInvite, Participant, ParticipantRole, SyncEvent,
+};
+
+fn group_response(conversation: Conversation, participants: Vec<Participant>) -> GroupResponse {
+    GroupResponse {
+        id: conversation.id,
+        name: conversation.name,
+        avatar_url: conversation.avatar_url,
+        settings: conversation.settings,
+        members: participants
+            .into_iter()
+            .map(|p| GroupMemberResponse {
+                user_id: p.user_id,
+                role: format!("{:?}", p.role),
+                joined_at: p.joined_at,
+            })
+            .collect(),
+        geofence: conversation.geofence.map(|geofence| GeofenceResponse {
+            latitude: geofence.center.latitude,
+            longitude: geofence.center.longitude,
+            radius_meters: geofence.radius_meters,
+            remove_on_exit: geofence.remove_on_exit,
+        }),
+        created_at: conversation.created_at,
+    }
+}
+
+fn invite_response(invite: GroupInvite) -> GroupInviteResponse {
+    GroupInviteResponse {
+        id: invite.id,
+        conversation_id: invite.conversation_id,
+        token: invite.token,
+        expires_at: invite.expires_at,
+        max_uses: invite.max_uses,
+        use_count: invite.use_count,
+        requires_approval: invite.requires_approval,
+        created_at: invite.created_at,
+    }
+}
+
+/// Pushes the system messages produced by a group change to connected clients.
+fn broadcast(state: &AppState, events: &[SyncEvent]) {
+    for event in events {
+        let _ = state.tx.send(ServerEvent::sync_event(event));
+    }
+}
+
+pub async fn create_group(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Json(payload): Json<CreateGroupRequest>,
+) -> Result<(StatusCode, Json<GroupResponse>), AppError> {
+    payload.validate()?;
+
+    let geofence = payload.geofence.map(|geofence| Geofence {
+        center: GeoPoint::new(geofence.latitude, geofence.longitude),
+        radius_meters: geofence.radius_meters,
+        remove_on_exit: geofence.remove_on_exit,
+    });
+
+    let (conversation, participants, event) = state
+        .create_group
+        .execute(current_user.id, payload.name, payload.member_ids, geofence)
+        .await?;
+
+    broadcast(&state, &[event]);
+
+    Ok((StatusCode::CREATED, Json(group_response(conversation, participants))))
+}
+
+pub async fn find_nearby_groups(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> Result<Json<Vec<NearbyGroupResponse>>, AppError> {
+    let groups = state
+        .find_nearby_groups
+        .execute(current_user.id)
+        .await?;
+
+    Ok(Json(
+        groups
+            .into_iter()
+            .map(|group| NearbyGroupResponse {
+                id: group.conversation.id,
+                radius_meters: group.conversation.geofence.map(|g| g.radius_meters).unwrap_or_default(),
+                name: group.conversation.name,
+                avatar_url: group.conversation.avatar_url,
+                member_count: group.member_count,
+                distance_km: group.distance_km,
+            })
+            .collect(),
+    ))
+}
+
+pub async fn join_nearby_group(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<Json<JoinGroupResponse>, AppError> {
+    let events = state
+        .join_nearby_group
+        .execute(current_user.id, conversation_id)
+        .await?;
+
+    broadcast(&state, &events);
+
+    Ok(Json(JoinGroupResponse {
+        conversation_id,
+        status: "Joined".to_string(),
+    }))
+}
+
+pub async fn get_group(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<Json<GroupResponse>, AppError> {
+    let (conversation, participants) = state
+        .get_group
+        .execute(current_user.id, conversation_id)
+        .await?;
+
+    Ok(Json(group_response(conversation, participants)))
+}
+
+pub async fn add_group_members(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(payload): Json<AddGroupMembersRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let events = state
+        .add_group_members
+        .execute(current_user.id, conversation_id, payload.user_ids)
+        .await?;
+
+    broadcast(&state, &events);
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn remove_group_member(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path((conversation_id, user_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<RemoveGroupMemberParams>,
+) -> Result<StatusCode, AppError> {
+    let events = state
+        .remove_group_member
+        .execute(current_user.id, conversation_id, user_id, params.ban)
+        .await?;
+
+    broadcast(&state, &events);
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn change_member_role(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path((conversation_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ChangeMemberRoleRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let role = match payload.role.as_str() {
+        "Admin" => ParticipantRole::Admin,
+        "Member" => ParticipantRole::Member,
+        _ => return Err(AppError::ValidationError("Role must be Admin or Member".to_string())),
+    };
+
+    let event = state
+        .change_member_role
+        .execute(current_user.id, conversation_id, user_id, role)
+        .await?;
+
+    broadcast(&state, &[event]);
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn transfer_group_ownership(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(payload): Json<TransferOwnershipRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let event = state
+        .transfer_group_ownership
+        .execute(current_user.id, conversation_id, payload.new_owner_id)
+        .await?;
+
+    broadcast(&state, &[event]);
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn update_group_info(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(payload): Json<UpdateGroupInfoRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let events = state
+        .update_group_info
+        .execute(current_user.id, conversation_id, payload.name, payload.avatar_url)
+        .await?;
+
+    broadcast(&state, &events);
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn update_group_settings(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(payload): Json<UpdateGroupSettingsRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    payload.validate()?;
+
+    let (conversation, event) = state
+        .update_group_settings
+        .execute(
+            current_user.id,
+            conversation_id,
+            payload.only_admins_can_post,
+            payload.forwarding_enabled,
+        )
+        .await?;
+
+    if let Some(event) = event {
+        broadcast(&state, &[event]);
+    }
+
+    Ok(Json(conversation.settings))
+}
+
+pub async fn create_group_invite(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(payload): Json<CreateGroupInviteRequest>,
+) -> Result<(StatusCode, Json<GroupInviteResponse>), AppError> {
+    payload.validate()?;
+
+    let invite = state
+        .create_group_invite
+        .execute(
+            current_user.id,
+            conversation_id,
+            payload.expires_in_seconds,
+            payload.max_uses,
+            payload.requires_approval,
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(invite_response(invite))))
+}
+
+pub async fn list_group_invites(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<Json<Vec<GroupInviteResponse>>, AppError> {
+    let invites = state
+        .list_group_invites
+        .execute(current_user.id, conversation_id)
+        .await?;
+
+    Ok(Json(invites.into_iter().map(invite_response).collect()))
+}
+
+pub async fn revoke_group_invite(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path((conversation_id, invite_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    state
+        .revoke_group_invite
+        .execute(current_user.id, conversation_id, invite_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn preview_group_invite(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<Json<InvitePreviewResponse>, AppError> {
+    let (invite, conversation, member_count) = state
+        .preview_group_invite
+        .execute(&token)
+        .await?;
+
+    Ok(Json(InvitePreviewResponse {
+        conversation_id: conversation.id,
+        name: conversation.name,
+        avatar_url: conversation.avatar_url,
+        member_count,
+        requires_approval: invite.requires_approval,
+    }))
+}
+
+pub async fn join_group_via_invite(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(token): Path<String>,
+) -> Result<Json<JoinGroupResponse>, AppError> {
+    let (conversation_id, status) = match state
+        .join_group_via_invite
+        .execute(current_user.id, &token)
+        .await?
+    {
+        JoinGroupResult::Joined(conversation_id, events) => {
+            broadcast(&state, &events);
+            (conversation_id, "Joined")
+        }
+        JoinGroupResult::PendingApproval(conversation_id) => (conversation_id, "PendingApproval"),
+    };
+
+    Ok(Json(JoinGroupResponse {
+        conversation_id,
+        status: status.to_string(),
+    }))
+}
+
+pub async fn list_join_requests(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<Json<Vec<JoinRequestResponse>>, AppError> {
+    let requests = state
+        .list_join_requests
+        .execute(current_user.id, conversation_id)
+        .await?;
+
+    Ok(Json(
+        requests
+            .into_iter()
+            .map(|r| JoinRequestResponse {
+                user_id: r.user_id,
+                invite_id: r.invite_id,
+                created_at: r.created_at,
+            })
+            .collect(),
+    ))
+}
+
+pub async fn review_join_request(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path((conversation_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ReviewJoinRequestRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let events = state
+        .review_join_request
+        .execute(current_user.id, conversation_id, user_id, payload.approve)
+        .await?;
+
+    broadcast(&state, &events);
+
+    Ok(StatusCode::OK)
+}
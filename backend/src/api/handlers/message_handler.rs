@@ -0,0 +1,196 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+    Extension,
+};
+use crate::api::middleware::auth_middleware::CurrentUser;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::{
+    MessageHistoryParams, MessageHistoryResponse, MessageResponse, ThreadParams, ThreadResponse,
+    HistoryCursor, ThreadCursor,
+    ThreadSummaryResponse, StarredMessagesParams, StarredMessageResponse, StarredMessagesResponse,
+    ServerEvent,
+};
+use crate::api::handlers::{AppError, AppState};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+
+pub async fn get_message_history(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+    Query(params): Query<MessageHistoryParams>,
+) -> Result<Json<MessageHistoryResponse>, AppError> {
+    params.validate()?;
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+
+    // Without an id the cursor covers the whole timestamp, as before ids were part of it
+    let before = params.before.map(|before| (before, params.before_id.unwrap_or(Uuid::nil())));
+
+    let history = state
+        .get_message_history
+        .execute(current_user.id, conversation_id, before, limit)
+        .await?;
+
+    let next_cursor = if history.len() as i64 == limit {
+        history.last().map(|(message, _)| HistoryCursor {
+            before: message.created_at,
+            before_id: message.id,
+        })
+    } else {
+        None
+    };
+
+    let messages = history
+        .iter()
+        .map(|(message, thread)| {
+            let mut response = MessageResponse::from(message);
+            if thread.reply_count > 0 {
+                response.thread = Some(ThreadSummaryResponse {
+                    reply_count: thread.reply_count,
+                    last_reply_at: thread.last_reply_at,
+                    last_replier_id: thread.last_replier_id,
+                });
+            }
+            response
+        })
+        .collect();
+
+    Ok(Json(MessageHistoryResponse { messages, next_cursor }))
+}
+
+pub async fn get_thread(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(message_id): Path<Uuid>,
+    Query(params): Query<ThreadParams>,
+) -> Result<Json<ThreadResponse>, AppError> {
+    params.validate()?;
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+
+    // Without an id the cursor covers the whole timestamp, as before ids were part of it
+    let after = params.after.map(|after| (after, params.after_id.unwrap_or(Uuid::max())));
+
+    let (root, replies) = state
+        .get_thread
+        .execute(current_user.id, message_id, after, limit)
+        .await?;
+
+    let next_cursor = if replies.len() as i64 == limit {
+        replies.last().map(|reply| ThreadCursor {
+            after: reply.created_at,
+            after_id: reply.id,
+        })
+    } else {
+        None
+    };
+
+    Ok(Json(ThreadResponse {
+        root: MessageResponse::from(&root),
+        replies: replies.iter().map(MessageResponse::from).collect(),
+        next_cursor,
+    }))
+}
+
+pub async fn pin_message(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(message_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let event = state
+        .pin_message
+        .execute(current_user.id, message_id)
+        .await?;
+
+    let _ = state.tx.send(ServerEvent::sync_event(&event));
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn unpin_message(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(message_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let event = state
+        .unpin_message
+        .execute(current_user.id, message_id)
+        .await?;
+
+    let _ = state.tx.send(ServerEvent::sync_event(&event));
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_pinned_messages(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<Json<Vec<MessageResponse>>, AppError> {
+    let messages = state
+        .get_pinned_messages
+        .execute(current_user.id, conversation_id)
+        .await?;
+
+    Ok(Json(messages.iter().map(MessageResponse::from).collect()))
+}
+
+pub async fn star_message(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(message_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state
+        .star_message
+        .execute(current_user.id, message_id)
+        .await?;
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn unstar_message(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(message_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state
+        .unstar_message
+        .execute(current_user.id, message_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_starred_messages(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Query(params): Query<StarredMessagesParams>,
+) -> Result<Json<StarredMessagesResponse>, AppError> {
+    params.validate()?;
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+
+    let starred = state
+        .get_starred_messages
+        .execute(current_user.id, params.before, limit)
+        .await?;
+
+    let next_cursor = if starred.len() as i64 == limit {
+        starred.last().map(|(_, starred_at)| *starred_at)
+    } else {
+        None
+    };
+
+    let messages = starred
+        .iter()
+        .map(|(message, starred_at)| StarredMessageResponse {
+            message: MessageResponse::from(message),
+            starred_at: *starred_at,
+        })
+        .collect();
+
+    Ok(Json(StarredMessagesResponse { messages, next_cursor }))
+}
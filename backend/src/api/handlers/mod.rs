@@ -3,9 +3,40 @@ pub mod kyc_handler;
 pub mod geo_handler;
 pub mod subscription_handler;
 pub mod notification_handler;
+pub mod conversation_handler;
+pub mod group_handler;
+pub mod channel_handler;
+pub mod message_handler;
+pub mod e2ee_handler;
+pub mod backup_handler;
+pub mod call_handler;
 
 pub use auth_handler::{login, register, AppState, AppError};
 pub use kyc_handler::{get_upload_url, submit_kyc, review_kyc};
-pub use geo_handler::{update_location, find_nearby};
-pub use subscription_handler::upgrade_subscription;
+pub use geo_handler::{
+    update_location, find_nearby, set_discoverability, block_user, unblock_user,
+    start_live_location, get_live_locations, get_live_location_track, stop_live_location,
+};
+pub use subscription_handler::{get_subscription, upgrade_subscription, cancel_subscription, payment_webhook};
 pub use notification_handler::register_device_token;
+pub use conversation_handler::set_disappearing_messages;
+pub use group_handler::{
+    create_group, get_group, add_group_members, remove_group_member, change_member_role,
+    transfer_group_ownership, update_group_info, update_group_settings,
+    create_group_invite, list_group_invites, revoke_group_invite, preview_group_invite,
+    join_group_via_invite, list_join_requests, review_join_request, find_nearby_groups, join_nearby_group,
+};
+pub use channel_handler::{create_channel, subscribe_channel, unsubscribe_channel, get_channel_views};
+pub use message_handler::{
+    get_message_history, get_thread, pin_message, unpin_message, get_pinned_messages,
+    star_message, unstar_message, get_starred_messages,
+};
+pub use e2ee_handler::{
+    register_device_keys, upload_prekeys, get_prekey_count, claim_prekey_bundles, get_key_history,
+    distribute_sender_key, get_sender_keys,
+};
+pub use backup_handler::{
+    setup_backup_vault, unlock_backup_vault, create_backup, complete_backup, list_backups,
+    restore_backup, delete_backup, disable_backups,
+};
+pub use call_handler::{get_ice_servers, get_group_call, join_group_call, leave_group_call};
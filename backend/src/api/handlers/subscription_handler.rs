@@ -1,16 +1,45 @@
 use axum::{
+    body::Bytes,
     extract::{State, Json, Extension},
+    http::{HeaderMap, StatusCode},
 };
+use chrono::Utc;
 use crate::api::middleware::auth_middleware::CurrentUser;
 use std::sync::Arc;
-use uuid::Uuid;
 use validator::Validate;
 
-use crate::application::{
-    UpgradeSubscriptionRequest, SubscriptionResponse,
-    UpgradeSubscription,
-};
+use crate::application::{UpgradeSubscriptionRequest, SubscriptionResponse};
 use crate::api::handlers::{AppError, AppState};
+use crate::domain::entities::{Subscription, SubscriptionTier};
+
+fn subscription_response(subscription: Option<Subscription>) -> SubscriptionResponse {
+    let tier = subscription
+        .as_ref()
+        .filter(|subscription| subscription.is_entitled(Utc::now()))
+        .map(|subscription| subscription.tier)
+        .unwrap_or(SubscriptionTier::Free);
+
+    SubscriptionResponse {
+        tier: format!("{:?}", tier),
+        status: subscription.as_ref().map(|s| format!("{:?}", s.status)),
+        current_period_start: subscription.as_ref().map(|s| s.current_period_start),
+        current_period_end: subscription.as_ref().map(|s| s.current_period_end),
+        cancel_at_period_end: subscription.as_ref().map_or(false, |s| s.cancel_at_period_end),
+        features: tier.features(),
+    }
+}
+
+pub async fn get_subscription(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> Result<Json<SubscriptionResponse>, AppError> {
+    let subscription = state
+        .get_subscription
+        .execute(current_user.id)
+        .await?;
+
+    Ok(Json(subscription_response(subscription)))
+}
 
 pub async fn upgrade_subscription(
     State(state): State<Arc<AppState>>,
@@ -18,13 +47,49 @@ pub async fn upgrade_subscription(
     Json(payload): Json<UpgradeSubscriptionRequest>,
 ) -> Result<Json<SubscriptionResponse>, AppError> {
     payload.validate()?;
-    
-    let user_id = current_user.id;
 
-    let response = state
+    let tier = match payload.tier.as_str() {
+        "Monthly" => SubscriptionTier::Monthly,
+        "Yearly" => SubscriptionTier::Yearly,
+        _ => return Err(AppError::ValidationError("Tier must be Monthly or Yearly".to_string())),
+    };
+
+    let subscription = state
         .upgrade_subscription
-        .execute(user_id, payload.tier)
+        .execute(current_user.id, tier, &payload.payment_token)
+        .await?;
+
+    Ok(Json(subscription_response(Some(subscription))))
+}
+
+pub async fn cancel_subscription(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> Result<Json<SubscriptionResponse>, AppError> {
+    let subscription = state
+        .cancel_subscription
+        .execute(current_user.id)
+        .await?;
+
+    Ok(Json(subscription_response(Some(subscription))))
+}
+
+/// Receives billing events from the payment provider. Unauthenticated; deliveries are trusted
+/// only once their signature checks out.
+pub async fn payment_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, AppError> {
+    let signature = headers
+        .get(state.handle_payment_webhook.signature_header())
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| AppError::AuthError("Missing webhook signature".to_string()))?;
+
+    state
+        .handle_payment_webhook
+        .execute(&body, signature)
         .await?;
 
-    Ok(Json(response))
+    Ok(StatusCode::OK)
 }
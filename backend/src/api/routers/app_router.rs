@@ -1,4 +1,4 @@
-use axum::{routing::post, Router};
+use axum::{routing::{delete, get, patch, post, put}, Router};
 use std::sync::Arc;
 use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
 
@@ -14,15 +14,66 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         // Protected Routes
         .route("/api/keys/upload", post(super::handlers::upload_public_key))
         .route("/api/users/:id/key", axum::routing::get(super::handlers::get_public_key))
+        .route("/api/keys/devices/:device_id", put(super::handlers::register_device_keys))
+        .route("/api/keys/devices/:device_id/prekeys", post(super::handlers::upload_prekeys).get(super::handlers::get_prekey_count))
+        .route("/api/users/:id/prekey-bundles", post(super::handlers::claim_prekey_bundles))
+        .route("/api/users/:id/key-history", get(super::handlers::get_key_history))
+        .route("/api/backups/vault", put(super::handlers::setup_backup_vault))
+        .route("/api/backups/vault/unlock", post(super::handlers::unlock_backup_vault))
+        .route("/api/backups", post(super::handlers::create_backup).get(super::handlers::list_backups).delete(super::handlers::disable_backups))
+        .route("/api/backups/:id", delete(super::handlers::delete_backup))
+        .route("/api/backups/:id/complete", post(super::handlers::complete_backup))
+        .route("/api/backups/:id/restore", get(super::handlers::restore_backup))
+        .route("/api/calls/ice-servers", get(super::handlers::get_ice_servers))
         .route("/api/kyc/upload-url", post(super::handlers::get_upload_url))
         .route("/api/kyc/submit", post(super::handlers::submit_kyc))
         .route("/api/admin/kyc/:id/review", post(super::handlers::review_kyc))
         .route("/api/geo/location", post(super::handlers::update_location))
         .route("/api/geo/nearby", axum::routing::get(super::handlers::find_nearby))
+        .route("/api/geo/discoverability", put(super::handlers::set_discoverability))
+        .route("/api/geo/blocks/:id", post(super::handlers::block_user).delete(super::handlers::unblock_user))
+        .route("/api/conversations/:id/live-locations", post(super::handlers::start_live_location).get(super::handlers::get_live_locations))
+        .route("/api/live-locations/:id", get(super::handlers::get_live_location_track).delete(super::handlers::stop_live_location))
+        .route("/api/subscriptions", get(super::handlers::get_subscription))
         .route("/api/subscriptions/upgrade", post(super::handlers::upgrade_subscription))
+        .route("/api/subscriptions/cancel", post(super::handlers::cancel_subscription))
         .route("/api/notifications/device-token", post(super::handlers::register_device_token))
+        .route("/api/conversations/:id/disappearing-messages", put(super::handlers::set_disappearing_messages))
+        .route("/api/conversations/:id/messages", get(super::handlers::get_message_history))
+        .route("/api/messages/:id/thread", get(super::handlers::get_thread))
+        .route("/api/conversations/:id/pins", get(super::handlers::get_pinned_messages))
+        .route("/api/messages/:id/pin", post(super::handlers::pin_message).delete(super::handlers::unpin_message))
+        .route("/api/messages/:id/star", post(super::handlers::star_message).delete(super::handlers::unstar_message))
+        .route("/api/starred", get(super::handlers::get_starred_messages))
+        .route("/api/groups", post(super::handlers::create_group))
+        .route("/api/groups/nearby", get(super::handlers::find_nearby_groups))
+        .route("/api/groups/:id", get(super::handlers::get_group).patch(super::handlers::update_group_info))
+        .route("/api/groups/:id/settings", patch(super::handlers::update_group_settings))
+        .route("/api/groups/:id/members", post(super::handlers::add_group_members))
+        .route("/api/groups/:id/members/:user_id", delete(super::handlers::remove_group_member))
+        .route("/api/groups/:id/members/:user_id/role", put(super::handlers::change_member_role))
+        .route("/api/groups/:id/owner", put(super::handlers::transfer_group_ownership))
+        .route("/api/groups/:id/sender-keys", post(super::handlers::distribute_sender_key).get(super::handlers::get_sender_keys))
+        .route("/api/groups/:id/call", get(super::handlers::get_group_call))
+        .route("/api/groups/:id/call/join", post(super::handlers::join_group_call))
+        .route("/api/groups/:id/call/leave", post(super::handlers::leave_group_call))
+        .route("/api/groups/:id/invites", post(super::handlers::create_group_invite).get(super::handlers::list_group_invites))
+        .route("/api/groups/:id/invites/:invite_id", delete(super::handlers::revoke_group_invite))
+        .route("/api/groups/:id/join-nearby", post(super::handlers::join_nearby_group))
+        .route("/api/groups/:id/join-requests", get(super::handlers::list_join_requests))
+        .route("/api/groups/:id/join-requests/:user_id", post(super::handlers::review_join_request))
+        .route("/api/invites/:token", get(super::handlers::preview_group_invite))
+        .route("/api/invites/:token/join", post(super::handlers::join_group_via_invite))
+        .route("/api/channels", post(super::handlers::create_channel))
+        .route("/api/channels/:id/subscription", post(super::handlers::subscribe_channel).delete(super::handlers::unsubscribe_channel))
+        .route("/api/channels/:id/views", post(super::handlers::get_channel_views))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), crate::api::middleware::auth_middleware::auth_middleware))
+        // Payment provider webhooks, authenticated by their signature
+        .route("/api/webhooks/payments", post(super::handlers::payment_webhook))
         // WebSocket
         .route("/ws", axum::routing::get(crate::api::ws::ws_handler))
+        // Public on purpose: the schema is static, holds no user data and is fetched by client
+        // code generators at build time, where no session exists
+        .route("/api/ws/schema", axum::routing::get(crate::api::ws::protocol_schema))
         .with_state(state)
 }
@@ -1,21 +1,46 @@
 use axum::{
-    extract::{ws::{Message, WebSocket, WebSocketUpgrade}, State, Query},
+    extract::{ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade}, State, Query},
     response::IntoResponse,
+    Json,
 };
 use futures::{sink::SinkExt, stream::StreamExt};
 use serde::Deserialize;
-use std::sync::Arc;
-use tokio::sync::broadcast;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
 use uuid::Uuid;
 use jsonwebtoken::{decode, DecodingKey, Validation};
+use validator::Validate;
 
 use crate::api::handlers::AppState;
-use crate::application::{WebSocketMessage, SendMessageRequest};
+use crate::api::ws::codec::{self, WireEncoding, JSON_SUBPROTOCOL, MSGPACK_SUBPROTOCOL};
+use crate::application::{
+    ClientEvent, ServerEvent, HelloFrame, ErrorFrame, ProtocolSchemaResponse,
+    MessageAck, MessageNack, IceCandidateFrame,
+    PROTOCOL_VERSION, MIN_PROTOCOL_VERSION,
+};
 use crate::api::middleware::auth_middleware::Claims;
+use crate::domain::DomainError;
+
+/// Number of sync events loaded per query while replaying a reconnecting device.
+const REPLAY_PAGE_SIZE: i64 = 500;
+/// How often the server pings each connection.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+/// Connections that send nothing (not even a pong) for this long are closed.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(90);
 
 #[derive(Deserialize)]
 pub struct WsParams {
     token: String,
+    /// Stable identifier of the connecting device, used to look up its stored sync cursor.
+    device_id: Option<String>,
+    /// Last sync sequence number the client has seen; overrides the stored cursor.
+    since: Option<i64>,
+    /// Highest protocol version the client speaks; defaults to the current version.
+    protocol_version: Option<u32>,
 }
 
 pub async fn ws_handler(
@@ -41,80 +66,204 @@ pub async fn ws_handler(
         }
     };
 
+    // Negotiate the protocol: speak the highest version both sides support
+    let requested_version = params.protocol_version.unwrap_or(PROTOCOL_VERSION);
+    if requested_version < MIN_PROTOCOL_VERSION {
+        return axum::http::Response::builder()
+            .status(400)
+            .body(axum::body::Body::from(format!(
+                "Unsupported protocol version {}; minimum is {}",
+                requested_version, MIN_PROTOCOL_VERSION
+            )))
+            .unwrap()
+            .into_response();
+    }
+    let protocol_version = requested_version.min(PROTOCOL_VERSION);
+
     let user_id = token_data.claims.sub;
-    
-    ws.on_upgrade(move |socket| handle_socket(socket, state, user_id))
+
+    // Prefer the compact binary encoding when the client offers it
+    ws.protocols([MSGPACK_SUBPROTOCOL, JSON_SUBPROTOCOL])
+        .on_upgrade(move |socket| {
+            handle_socket(socket, state, user_id, params.device_id, params.since, protocol_version)
+        })
+}
+
+/// JSON Schema of every client and server frame, used to generate the app's protocol models.
+/// Served without authentication; it only describes the protocol, which the client ships anyway.
+pub async fn protocol_schema() -> Json<ProtocolSchemaResponse> {
+    Json(ProtocolSchemaResponse {
+        protocol_version: PROTOCOL_VERSION,
+        min_protocol_version: MIN_PROTOCOL_VERSION,
+        client_events: schemars::schema_for!(ClientEvent),
+        server_events: schemars::schema_for!(ServerEvent),
+    })
+}
+
+fn nack_frame(client_message_id: Option<String>, error: &DomainError) -> ServerEvent {
+    // Internal details stay in the logs; the client only needs the code
+    let message = match error {
+        DomainError::InternalError(_) => "Internal error".to_string(),
+        other => other.to_string(),
+    };
+    ServerEvent::Nack(MessageNack {
+        client_message_id,
+        code: error.code().to_string(),
+        error: message,
+    })
+}
+
+fn error_frame(code: &str, message: impl Into<String>) -> ServerEvent {
+    ServerEvent::Error(ErrorFrame {
+        code: code.to_string(),
+        message: message.into(),
+    })
 }
 
-async fn handle_socket(socket: WebSocket, state: Arc<AppState>, user_id: String) {
+async fn handle_socket(
+    socket: WebSocket,
+    state: Arc<AppState>,
+    user_id: String,
+    device_id: Option<String>,
+    since: Option<i64>,
+    protocol_version: u32,
+) {
+    let encoding = WireEncoding::from_subprotocol(
+        socket.protocol().and_then(|protocol| protocol.to_str().ok()),
+    );
     let (mut sender, mut receiver) = socket.split();
+    let user_uuid = Uuid::parse_str(&user_id).unwrap_or_default();
+
+    let hello = ServerEvent::Hello(HelloFrame {
+        protocol_version,
+        heartbeat_interval_secs: HEARTBEAT_INTERVAL.as_secs(),
+    });
+    if sender.send(encoding.encode(&hello)).await.is_err() {
+        return;
+    }
+
+    // Subscribe before replaying so nothing published during the replay is lost
     let mut rx = state.tx.subscribe();
 
+    // Replay everything the device missed, in order, before switching to live delivery
+    let mut last_replayed_seq = 0;
+    match state.get_missed_events.start_cursor(user_uuid, device_id.as_deref(), since).await {
+        Ok(Some(mut cursor)) => loop {
+            let events = match state.get_missed_events.execute(user_uuid, cursor, REPLAY_PAGE_SIZE).await {
+                Ok(events) => events,
+                Err(e) => {
+                    tracing::error!("Failed to replay sync events for {}: {}", user_id, e);
+                    break;
+                }
+            };
+
+            for event in &events {
+                if sender.send(encoding.encode(&ServerEvent::sync_event(event))).await.is_err() {
+                    return;
+                }
+                cursor = event.seq;
+            }
+            last_replayed_seq = cursor;
+
+            if (events.len() as i64) < REPLAY_PAGE_SIZE {
+                break;
+            }
+        },
+        Ok(None) => {}
+        Err(e) => tracing::error!("Failed to resolve sync cursor for {}: {}", user_id, e),
+    }
+
+    // Replies addressed only to this connection (acks, nacks, errors)
+    let (reply_tx, mut reply_rx) = mpsc::unbounded_channel::<ServerEvent>();
+
+    // Milliseconds since `connected_at` at which the client last sent any frame
+    let connected_at = Instant::now();
+    let last_activity = Arc::new(AtomicU64::new(0));
+    let send_last_activity = last_activity.clone();
+
     // Spawn a task to send messages to the client
     let mut send_task = tokio::spawn(async move {
-        while let Ok(msg) = rx.recv().await {
-            // In a real app, we would filter messages based on recipient
-            // For this MVP, we send all messages to all connected clients
-            if sender.send(Message::Text(msg)).await.is_err() {
+        let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+        heartbeat.tick().await;
+
+        loop {
+            let event = tokio::select! {
+                reply = reply_rx.recv() => match reply {
+                    Some(reply) => reply,
+                    None => break,
+                },
+                live = rx.recv() => match live {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("WebSocket client lagged, skipped {} broadcasts", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = heartbeat.tick() => {
+                    let idle_ms = (connected_at.elapsed().as_millis() as u64)
+                        .saturating_sub(send_last_activity.load(Ordering::Relaxed));
+                    if idle_ms > CLIENT_TIMEOUT.as_millis() as u64 {
+                        tracing::info!("Closing idle WebSocket connection");
+                        let _ = sender.send(Message::Close(Some(CloseFrame {
+                            code: axum::extract::ws::close_code::AWAY,
+                            reason: "heartbeat timeout".into(),
+                        }))).await;
+                        break;
+                    }
+                    if sender.send(Message::Ping(Vec::new())).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            // Skip live events that were already delivered by the replay above. Seqs commit in
+            // order, so everything past it was still in flight and arrives here
+            if let ServerEvent::SyncEvent(sync_event) = &event {
+                if sync_event.seq <= last_replayed_seq {
+                    continue;
+                }
+            }
+
+            // Frames carrying private data only go to the users they are addressed to
+            if !event.is_addressed_to(user_uuid) {
+                continue;
+            }
+
+            if sender.send(encoding.encode(&event)).await.is_err() {
                 break;
             }
         }
     });
 
     // Spawn a task to receive messages from the client
+    let recv_state = state.clone();
+    let recv_device_id = device_id.clone();
     let mut recv_task = tokio::spawn(async move {
+        let state = recv_state;
+        let device_id = recv_device_id;
         while let Some(Ok(msg)) = receiver.next().await {
+            last_activity.store(connected_at.elapsed().as_millis() as u64, Ordering::Relaxed);
+
             match msg {
-                Message::Text(text) => {
-                // Parse message
-                if let Ok(ws_msg) = serde_json::from_str::<WebSocketMessage>(&text) {
-                    match ws_msg.event_type.as_str() {
-                        "SendMessage" => {
-                            if let Ok(req) = serde_json::from_value::<SendMessageRequest>(ws_msg.payload) {
-                                // Persist message
-                                if let Ok(saved_msg) = state.send_message.execute(
-                                    Uuid::parse_str(&user_id).unwrap_or_default(),
-                                    req.conversation_id,
-                                    req.content,
-                                    req.message_type,
-                                    req.reply_to_id,
-                                    req.self_destruct_in_seconds
-                                ).await {
-                                    // Broadcast to others via Redis/Internal Channel
-                                    // For now, using internal broadcast channel
-                                    let _ = state.tx.send(serde_json::to_string(&saved_msg).unwrap_or_default());
-                                }
-                            }
-                        },
-                        "WebRtcSignal" => {
-                            // Relay WebRTC signaling messages directly to target user
-                            // In a real app, we would look up the specific connection for target_user_id
-                            // For this MVP with broadcast channel, we broadcast to everyone and clients filter
-                            if let Ok(signal) = serde_json::from_value::<WebRtcSignal>(ws_msg.payload) {
-                                // Wrap it back in a WebSocketMessage to send out
-                                let relay_msg = WebSocketMessage {
-                                    event_type: "WebRtcSignal".to_string(),
-                                    payload: serde_json::to_value(signal).unwrap_or_default(),
-                                };
-                                let _ = state.tx.send(serde_json::to_string(&relay_msg).unwrap_or_default());
-                            }
-                        },
-                        "SystemEvent" => {
-                            // Handle anti-screenshot, etc.
-                            let _ = state.tx.send(text);
-                        },
-                        _ => {}
+                Message::Text(_) | Message::Binary(_) => match codec::decode(&msg) {
+                    Some(Ok(event)) => {
+                        handle_client_event(&state, user_uuid, device_id.as_deref(), event, &reply_tx).await;
                     }
-                }
+                    Some(Err(e)) => {
+                        let _ = reply_tx.send(error_frame("MALFORMED_FRAME", e));
+                    }
+                    None => {}
                 },
-                Message::Binary(_) => {
-                    // Handle binary messages if needed
+                Message::Pong(_) => {
+                    // Answers to our heartbeat also keep the device's call alive
+                    if let Err(e) = state.track_call_presence.heartbeat(user_uuid, device_id.as_deref()).await {
+                        tracing::error!("Failed to record call heartbeat for {}: {}", user_uuid, e);
+                    }
                 },
                 Message::Ping(_) => {
-                    // Respond to pings
-                },
-                Message::Pong(_) => {
-                    // Handle pongs
+                    // Pings are answered by the WebSocket layer and only count as activity
                 },
                 Message::Close(_) => {
                     // Client disconnected
@@ -129,4 +278,193 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>, user_id: String)
         _ = (&mut send_task) => recv_task.abort(),
         _ = (&mut recv_task) => send_task.abort(),
     };
+
+    // A device that drops off hangs up its call, so neither party stays busy
+    match state.track_call_presence.disconnected(user_uuid, device_id.as_deref()).await {
+        Ok(Some((call, event))) => {
+            let _ = state.tx.send(ServerEvent::call_update(&call, None));
+            let _ = state.tx.send(ServerEvent::sync_event(&event));
+        }
+        Ok(None) => {}
+        Err(e) => tracing::error!("Failed to end call of disconnected {}: {}", user_id, e),
+    }
+}
+
+async fn handle_client_event(
+    state: &AppState,
+    user_id: Uuid,
+    device_id: Option<&str>,
+    event: ClientEvent,
+    reply_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    match event {
+        ClientEvent::SendMessage(req) => {
+            let client_message_id = req.client_message_id.clone();
+            if let Err(e) = req.validate() {
+                let error = DomainError::ValidationError(e.to_string());
+                let _ = reply_tx.send(nack_frame(client_message_id, &error));
+                return;
+            }
+
+            // Persist message
+            match state.send_message.execute(user_id, req).await {
+                Ok((saved_msg, event)) => {
+                    let _ = reply_tx.send(ServerEvent::Ack(MessageAck {
+                        client_message_id,
+                        id: saved_msg.id,
+                        created_at: saved_msg.created_at,
+                    }));
+
+                    // Retries of an already persisted message are acked but not re-broadcast
+                    if let Some(event) = event {
+                        // Broadcast to others via Redis/Internal Channel
+                        // For now, using internal broadcast channel
+                        let _ = state.tx.send(ServerEvent::sync_event(&event));
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to send message for {}: {}", user_id, e);
+                    let _ = reply_tx.send(nack_frame(client_message_id, &e));
+                }
+            }
+        },
+        ClientEvent::ForwardMessage(req) => {
+            if let Err(e) = req.validate() {
+                let _ = reply_tx.send(error_frame("VALIDATION_ERROR", e.to_string()));
+                return;
+            }
+
+            // Each forwarded copy is acked against the client id of its target
+            let client_message_ids: Vec<Option<String>> = req.targets.iter()
+                .map(|target| target.client_message_id.clone())
+                .collect();
+            match state.forward_message.execute(user_id, req).await {
+                Ok(results) => {
+                    for (client_message_id, result) in client_message_ids.into_iter().zip(results) {
+                        match result {
+                            Ok((saved_msg, event)) => {
+                                let _ = reply_tx.send(ServerEvent::Ack(MessageAck {
+                                    client_message_id,
+                                    id: saved_msg.id,
+                                    created_at: saved_msg.created_at,
+                                }));
+                                if let Some(event) = event {
+                                    let _ = state.tx.send(ServerEvent::sync_event(&event));
+                                }
+                            }
+                            Err(e) => {
+                                let _ = reply_tx.send(nack_frame(client_message_id, &e));
+                            }
+                        }
+                    }
+                }
+                Err(e) => { let _ = reply_tx.send(error_frame(e.code(), e.to_string())); }
+            }
+        },
+        ClientEvent::EditMessage(req) => {
+            if let Err(e) = req.validate() {
+                let _ = reply_tx.send(error_frame("VALIDATION_ERROR", e.to_string()));
+                return;
+            }
+            match state.edit_message.execute(user_id, req.message_id, req.content.into_inner()).await {
+                Ok(event) => { let _ = state.tx.send(ServerEvent::sync_event(&event)); }
+                Err(e) => { let _ = reply_tx.send(error_frame(e.code(), e.to_string())); }
+            }
+        },
+        ClientEvent::DeleteMessage(req) => {
+            match state.delete_message.execute(user_id, req.message_id).await {
+                Ok(event) => { let _ = state.tx.send(ServerEvent::sync_event(&event)); }
+                Err(e) => { let _ = reply_tx.send(error_frame(e.code(), e.to_string())); }
+            }
+        },
+        ClientEvent::MarkRead(req) => {
+            match state.mark_as_read.execute(user_id, req.message_id).await {
+                Ok(Some(event)) => { let _ = state.tx.send(ServerEvent::sync_event(&event)); }
+                Ok(None) => {}
+                Err(e) => { let _ = reply_tx.send(error_frame(e.code(), e.to_string())); }
+            }
+        },
+        ClientEvent::SyncAck(req) => {
+            // Persist the device's position so the next connection resumes from here
+            let Some(device_id) = device_id else {
+                let _ = reply_tx.send(error_frame("VALIDATION_ERROR", "SyncAck requires a device_id on connect"));
+                return;
+            };
+            if let Err(e) = state.acknowledge_sync.execute(user_id, device_id.to_string(), req.seq).await {
+                tracing::error!("Failed to store sync cursor for {}: {}", user_id, e);
+            }
+        },
+        ClientEvent::StartCall(req) => {
+            if let Err(e) = req.validate() {
+                let _ = reply_tx.send(error_frame("VALIDATION_ERROR", e.to_string()));
+                return;
+            }
+            match state.start_call.execute(user_id, device_id, &req).await {
+                Ok((call, event)) => {
+                    let _ = state.tx.send(ServerEvent::call_update(&call, Some(req.offer)));
+                    if let Some(event) = event {
+                        let _ = state.tx.send(ServerEvent::sync_event(&event));
+                    }
+                }
+                Err(e) => { let _ = reply_tx.send(error_frame(e.code(), e.to_string())); }
+            }
+        },
+        ClientEvent::AnswerCall(req) => {
+            if let Err(e) = req.validate() {
+                let _ = reply_tx.send(error_frame("VALIDATION_ERROR", e.to_string()));
+                return;
+            }
+            // Also tells the callee's other devices to stop ringing
+            match state.answer_call.execute(user_id, device_id, req.call_id).await {
+                Ok(call) => { let _ = state.tx.send(ServerEvent::call_update(&call, Some(req.answer))); }
+                Err(e) => { let _ = reply_tx.send(error_frame(e.code(), e.to_string())); }
+            }
+        },
+        ClientEvent::EndCall(req) => {
+            match state.end_call.execute(user_id, req.call_id).await {
+                Ok((call, event)) => {
+                    let _ = state.tx.send(ServerEvent::call_update(&call, None));
+                    let _ = state.tx.send(ServerEvent::sync_event(&event));
+                }
+                Err(e) => { let _ = reply_tx.send(error_frame(e.code(), e.to_string())); }
+            }
+        },
+        ClientEvent::IceCandidate(req) => {
+            if let Err(e) = req.validate() {
+                let _ = reply_tx.send(error_frame("VALIDATION_ERROR", e.to_string()));
+                return;
+            }
+            match state.get_active_call.execute(user_id, req.call_id).await {
+                Ok(call) => {
+                    let _ = state.tx.send(ServerEvent::IceCandidate(IceCandidateFrame {
+                        call_id: call.id,
+                        from_user_id: user_id,
+                        to_user_id: call.peer_of(user_id),
+                        candidate: req.candidate,
+                        sdp_mid: req.sdp_mid,
+                        sdp_m_line_index: req.sdp_m_line_index,
+                    }));
+                }
+                Err(e) => { let _ = reply_tx.send(error_frame(e.code(), e.to_string())); }
+            }
+        },
+        ClientEvent::UpdateLiveLocation(req) => {
+            if let Err(e) = req.validate() {
+                let _ = reply_tx.send(error_frame("VALIDATION_ERROR", e.to_string()));
+                return;
+            }
+            match state.update_live_location.execute(user_id, req).await {
+                Ok(Some((session, point, participants))) => {
+                    let _ = state.tx.send(ServerEvent::live_location(&session, &point, participants));
+                }
+                // Throttled: the next update will carry the newer position anyway
+                Ok(None) => {}
+                Err(e) => { let _ = reply_tx.send(error_frame(e.code(), e.to_string())); }
+            }
+        },
+        ClientEvent::SystemEvent(payload) => {
+            // Handle anti-screenshot, etc.
+            let _ = state.tx.send(ServerEvent::SystemEvent(payload));
+        },
+    }
 }
@@ -0,0 +1,41 @@
+use axum::extract::ws::Message;
+
+use crate::application::{ClientEvent, ServerEvent};
+
+/// Subprotocol for JSON text frames. Also the default when the client offers none.
+pub const JSON_SUBPROTOCOL: &str = "chat.json";
+/// Subprotocol for MessagePack binary frames, which carry ciphertext as raw bytes.
+pub const MSGPACK_SUBPROTOCOL: &str = "chat.msgpack";
+
+/// Wire encoding negotiated through the `Sec-WebSocket-Protocol` header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WireEncoding {
+    Json,
+    MessagePack,
+}
+
+impl WireEncoding {
+    pub fn from_subprotocol(protocol: Option<&str>) -> Self {
+        match protocol {
+            Some(MSGPACK_SUBPROTOCOL) => WireEncoding::MessagePack,
+            _ => WireEncoding::Json,
+        }
+    }
+
+    pub fn encode(&self, event: &ServerEvent) -> Message {
+        match self {
+            WireEncoding::Json => Message::Text(serde_json::to_string(event).unwrap_or_default()),
+            WireEncoding::MessagePack => Message::Binary(rmp_serde::to_vec_named(event).unwrap_or_default()),
+        }
+    }
+}
+
+/// Decodes a client data frame. Text frames are always JSON and binary frames are always
+/// MessagePack, so a MessagePack client can still send hand-written JSON while debugging.
+pub fn decode(frame: &Message) -> Option<Result<ClientEvent, String>> {
+    match frame {
+        Message::Text(text) => Some(serde_json::from_str(text).map_err(|e| e.to_string())),
+        Message::Binary(bytes) => Some(rmp_serde::from_slice(bytes).map_err(|e| e.to_string())),
+        _ => None,
+    }
+}
@@ -1,3 +1,4 @@
 pub mod chat_ws;
+pub mod codec;
 
-pub use chat_ws::ws_handler;
+pub use chat_ws::{ws_handler, protocol_schema};
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::entities::Backup;
+
+/// `access_key` is stretched from the passphrase on the device; the passphrase never leaves it.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct BackupVaultRequest {
+    #[validate(length(min = 32, max = 128))]
+    pub access_key: String,
+}
+
+/// Creating a vault needs only `access_key`. Replacing one needs `current_access_key` to keep
+/// existing backups, or the account `password` to start over when the passphrase is lost.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SetupBackupVaultRequest {
+    #[validate(length(min = 32, max = 128))]
+    pub access_key: String,
+    #[validate(length(min = 32, max = 128))]
+    pub current_access_key: Option<String>,
+    #[validate(length(min = 6))]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupSecretResponse {
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateBackupRequest {
+    #[validate(range(min = 1))]
+    pub size_bytes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupResponse {
+    pub id: Uuid,
+    pub version: i32,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupUploadResponse {
+    pub backup: BackupResponse,
+    pub upload_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupDownloadResponse {
+    pub backup: BackupResponse,
+    pub download_url: String,
+}
+
+impl From<Backup> for BackupResponse {
+    fn from(backup: Backup) -> Self {
+        Self {
+            id: backup.id,
+            version: backup.version,
+            size_bytes: backup.size_bytes,
+            created_at: backup.created_at,
+            completed_at: backup.completed_at,
+        }
+    }
+}
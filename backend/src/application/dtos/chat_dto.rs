@@ -1,35 +1,227 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use validator::Validate;
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+use crate::application::dtos::ciphertext::{validate_ciphertext, Ciphertext, SyncPayload};
+use crate::domain::entities::Message;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct SendMessageRequest {
+    /// Client-generated ID used to deduplicate retries of the same send.
+    #[validate(length(min = 1, max = 64))]
+    pub client_message_id: Option<String>,
+
     pub conversation_id: Uuid,
     
-    #[validate(length(min = 1))]
-    pub content: String,
+    #[validate(custom = "validate_ciphertext")]
+    pub content: Ciphertext,
     
     pub message_type: String,
     pub reply_to_id: Option<Uuid>,
+
+    /// Overrides the conversation's disappearing-messages timer for this message.
+    #[validate(range(min = 1, max = 604800))]
     pub self_destruct_in_seconds: Option<i64>,
+
+    /// Start the self-destruct countdown when a recipient reads the message.
+    pub self_destruct_on_read: Option<bool>,
+
+    /// Sender key epoch the content was encrypted under; required in groups. Group messages
+    /// under an outdated epoch are rejected so that removed members never receive readable
+    /// content.
+    pub sender_key_epoch: Option<i32>,
+}
+
+/// Forwards an existing message. The client re-encrypts the content for each target.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
+pub struct ForwardMessageRequest {
+    pub message_id: Uuid,
+
+    #[validate(length(min = 1, max = 5))]
+    #[validate]
+    pub targets: Vec<ForwardTarget>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
+pub struct ForwardTarget {
+    #[validate(length(min = 1, max = 64))]
+    pub client_message_id: Option<String>,
+    pub conversation_id: Uuid,
+    #[validate(custom = "validate_ciphertext")]
+    pub content: Ciphertext,
+    pub sender_key_epoch: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MessageResponse {
     pub id: Uuid,
     pub conversation_id: Uuid,
-    pub sender_id: Uuid,
+    pub sender_id: Option<Uuid>,
     pub content: String,
     pub message_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub self_destruct_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_destruct_seconds: Option<i64>,
+    #[serde(default)]
+    pub self_destruct_on_read: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarded_from_id: Option<Uuid>,
+    #[serde(default)]
+    pub forward_count: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_key_epoch: Option<i32>,
+    /// Reply activity, only filled in message history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread: Option<ThreadSummaryResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ThreadSummaryResponse {
+    pub reply_count: i64,
+    pub last_reply_at: Option<DateTime<Utc>>,
+    pub last_replier_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct MessageHistoryParams {
+    /// Return messages older than this message (the `next_cursor` of the previous page).
+    pub before: Option<DateTime<Utc>>,
+    pub before_id: Option<Uuid>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ThreadParams {
+    /// Return replies newer than this reply (the `next_cursor` of the previous page).
+    pub after: Option<DateTime<Utc>>,
+    pub after_id: Option<Uuid>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct StarredMessagesParams {
+    /// Return messages starred before this timestamp (the `next_cursor` of the previous page).
+    pub before: Option<DateTime<Utc>>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StarredMessageResponse {
+    #[serde(flatten)]
+    pub message: MessageResponse,
+    pub starred_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct WebSocketMessage {
-    #[serde(rename = "type")]
+pub struct StarredMessagesResponse {
+    pub messages: Vec<StarredMessageResponse>,
+    pub next_cursor: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistoryCursor {
+    pub before: DateTime<Utc>,
+    pub before_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MessageHistoryResponse {
+    pub messages: Vec<MessageResponse>,
+    pub next_cursor: Option<HistoryCursor>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ThreadCursor {
+    pub after: DateTime<Utc>,
+    pub after_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ThreadResponse {
+    pub root: MessageResponse,
+    pub replies: Vec<MessageResponse>,
+    pub next_cursor: Option<ThreadCursor>,
+}
+
+impl From<&Message> for MessageResponse {
+    fn from(message: &Message) -> Self {
+        Self {
+            id: message.id,
+            conversation_id: message.conversation_id,
+            sender_id: message.sender_id,
+            content: message.content.clone(),
+            message_type: format!("{:?}", message.message_type),
+            reply_to_id: message.reply_to_id,
+            created_at: message.created_at,
+            self_destruct_at: message.self_destruct_at,
+            self_destruct_seconds: message.self_destruct_seconds,
+            self_destruct_on_read: message.self_destruct_on_read,
+            forwarded_from_id: message.forwarded_from_id,
+            forward_count: message.forward_count,
+            sender_key_epoch: message.sender_key_epoch,
+            thread: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
+pub struct EditMessageRequest {
+    pub message_id: Uuid,
+
+    #[validate(custom = "validate_ciphertext")]
+    pub content: Ciphertext,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeleteMessageRequest {
+    pub message_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MarkReadRequest {
+    pub message_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SyncAckRequest {
+    pub seq: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SyncEventResponse {
+    pub seq: i64,
+    pub conversation_id: Uuid,
     pub event_type: String,
-    pub payload: serde_json::Value,
+    pub payload: SyncPayload,
+    pub created_at: DateTime<Utc>,
+    /// Conversation members the frame is routed to; not part of the wire format.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub recipients: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MessageAck {
+    pub client_message_id: Option<String>,
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MessageNack {
+    pub client_message_id: Option<String>,
+    pub code: String,
+    pub error: String,
 }
@@ -0,0 +1,132 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use schemars::JsonSchema;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use validator::ValidationError;
+
+/// Encrypted message content, held as base64 text.
+///
+/// Human-readable encodings (JSON) always carry the base64 string. Binary encodings
+/// (MessagePack) always carry the raw bytes, avoiding the base64 and JSON string overhead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ciphertext(pub String);
+
+impl Ciphertext {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl Serialize for Ciphertext {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_str_as_bytes(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ciphertext {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CiphertextVisitor;
+
+        impl<'de> de::Visitor<'de> for CiphertextVisitor {
+            type Value = Ciphertext;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a base64 string or raw bytes")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Ciphertext, E> {
+                Ok(Ciphertext(value.to_string()))
+            }
+
+            fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Ciphertext, E> {
+                Ok(Ciphertext(BASE64.encode(value)))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(CiphertextVisitor)
+        } else {
+            deserializer.deserialize_any(CiphertextVisitor)
+        }
+    }
+}
+
+impl JsonSchema for Ciphertext {
+    fn schema_name() -> String {
+        "Ciphertext".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        // Only the JSON encoding is described; MessagePack sends the same field as bin
+        String::json_schema(gen)
+    }
+}
+
+pub fn validate_ciphertext(value: &Ciphertext) -> Result<(), ValidationError> {
+    if value.0.is_empty() {
+        return Err(ValidationError::new("empty_ciphertext"));
+    }
+    if BASE64.decode(&value.0).is_err() {
+        return Err(ValidationError::new("invalid_ciphertext"));
+    }
+    Ok(())
+}
+
+fn serialize_str_as_bytes<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        return serializer.serialize_str(value);
+    }
+    let bytes = BASE64.decode(value).map_err(|_| ser::Error::custom("ciphertext is not valid base64"))?;
+    serializer.serialize_bytes(&bytes)
+}
+
+/// A sync event payload and the fields of it that hold message ciphertext. Payloads are stored
+/// as JSON, so the ciphertext type is lost by the time they are delivered; the event they
+/// belong to says which fields to send as raw bytes on binary encodings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncPayload {
+    pub value: serde_json::Value,
+    pub ciphertext_fields: &'static [&'static str],
+}
+
+impl Serialize for SyncPayload {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let serde_json::Value::Object(fields) = &self.value else {
+            return self.value.serialize(serializer);
+        };
+        if serializer.is_human_readable() || self.ciphertext_fields.is_empty() {
+            return self.value.serialize(serializer);
+        }
+
+        let mut map = serializer.serialize_map(Some(fields.len()))?;
+        for (key, value) in fields {
+            match value {
+                serde_json::Value::String(content) if self.ciphertext_fields.contains(&key.as_str()) => {
+                    map.serialize_entry(key, &Ciphertext(content.clone()))?
+                }
+                _ => map.serialize_entry(key, value)?,
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SyncPayload {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(SyncPayload {
+            value: serde_json::Value::deserialize(deserializer)?,
+            ciphertext_fields: &[],
+        })
+    }
+}
+
+impl JsonSchema for SyncPayload {
+    fn schema_name() -> String {
+        "SyncPayload".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        serde_json::Value::json_schema(gen)
+    }
+}
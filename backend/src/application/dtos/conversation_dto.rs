@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct DisappearingMessagesRequest {
+    /// Default lifetime of new messages; `None` turns disappearing messages off.
+    #[validate(range(min = 1, max = 604800))]
+    pub timer_seconds: Option<i64>,
+
+    #[serde(default)]
+    pub start_on_read: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConversationSettingsResponse {
+    pub conversation_id: Uuid,
+    pub settings: serde_json::Value,
+}
@@ -1,13 +1,230 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::domain::entities::{
+    KeyLogEntry, OneTimePreKey, PreKeyBundle, PublicKey, SenderKeyEnvelope, Signature, SignedPreKey,
+};
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UploadPublicKeyRequest {
-    #[validate(length(min = 1))]
+    #[validate(custom = "validate_public_key")]
     pub public_key: String,
+    /// XEdDSA signature by the key being replaced over its rotation message: the tag
+    /// `secure-chat/identity-key-rotation/v1`, user id, an empty device id, `public_key` (raw bytes) and the
+    /// `entry_hash` of the newest key history entry, each prefixed with its length as a
+    /// big-endian u32.
+    #[validate(custom = "validate_signature")]
+    pub signature: Option<String>,
+    /// OTP sent to the account's phone number, accepted instead of `signature`.
+    #[validate(length(min = 4, max = 10))]
+    pub otp: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PublicKeyResponse {
     pub public_key: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SignedPreKeyPayload {
+    #[validate(range(min = 0))]
+    pub key_id: i32,
+    #[validate(custom = "validate_public_key")]
+    pub public_key: String,
+    #[validate(custom = "validate_signature")]
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct OneTimePreKeyPayload {
+    #[validate(range(min = 0))]
+    pub key_id: i32,
+    #[validate(custom = "validate_public_key")]
+    pub public_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct RegisterDeviceKeysRequest {
+    #[validate(custom = "validate_public_key")]
+    pub identity_key: String,
+    #[validate]
+    pub signed_prekey: SignedPreKeyPayload,
+    #[serde(default)]
+    #[validate(length(max = 100))]
+    #[validate]
+    pub one_time_prekeys: Vec<OneTimePreKeyPayload>,
+    /// Required when replacing the device's identity key: a signature by the current one over
+    /// its rotation message, made as for `UploadPublicKeyRequest` but with the device id.
+    #[validate(custom = "validate_signature")]
+    pub signature: Option<String>,
+    /// OTP sent to the account's phone number, accepted instead of `signature`.
+    #[validate(length(min = 4, max = 10))]
+    pub otp: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UploadPreKeysRequest {
+    #[validate(length(min = 1, max = 100))]
+    #[validate]
+    pub one_time_prekeys: Vec<OneTimePreKeyPayload>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreKeyCountResponse {
+    pub device_id: String,
+    pub remaining: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreKeyBundleResponse {
+    pub device_id: String,
+    pub identity_key: String,
+    pub signed_prekey: SignedPreKeyPayload,
+    pub one_time_prekey: Option<OneTimePreKeyPayload>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreKeyBundlesResponse {
+    pub user_id: Uuid,
+    pub devices: Vec<PreKeyBundleResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeyLogEntryResponse {
+    pub seq: i64,
+    pub device_id: Option<String>,
+    pub identity_key: String,
+    pub prev_hash: Option<String>,
+    pub entry_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeyHistoryResponse {
+    pub user_id: Uuid,
+    pub entries: Vec<KeyLogEntryResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SenderKeyEnvelopePayload {
+    pub recipient_id: Uuid,
+    #[validate(length(min = 1, max = 100))]
+    pub recipient_device_id: String,
+    /// The sender key, encrypted with the pairwise session of the recipient device.
+    #[validate(length(min = 1, max = 4096))]
+    pub ciphertext: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct DistributeSenderKeyRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub device_id: String,
+    pub epoch: i32,
+    #[validate(length(min = 1, max = 5000))]
+    #[validate]
+    pub envelopes: Vec<SenderKeyEnvelopePayload>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SenderKeysParams {
+    #[validate(length(min = 1, max = 100))]
+    pub device_id: String,
+    /// Epoch to fetch envelopes of, for messages sent before the last rotation. Defaults to
+    /// the current epoch.
+    #[validate(range(min = 1))]
+    pub epoch: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SenderKeyEnvelopeResponse {
+    pub sender_id: Uuid,
+    pub sender_device_id: String,
+    pub ciphertext: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SenderKeysResponse {
+    pub conversation_id: Uuid,
+    pub epoch: i32,
+    pub envelopes: Vec<SenderKeyEnvelopeResponse>,
+}
+
+impl From<SignedPreKeyPayload> for SignedPreKey {
+    fn from(payload: SignedPreKeyPayload) -> Self {
+        SignedPreKey {
+            key_id: payload.key_id,
+            public_key: PublicKey::canonicalize(payload.public_key),
+            signature: payload.signature,
+        }
+    }
+}
+
+impl From<OneTimePreKeyPayload> for OneTimePreKey {
+    fn from(payload: OneTimePreKeyPayload) -> Self {
+        OneTimePreKey {
+            key_id: payload.key_id,
+            public_key: PublicKey::canonicalize(payload.public_key),
+        }
+    }
+}
+
+impl From<PreKeyBundle> for PreKeyBundleResponse {
+    fn from(bundle: PreKeyBundle) -> Self {
+        Self {
+            device_id: bundle.device_id,
+            identity_key: bundle.identity_key,
+            signed_prekey: SignedPreKeyPayload {
+                key_id: bundle.signed_prekey.key_id,
+                public_key: bundle.signed_prekey.public_key,
+                signature: bundle.signed_prekey.signature,
+            },
+            one_time_prekey: bundle.one_time_prekey.map(|key| OneTimePreKeyPayload {
+                key_id: key.key_id,
+                public_key: key.public_key,
+            }),
+        }
+    }
+}
+
+impl From<KeyLogEntry> for KeyLogEntryResponse {
+    fn from(entry: KeyLogEntry) -> Self {
+        Self {
+            seq: entry.seq,
+            device_id: entry.device_id,
+            identity_key: entry.identity_key,
+            prev_hash: entry.prev_hash,
+            entry_hash: entry.entry_hash,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+impl From<SenderKeyEnvelope> for SenderKeyEnvelopeResponse {
+    fn from(envelope: SenderKeyEnvelope) -> Self {
+        Self {
+            sender_id: envelope.sender_id,
+            sender_device_id: envelope.sender_device_id,
+            ciphertext: envelope.ciphertext,
+            created_at: envelope.created_at,
+        }
+    }
+}
+
+/// Keys must be 32 bytes, encoded as base64 or hex.
+pub fn validate_public_key(value: &str) -> Result<(), ValidationError> {
+    if PublicKey::parse(value).is_none() {
+        return Err(ValidationError::new("invalid_public_key"));
+    }
+    Ok(())
+}
+
+/// Signatures must be 64 bytes, encoded as base64 or hex.
+pub fn validate_signature(value: &str) -> Result<(), ValidationError> {
+    if Signature::parse(value).is_none() {
+        return Err(ValidationError::new("invalid_signature"));
+    }
+    Ok(())
+}
@@ -1,7 +1,11 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::domain::entities::{LiveLocationSession, LocationPoint};
+
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UpdateLocationRequest {
     #[validate(range(min = -90.0, max = 90.0))]
@@ -21,15 +25,112 @@ pub struct FindNearbyRequest {
     
     #[validate(range(min = 0.1, max = 1000.0))]
     pub radius_km: f64,
+
+    #[serde(default)]
+    pub verified_only: bool,
+
+    #[serde(default)]
+    pub online_only: bool,
+
+    /// Continue after this user (the `next_cursor` of the previous page).
+    pub after_distance_km: Option<f64>,
+    pub after_user_id: Option<Uuid>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiscoverabilityRequest {
+    pub discoverable: bool,
+}
+
+/// Never carries coordinates; `distance_km` is rounded up to whole kilometers.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UserLocationResponse {
     pub user_id: Uuid,
-    pub name: String,
-    pub username: String,
+    pub name: Option<String>,
+    pub username: Option<String>,
     pub avatar_url: Option<String>,
+    pub is_verified: bool,
+    pub is_online: bool,
+    pub distance_km: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NearbyCursor {
+    pub after_distance_km: f64,
+    pub after_user_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NearbyUsersResponse {
+    pub users: Vec<UserLocationResponse>,
+    pub next_cursor: Option<NearbyCursor>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StartLiveLocationRequest {
+    /// 15, 60 or 480.
+    pub duration_minutes: i64,
+}
+
+/// Position update for a running live location session, sent over WebSocket.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
+pub struct LiveLocationUpdate {
+    pub session_id: Uuid,
+
+    #[validate(range(min = -90.0, max = 90.0))]
+    pub latitude: f64,
+
+    #[validate(range(min = -180.0, max = 180.0))]
+    pub longitude: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LocationPointResponse {
     pub latitude: f64,
     pub longitude: f64,
-    pub distance_km: Option<f64>, // Calculated distance
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LiveLocationResponse {
+    pub session_id: Uuid,
+    pub conversation_id: Uuid,
+    pub user_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub last_location: Option<LocationPointResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LiveLocationTrackResponse {
+    pub session: LiveLocationResponse,
+    pub points: Vec<LocationPointResponse>,
+}
+
+impl From<&LocationPoint> for LocationPointResponse {
+    fn from(point: &LocationPoint) -> Self {
+        Self {
+            latitude: point.latitude,
+            longitude: point.longitude,
+            recorded_at: point.recorded_at,
+        }
+    }
+}
+
+impl From<&LiveLocationSession> for LiveLocationResponse {
+    fn from(session: &LiveLocationSession) -> Self {
+        Self {
+            session_id: session.id,
+            conversation_id: session.conversation_id,
+            user_id: session.user_id,
+            started_at: session.started_at,
+            expires_at: session.expires_at,
+            stopped_at: session.stopped_at,
+            last_location: session.last_point.as_ref().map(LocationPointResponse::from),
+        }
+    }
 }
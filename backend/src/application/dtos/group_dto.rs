@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::entities::MAX_GEOFENCE_RADIUS_METERS;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateGroupRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    #[serde(default)]
+    pub member_ids: Vec<Uuid>,
+
+    /// Makes the group location-bound, discoverable by anyone inside the area.
+    #[validate]
+    pub geofence: Option<GeofenceRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct GeofenceRequest {
+    #[validate(range(min = -90.0, max = 90.0))]
+    pub latitude: f64,
+
+    #[validate(range(min = -180.0, max = 180.0))]
+    pub longitude: f64,
+
+    #[validate(range(min = 100, max = "MAX_GEOFENCE_RADIUS_METERS"))]
+    pub radius_meters: i32,
+
+    /// Remove members (other than the owner) once they report a position outside the area.
+    #[serde(default)]
+    pub remove_on_exit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct AddGroupMembersRequest {
+    #[validate(length(min = 1))]
+    pub user_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ChangeMemberRoleRequest {
+    #[validate(length(min = 1))]
+    pub role: String, // "Admin", "Member"
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct TransferOwnershipRequest {
+    pub new_owner_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateGroupInfoRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+
+    #[validate(url)]
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateGroupSettingsRequest {
+    pub only_admins_can_post: Option<bool>,
+    pub forwarding_enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupMemberResponse {
+    pub user_id: Uuid,
+    pub role: String,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupResponse {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub settings: serde_json::Value,
+    pub members: Vec<GroupMemberResponse>,
+    pub geofence: Option<GeofenceResponse>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GeofenceResponse {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_meters: i32,
+    pub remove_on_exit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NearbyGroupResponse {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub member_count: i64,
+    pub distance_km: f64,
+    pub radius_meters: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoveGroupMemberParams {
+    /// Also prevent the user from rejoining through invite links.
+    #[serde(default)]
+    pub ban: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateGroupInviteRequest {
+    #[validate(range(min = 60, max = 2592000))]
+    pub expires_in_seconds: Option<i64>,
+
+    #[validate(range(min = 1, max = 100000))]
+    pub max_uses: Option<i32>,
+
+    #[serde(default)]
+    pub requires_approval: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupInviteResponse {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+    pub token: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+    pub requires_approval: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InvitePreviewResponse {
+    pub conversation_id: Uuid,
+    pub name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub member_count: i64,
+    pub requires_approval: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JoinGroupResponse {
+    pub conversation_id: Uuid,
+    pub status: String, // "Joined", "PendingApproval"
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JoinRequestResponse {
+    pub user_id: Uuid,
+    pub invite_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ReviewJoinRequestRequest {
+    pub approve: bool,
+}
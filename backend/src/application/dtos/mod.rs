@@ -6,12 +6,53 @@ pub mod geo_dto;
 pub mod subscription_dto;
 pub mod notification_dto;
 pub mod e2ee_dto;
+pub mod backup_dto;
+pub mod ws_dto;
+pub mod ciphertext;
+pub mod conversation_dto;
+pub mod group_dto;
+pub mod channel_dto;
 
 pub use auth_dto::{AuthResponse, LoginRequest, RegisterRequest, VerifyOtpRequest};
 pub use kyc_dto::{GetUploadUrlRequest, UploadUrlResponse, SubmitKycRequest, KycResponse, ReviewKycRequest};
-pub use chat_dto::{SendMessageRequest, MessageResponse, WebSocketMessage};
-pub use webrtc_dto::{WebRtcSignal, CallRequest, CallResponse, IceCandidate};
-pub use geo_dto::{UpdateLocationRequest, FindNearbyRequest, UserLocationResponse};
+pub use chat_dto::{
+    SendMessageRequest, MessageResponse, ForwardMessageRequest,
+    EditMessageRequest, DeleteMessageRequest, MarkReadRequest, SyncAckRequest, SyncEventResponse,
+    MessageAck, MessageNack,
+    ThreadSummaryResponse, MessageHistoryParams, ThreadParams, MessageHistoryResponse, ThreadResponse,
+    HistoryCursor, ThreadCursor,
+    StarredMessagesParams, StarredMessageResponse, StarredMessagesResponse,
+};
+pub use webrtc_dto::{CallRequest, CallResponse, EndCallRequest, IceCandidate,
+    IceServerResponse, IceServersResponse, JoinGroupCallRequest, GroupCallResponse, GroupCallStatusResponse,
+    GroupCallJoinResponse,
+};
+pub use geo_dto::{
+    UpdateLocationRequest, FindNearbyRequest, DiscoverabilityRequest, UserLocationResponse,
+    NearbyCursor, NearbyUsersResponse, StartLiveLocationRequest, LiveLocationUpdate,
+    LocationPointResponse, LiveLocationResponse, LiveLocationTrackResponse,
+};
 pub use subscription_dto::{UpgradeSubscriptionRequest, SubscriptionResponse};
 pub use notification_dto::RegisterDeviceTokenRequest;
-pub use e2ee_dto::{UploadPublicKeyRequest, PublicKeyResponse};
+pub use e2ee_dto::{
+    UploadPublicKeyRequest, PublicKeyResponse, RegisterDeviceKeysRequest, UploadPreKeysRequest,
+    PreKeyCountResponse, PreKeyBundlesResponse, KeyHistoryResponse,
+    DistributeSenderKeyRequest, SenderKeysParams, SenderKeysResponse,
+};
+pub use backup_dto::{
+    BackupVaultRequest, SetupBackupVaultRequest, BackupSecretResponse, CreateBackupRequest, BackupResponse,
+    BackupUploadResponse, BackupDownloadResponse,
+};
+pub use conversation_dto::{DisappearingMessagesRequest, ConversationSettingsResponse};
+pub use group_dto::{
+    CreateGroupRequest, AddGroupMembersRequest, ChangeMemberRoleRequest, TransferOwnershipRequest,
+    UpdateGroupInfoRequest, UpdateGroupSettingsRequest, GroupMemberResponse, GroupResponse,
+    RemoveGroupMemberParams, CreateGroupInviteRequest, GroupInviteResponse, InvitePreviewResponse,
+    JoinGroupResponse, JoinRequestResponse, ReviewJoinRequestRequest, GeofenceResponse,
+    NearbyGroupResponse,
+};
+pub use channel_dto::{CreateChannelRequest, ChannelResponse, ChannelViewsRequest, MessageViewsResponse};
+pub use ws_dto::{
+    ClientEvent, ServerEvent, HelloFrame, ErrorFrame, PreKeysLowFrame, IceCandidateFrame, ProtocolSchemaResponse,
+    PROTOCOL_VERSION, MIN_PROTOCOL_VERSION,
+};
@@ -1,17 +1,23 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UpgradeSubscriptionRequest {
     #[validate(length(min = 1))]
-    pub tier: String, // "Free", "Premium", "Gold"
-    
-    pub payment_token: Option<String>, // Mock payment token
+    pub tier: String, // "Monthly", "Yearly"
+
+    /// Payment method collected by the client through the payment provider's SDK.
+    #[validate(length(min = 1, max = 255))]
+    pub payment_token: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SubscriptionResponse {
     pub tier: String,
-    pub expires_at: Option<String>,
+    pub status: Option<String>, // "Active", "PastDue", "Canceled", "Expired"; None if never subscribed
+    pub current_period_start: Option<DateTime<Utc>>,
+    pub current_period_end: Option<DateTime<Utc>>,
+    pub cancel_at_period_end: bool,
     pub features: Vec<String>,
 }
@@ -1,33 +1,103 @@
+use schemars::JsonSchema;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use validator::Validate;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct WebRtcSignal {
-    pub type_: String, // "offer", "answer", "candidate", "bye"
-    pub sdp: Option<String>,
-    pub candidate: Option<String>,
-    pub sdp_mid: Option<String>,
-    pub sdp_m_line_index: Option<i32>,
-    pub target_user_id: Uuid,
-}
+use crate::domain::entities::GroupCall;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct CallRequest {
+    pub conversation_id: Uuid,
     pub target_user_id: Uuid,
+    #[validate(length(min = 1, max = 65536))]
     pub offer: String, // SDP
     pub is_video: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct CallResponse {
-    pub caller_id: Uuid,
+    pub call_id: Uuid,
+    #[validate(length(min = 1, max = 65536))]
     pub answer: String, // SDP
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Hangs up, rejects or cancels a call depending on its state.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EndCallRequest {
+    pub call_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct IceCandidate {
-    pub target_user_id: Uuid,
+    pub call_id: Uuid,
+    #[validate(length(min = 1, max = 4096))]
     pub candidate: String,
     pub sdp_mid: Option<String>,
     pub sdp_m_line_index: Option<i32>,
 }
+
+/// Entry of `RTCConfiguration.iceServers`; STUN servers carry no credentials.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IceServerResponse {
+    pub urls: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IceServersResponse {
+    pub ice_servers: Vec<IceServerResponse>,
+    /// Seconds until the TURN credentials expire; fetch new ones before placing a call after that.
+    pub ttl_seconds: i64,
+    /// Video bitrate the caller's subscription is meant for; clients cap their encoders to it.
+    /// The server does not enforce it.
+    pub recommended_bitrate_kbps: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JoinGroupCallRequest {
+    /// Only used when this join starts the call.
+    #[serde(default)]
+    pub is_video: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupCallResponse {
+    pub call_id: Uuid,
+    pub conversation_id: Uuid,
+    pub started_by: Uuid,
+    pub is_video: bool,
+    pub started_at: DateTime<Utc>,
+    pub participants: Vec<Uuid>,
+}
+
+/// `call` is null when no call is in progress.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupCallStatusResponse {
+    pub call: Option<GroupCallResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupCallJoinResponse {
+    pub call: GroupCallResponse,
+    pub sfu_url: String,
+    pub token: String,
+    pub can_publish: bool,
+    pub can_subscribe: bool,
+}
+
+impl GroupCallResponse {
+    pub fn new(call: GroupCall, participants: Vec<Uuid>) -> Self {
+        Self {
+            call_id: call.id,
+            conversation_id: call.conversation_id,
+            started_by: call.started_by,
+            is_video: call.is_video,
+            started_at: call.started_at,
+            participants,
+        }
+    }
+}
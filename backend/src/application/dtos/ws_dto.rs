@@ -0,0 +1,192 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::{Call, LiveLocationSession, LocationPoint, SyncEvent, SyncEventType};
+use crate::application::dtos::ciphertext::SyncPayload;
+use crate::application::dtos::{
+    SendMessageRequest, ForwardMessageRequest, EditMessageRequest, DeleteMessageRequest, MarkReadRequest, SyncAckRequest,
+    SyncEventResponse, MessageAck, MessageNack, CallRequest, CallResponse, EndCallRequest, IceCandidate,
+    LiveLocationUpdate,
+};
+
+/// Protocol version spoken by this server.
+pub const PROTOCOL_VERSION: u32 = 1;
+/// Oldest protocol version the server still accepts.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Frames sent by clients. Encoded as `{"type": "...", "payload": {...}}`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", content = "payload")]
+pub enum ClientEvent {
+    SendMessage(SendMessageRequest),
+    ForwardMessage(ForwardMessageRequest),
+    EditMessage(EditMessageRequest),
+    DeleteMessage(DeleteMessageRequest),
+    MarkRead(MarkReadRequest),
+    SyncAck(SyncAckRequest),
+    StartCall(CallRequest),
+    AnswerCall(CallResponse),
+    EndCall(EndCallRequest),
+    IceCandidate(IceCandidate),
+    UpdateLiveLocation(LiveLocationUpdate),
+    SystemEvent(serde_json::Value),
+}
+
+/// Payload fields of a sync event that hold message ciphertext. System and call records are
+/// stored as plain text.
+fn ciphertext_fields(event: &SyncEvent) -> &'static [&'static str] {
+    match event.event_type {
+        SyncEventType::MessageCreated => match event.payload["message_type"].as_str() {
+            Some("System") | Some("CallSignal") => &[],
+            _ => &["content"],
+        },
+        SyncEventType::MessageEdited => &["content"],
+        _ => &[],
+    }
+}
+
+/// Frames sent by the server. Encoded as `{"type": "...", "payload": {...}}`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", content = "payload")]
+pub enum ServerEvent {
+    Hello(HelloFrame),
+    SyncEvent(SyncEventResponse),
+    Ack(MessageAck),
+    Nack(MessageNack),
+    SystemEvent(serde_json::Value),
+    PreKeysLow(PreKeysLowFrame),
+    CallUpdate(CallUpdateFrame),
+    IceCandidate(IceCandidateFrame),
+    LiveLocation(LiveLocationFrame),
+    Error(ErrorFrame),
+}
+
+impl ServerEvent {
+    pub fn sync_event(event: &SyncEvent) -> Self {
+        ServerEvent::SyncEvent(SyncEventResponse {
+            seq: event.seq,
+            conversation_id: event.conversation_id,
+            event_type: format!("{:?}", event.event_type),
+            payload: SyncPayload {
+                value: event.payload.clone(),
+                ciphertext_fields: ciphertext_fields(event),
+            },
+            created_at: event.created_at,
+            recipients: event.recipients.clone(),
+        })
+    }
+
+    /// Tells both parties of a call about its new state. `sdp` carries the offer while the call
+    /// rings and the answer once it is accepted.
+    pub fn call_update(call: &Call, sdp: Option<String>) -> Self {
+        ServerEvent::CallUpdate(CallUpdateFrame {
+            call_id: call.id,
+            conversation_id: call.conversation_id,
+            caller_id: call.caller_id,
+            callee_id: call.callee_id,
+            is_video: call.is_video,
+            status: format!("{:?}", call.status),
+            answered_device_id: call.answered_device_id.clone(),
+            sdp,
+        })
+    }
+
+    /// Position update routed to the conversation's `participants` only.
+    pub fn live_location(session: &LiveLocationSession, point: &LocationPoint, participants: Vec<Uuid>) -> Self {
+        ServerEvent::LiveLocation(LiveLocationFrame {
+            session_id: session.id,
+            conversation_id: session.conversation_id,
+            user_id: session.user_id,
+            latitude: point.latitude,
+            longitude: point.longitude,
+            recorded_at: point.recorded_at,
+            recipients: participants,
+        })
+    }
+
+    /// Whether a connection of `user_id` should receive this frame. Sync events go to the
+    /// conversation's members and frames carrying private data to the users they concern;
+    /// everything else goes to every connection.
+    pub fn is_addressed_to(&self, user_id: Uuid) -> bool {
+        match self {
+            ServerEvent::CallUpdate(frame) => frame.caller_id == user_id || frame.callee_id == user_id,
+            ServerEvent::IceCandidate(frame) => frame.to_user_id == user_id,
+            ServerEvent::LiveLocation(frame) => frame.recipients.contains(&user_id),
+            ServerEvent::PreKeysLow(frame) => frame.user_id == user_id,
+            ServerEvent::SyncEvent(frame) => frame.recipients.contains(&user_id),
+            _ => true,
+        }
+    }
+}
+
+/// First frame on every connection, confirming the negotiated protocol.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HelloFrame {
+    pub protocol_version: u32,
+    pub heartbeat_interval_secs: u64,
+}
+
+/// Sent when a client frame cannot be processed.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorFrame {
+    pub code: String,
+    pub message: String,
+}
+
+/// Asks a device to upload more one-time prekeys before it runs out.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PreKeysLowFrame {
+    pub user_id: Uuid,
+    pub device_id: String,
+    pub remaining: i64,
+}
+
+/// State change of a call. Devices of the callee other than `answered_device_id` stop ringing
+/// once the call is accepted.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CallUpdateFrame {
+    pub call_id: Uuid,
+    pub conversation_id: Uuid,
+    pub caller_id: Uuid,
+    pub callee_id: Uuid,
+    pub is_video: bool,
+    pub status: String,
+    pub answered_device_id: Option<String>,
+    pub sdp: Option<String>,
+}
+
+/// ICE candidate relayed from one party of a call to the other.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IceCandidateFrame {
+    pub call_id: Uuid,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub candidate: String,
+    pub sdp_mid: Option<String>,
+    pub sdp_m_line_index: Option<i32>,
+}
+
+/// Latest position of a participant sharing their live location in a conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LiveLocationFrame {
+    pub session_id: Uuid,
+    pub conversation_id: Uuid,
+    pub user_id: Uuid,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub recorded_at: DateTime<Utc>,
+    /// Participants the frame is routed to; not part of the wire format.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub recipients: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProtocolSchemaResponse {
+    pub protocol_version: u32,
+    pub min_protocol_version: u32,
+    pub client_events: schemars::schema::RootSchema,
+    pub server_events: schemars::schema::RootSchema,
+}
@@ -1,5 +1,5 @@
 pub mod dtos;
 pub mod use_cases;
 
-pub use dtos::{AuthResponse, LoginRequest, RegisterRequest};
-pub use use_cases::{LoginUser, RegisterUser};
+pub use dtos::*;
+pub use use_cases::*;
@@ -1,21 +1,69 @@
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::application::use_cases::e2ee::{helpers::announce_key_change, AuthorizeKeyRotation, KeyRotationProof};
 use crate::domain::{
-    repositories::UserRepository,
-    DomainResult,
+    entities::{PublicKey, SyncEvent},
+    repositories::{ConversationRepository, SyncRepository, UserRepository},
+    DomainError, DomainResult,
 };
 
 pub struct UploadPublicKey {
     user_repo: Arc<dyn UserRepository>,
+    authorize_key_rotation: Arc<AuthorizeKeyRotation>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
 }
 
 impl UploadPublicKey {
-    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
-        Self { user_repo }
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        authorize_key_rotation: Arc<AuthorizeKeyRotation>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+    ) -> Self {
+        Self {
+            user_repo,
+            authorize_key_rotation,
+            conversation_repo,
+            sync_repo,
+        }
     }
 
-    pub async fn execute(&self, user_id: Uuid, public_key: String) -> DomainResult<()> {
-        self.user_repo.update_public_key(user_id, public_key).await
+    /// Stores the account key and returns the `KeyChanged` events to broadcast if it replaced one.
+    /// Replacing a key requires a rotation proof signed by the current key or a fresh OTP.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        public_key: String,
+        signature: Option<String>,
+        otp: Option<String>,
+    ) -> DomainResult<Vec<SyncEvent>> {
+        let new_key = PublicKey::parse(&public_key)
+            .ok_or_else(|| DomainError::ValidationError("Public key must be 32 bytes, base64 or hex encoded".to_string()))?;
+        let public_key = new_key.to_base64();
+
+        let user = self.user_repo.find_by_id(user_id).await?
+            .ok_or_else(|| DomainError::NotFound("User not found".to_string()))?;
+
+        let owners = self.user_repo.find_public_key_owners(&new_key).await?;
+        if owners.iter().any(|owner_id| *owner_id != user_id) {
+            return Err(DomainError::Conflict("Public key is already registered to another user".to_string()));
+        }
+
+        match user.public_key.as_deref() {
+            // Also catches a key stored in another encoding before keys were canonicalized
+            Some(current) if PublicKey::parse(current) == Some(new_key) => return Ok(Vec::new()),
+            Some(current) => {
+                self.authorize_key_rotation
+                    .execute(&user, None, current, &new_key, KeyRotationProof { signature, otp })
+                    .await?
+            }
+            None => {}
+        }
+
+        let entry = self.user_repo.update_public_key(user_id, public_key).await?;
+
+        announce_key_change(self.conversation_repo.as_ref(), self.sync_repo.as_ref(), entry).await
     }
 }
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{find_owned_backup, purge_backup};
+use crate::domain::{
+    entities::Backup,
+    repositories::BackupRepository,
+    DomainError, DomainResult,
+};
+use crate::infrastructure::external::S3Service;
+
+/// Completed backups kept per user; older versions are deleted when a new one completes.
+pub const MAX_BACKUP_VERSIONS: usize = 3;
+
+pub struct CompleteBackup {
+    backup_repo: Arc<dyn BackupRepository>,
+    s3_service: Arc<S3Service>,
+}
+
+impl CompleteBackup {
+    pub fn new(backup_repo: Arc<dyn BackupRepository>, s3_service: Arc<S3Service>) -> Self {
+        Self {
+            backup_repo,
+            s3_service,
+        }
+    }
+
+    /// Marks an uploaded backup as restorable and applies the retention policy. The archive must
+    /// be in S3 with the announced size, so older versions are never purged in favour of an
+    /// upload that did not happen.
+    pub async fn execute(&self, user_id: Uuid, backup_id: Uuid) -> DomainResult<Backup> {
+        let pending = find_owned_backup(self.backup_repo.as_ref(), user_id, backup_id).await?;
+
+        let uploaded_size = self.s3_service
+            .object_size(&pending.object_key)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("S3 error: {}", e)))?;
+        match uploaded_size {
+            None => return Err(DomainError::ValidationError("Backup archive has not been uploaded".to_string())),
+            Some(size) if size != pending.size_bytes => {
+                return Err(DomainError::ValidationError(format!(
+                    "Uploaded archive has {} bytes, expected {}",
+                    size, pending.size_bytes
+                )));
+            }
+            Some(_) => {}
+        }
+
+        let backup = self.backup_repo.mark_complete(backup_id).await?;
+
+        let expired = self.backup_repo
+            .find_by_user(user_id)
+            .await?
+            .into_iter()
+            .filter(|b| b.is_complete())
+            .skip(MAX_BACKUP_VERSIONS);
+
+        for old in expired {
+            purge_backup(self.backup_repo.as_ref(), &self.s3_service, &old).await?;
+        }
+
+        Ok(backup)
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::Backup,
+    repositories::BackupRepository,
+    DomainError, DomainResult,
+};
+use crate::infrastructure::external::S3Service;
+
+/// Upper bound for a single encrypted archive (2 GiB).
+pub const MAX_BACKUP_SIZE_BYTES: i64 = 2 * 1024 * 1024 * 1024;
+
+pub struct CreateBackup {
+    backup_repo: Arc<dyn BackupRepository>,
+    s3_service: Arc<S3Service>,
+}
+
+impl CreateBackup {
+    pub fn new(backup_repo: Arc<dyn BackupRepository>, s3_service: Arc<S3Service>) -> Self {
+        Self {
+            backup_repo,
+            s3_service,
+        }
+    }
+
+    /// Reserves the next backup version and returns a presigned URL the client PUTs the
+    /// encrypted archive to. The backup is not listed until it is completed.
+    pub async fn execute(&self, user_id: Uuid, size_bytes: i64) -> DomainResult<(Backup, String)> {
+        if size_bytes <= 0 || size_bytes > MAX_BACKUP_SIZE_BYTES {
+            return Err(DomainError::ValidationError(format!(
+                "Backup size must be between 1 and {} bytes",
+                MAX_BACKUP_SIZE_BYTES
+            )));
+        }
+
+        if self.backup_repo.find_vault(user_id).await?.is_none() {
+            return Err(DomainError::ValidationError(
+                "Set up a backup passphrase first".to_string(),
+            ));
+        }
+
+        let backup = self.backup_repo.create(&Backup::new(user_id, size_bytes)).await?;
+
+        let upload_url = self.s3_service
+            .get_presigned_upload_url(&backup.object_key, "application/octet-stream", backup.size_bytes)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("S3 error: {}", e)))?;
+
+        Ok((backup, upload_url))
+    }
+}
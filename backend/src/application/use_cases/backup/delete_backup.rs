@@ -0,0 +1,28 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{find_owned_backup, purge_backup};
+use crate::domain::{
+    repositories::BackupRepository,
+    DomainResult,
+};
+use crate::infrastructure::external::S3Service;
+
+pub struct DeleteBackup {
+    backup_repo: Arc<dyn BackupRepository>,
+    s3_service: Arc<S3Service>,
+}
+
+impl DeleteBackup {
+    pub fn new(backup_repo: Arc<dyn BackupRepository>, s3_service: Arc<S3Service>) -> Self {
+        Self {
+            backup_repo,
+            s3_service,
+        }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, backup_id: Uuid) -> DomainResult<()> {
+        let backup = find_owned_backup(self.backup_repo.as_ref(), user_id, backup_id).await?;
+        purge_backup(self.backup_repo.as_ref(), &self.s3_service, &backup).await
+    }
+}
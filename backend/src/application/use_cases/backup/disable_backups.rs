@@ -0,0 +1,32 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::purge_backup;
+use crate::domain::{
+    repositories::BackupRepository,
+    DomainResult,
+};
+use crate::infrastructure::external::S3Service;
+
+pub struct DisableBackups {
+    backup_repo: Arc<dyn BackupRepository>,
+    s3_service: Arc<S3Service>,
+}
+
+impl DisableBackups {
+    pub fn new(backup_repo: Arc<dyn BackupRepository>, s3_service: Arc<S3Service>) -> Self {
+        Self {
+            backup_repo,
+            s3_service,
+        }
+    }
+
+    /// Deletes every backup and the vault, opting the user out of backups.
+    pub async fn execute(&self, user_id: Uuid) -> DomainResult<()> {
+        for backup in self.backup_repo.find_by_user(user_id).await? {
+            purge_backup(self.backup_repo.as_ref(), &self.s3_service, &backup).await?;
+        }
+
+        self.backup_repo.delete_vault(user_id).await
+    }
+}
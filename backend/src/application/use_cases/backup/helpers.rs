@@ -0,0 +1,37 @@
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::Backup,
+    repositories::BackupRepository,
+    DomainError, DomainResult,
+};
+use crate::infrastructure::external::S3Service;
+
+/// Looks up a backup and checks it belongs to the user. Other users' backups are reported as
+/// missing so their ids cannot be probed.
+pub(super) async fn find_owned_backup(
+    backup_repo: &dyn BackupRepository,
+    user_id: Uuid,
+    backup_id: Uuid,
+) -> DomainResult<Backup> {
+    backup_repo
+        .find_by_id(backup_id)
+        .await?
+        .filter(|backup| backup.user_id == user_id)
+        .ok_or_else(|| DomainError::NotFound("Backup not found".to_string()))
+}
+
+/// Deletes the archive from S3 before the row, so a failed S3 call leaves the backup listed
+/// and the deletion can be retried.
+pub(super) async fn purge_backup(
+    backup_repo: &dyn BackupRepository,
+    s3_service: &S3Service,
+    backup: &Backup,
+) -> DomainResult<()> {
+    s3_service
+        .delete_object(&backup.object_key)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("S3 error: {}", e)))?;
+
+    backup_repo.delete(backup.id).await
+}
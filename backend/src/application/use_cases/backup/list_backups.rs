@@ -0,0 +1,24 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::Backup,
+    repositories::BackupRepository,
+    DomainResult,
+};
+
+pub struct ListBackups {
+    backup_repo: Arc<dyn BackupRepository>,
+}
+
+impl ListBackups {
+    pub fn new(backup_repo: Arc<dyn BackupRepository>) -> Self {
+        Self { backup_repo }
+    }
+
+    /// Completed backups of the user, newest first.
+    pub async fn execute(&self, user_id: Uuid) -> DomainResult<Vec<Backup>> {
+        let backups = self.backup_repo.find_by_user(user_id).await?;
+        Ok(backups.into_iter().filter(|b| b.is_complete()).collect())
+    }
+}
@@ -0,0 +1,18 @@
+mod helpers;
+pub mod setup_backup_vault;
+pub mod unlock_backup_vault;
+pub mod create_backup;
+pub mod complete_backup;
+pub mod list_backups;
+pub mod restore_backup;
+pub mod delete_backup;
+pub mod disable_backups;
+
+pub use setup_backup_vault::SetupBackupVault;
+pub use unlock_backup_vault::UnlockBackupVault;
+pub use create_backup::CreateBackup;
+pub use complete_backup::CompleteBackup;
+pub use list_backups::ListBackups;
+pub use restore_backup::RestoreBackup;
+pub use delete_backup::DeleteBackup;
+pub use disable_backups::DisableBackups;
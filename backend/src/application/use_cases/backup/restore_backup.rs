@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::find_owned_backup;
+use crate::domain::{
+    entities::Backup,
+    repositories::BackupRepository,
+    DomainError, DomainResult,
+};
+use crate::infrastructure::external::S3Service;
+
+pub struct RestoreBackup {
+    backup_repo: Arc<dyn BackupRepository>,
+    s3_service: Arc<S3Service>,
+}
+
+impl RestoreBackup {
+    pub fn new(backup_repo: Arc<dyn BackupRepository>, s3_service: Arc<S3Service>) -> Self {
+        Self {
+            backup_repo,
+            s3_service,
+        }
+    }
+
+    /// Returns a presigned URL to download the encrypted archive.
+    pub async fn execute(&self, user_id: Uuid, backup_id: Uuid) -> DomainResult<(Backup, String)> {
+        let backup = find_owned_backup(self.backup_repo.as_ref(), user_id, backup_id).await?;
+        if !backup.is_complete() {
+            return Err(DomainError::NotFound("Backup not found".to_string()));
+        }
+
+        let download_url = self.s3_service
+            .get_presigned_download_url(&backup.object_key)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("S3 error: {}", e)))?;
+
+        Ok((backup, download_url))
+    }
+}
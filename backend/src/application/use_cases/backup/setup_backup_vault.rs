@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::purge_backup;
+use super::UnlockBackupVault;
+use crate::domain::{
+    entities::BackupVault,
+    repositories::{BackupRepository, UserRepository},
+    services::AuthService,
+    DomainError, DomainResult,
+};
+use crate::infrastructure::external::S3Service;
+
+pub struct SetupBackupVault {
+    backup_repo: Arc<dyn BackupRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    auth_service: Arc<dyn AuthService>,
+    s3_service: Arc<S3Service>,
+    unlock_backup_vault: Arc<UnlockBackupVault>,
+}
+
+impl SetupBackupVault {
+    pub fn new(
+        backup_repo: Arc<dyn BackupRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        auth_service: Arc<dyn AuthService>,
+        s3_service: Arc<S3Service>,
+        unlock_backup_vault: Arc<UnlockBackupVault>,
+    ) -> Self {
+        Self {
+            backup_repo,
+            user_repo,
+            auth_service,
+            s3_service,
+            unlock_backup_vault,
+        }
+    }
+
+    /// Opts the user into backups, or changes their passphrase. `access_key` is derived by the
+    /// client from the passphrase and is never the passphrase itself. Returns the vault secret,
+    /// which the client mixes into its backup key.
+    ///
+    /// Replacing an existing vault needs proof of the caller: with the current access key the
+    /// secret is kept and existing backups stay readable. A user who forgot the passphrase can
+    /// reset with the account password instead, which draws a new secret and removes the
+    /// backups that can no longer be decrypted. Either guess counts against the unlock limit.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        access_key: String,
+        current_access_key: Option<String>,
+        password: Option<String>,
+    ) -> DomainResult<String> {
+        let secret = if self.backup_repo.find_vault(user_id).await?.is_none() {
+            None
+        } else if let Some(current_access_key) = current_access_key {
+            Some(self.unlock_backup_vault.execute(user_id, current_access_key).await?)
+        } else if let Some(password) = password {
+            if self.backup_repo.reserve_unlock_attempt(user_id).await?.is_none() {
+                return Err(DomainError::AuthenticationError("Too many failed attempts on the backup vault".to_string()));
+            }
+            let user = self.user_repo.find_by_id(user_id).await?
+                .ok_or_else(|| DomainError::NotFound("User not found".to_string()))?;
+            if !self.auth_service.verify_password(&password, &user.password_hash).await? {
+                return Err(DomainError::AuthenticationError("Wrong password".to_string()));
+            }
+            None
+        } else {
+            return Err(DomainError::AuthenticationError(
+                "Replacing the backup vault requires the current passphrase or the account password".to_string(),
+            ));
+        };
+
+        let access_key = self.unlock_backup_vault.pepper(user_id, &access_key);
+        let access_key_hash = self.auth_service.hash_password(&access_key).await?;
+
+        let secret = match secret {
+            Some(secret) => secret,
+            None => {
+                for backup in self.backup_repo.find_by_user(user_id).await? {
+                    purge_backup(self.backup_repo.as_ref(), &self.s3_service, &backup).await?;
+                }
+                BackupVault::generate_secret()
+            }
+        };
+
+        let vault = BackupVault::new(user_id, access_key_hash, &access_key, &secret);
+        self.backup_repo.upsert_vault(&vault).await?;
+
+        Ok(secret)
+    }
+}
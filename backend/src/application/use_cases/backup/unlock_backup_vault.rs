@@ -0,0 +1,78 @@
+use ring::hmac;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::BackupVault,
+    repositories::BackupRepository,
+    services::AuthService,
+    DomainError, DomainResult,
+};
+
+pub struct UnlockBackupVault {
+    backup_repo: Arc<dyn BackupRepository>,
+    auth_service: Arc<dyn AuthService>,
+    pepper: hmac::Key,
+}
+
+impl UnlockBackupVault {
+    /// `pepper` is the server secret mixed into every access key; it must not be stored in
+    /// the database.
+    pub fn new(backup_repo: Arc<dyn BackupRepository>, auth_service: Arc<dyn AuthService>, pepper: &str) -> Self {
+        Self {
+            backup_repo,
+            auth_service,
+            pepper: hmac::Key::new(hmac::HMAC_SHA256, pepper.as_bytes()),
+        }
+    }
+
+    /// The access key as it is hashed and seals the secret.
+    pub fn pepper(&self, user_id: Uuid, access_key: &str) -> String {
+        BackupVault::pepper_access_key(&self.pepper, user_id, access_key)
+    }
+
+    /// Releases the vault secret when `access_key` matches. Each guess is counted before it is
+    /// checked. When the last allowed guess is wrong the vault is destroyed, and backups made
+    /// with it can no longer be decrypted by anyone.
+    pub async fn execute(&self, user_id: Uuid, access_key: String) -> DomainResult<String> {
+        let vault = match self.backup_repo.reserve_unlock_attempt(user_id).await? {
+            Some(vault) => vault,
+            None => {
+                // Either there never was a vault, or a previous request used the last attempt
+                // and has not deleted it yet.
+                if self.backup_repo.find_vault(user_id).await?.is_some() {
+                    self.backup_repo.delete_vault(user_id).await?;
+                }
+                return Err(DomainError::NotFound("No backup vault".to_string()));
+            }
+        };
+
+        let peppered_key = self.pepper(user_id, &access_key);
+        let key = if vault.peppered { &peppered_key } else { &access_key };
+
+        if self.auth_service.verify_password(key, &vault.access_key_hash).await? {
+            let secret = vault.open_secret(key)
+                .ok_or_else(|| DomainError::InternalError("Backup vault secret cannot be opened".to_string()))?;
+            if !vault.peppered || vault.sealed_secret.is_none() {
+                let access_key_hash = self.auth_service.hash_password(&peppered_key).await?;
+                let sealed = BackupVault::seal_secret(user_id, &peppered_key, &secret);
+                self.backup_repo.upgrade_vault(user_id, &access_key_hash, &sealed).await?;
+            }
+            self.backup_repo.reset_unlock_attempts(user_id).await?;
+            return Ok(secret);
+        }
+
+        let remaining = vault.remaining_attempts();
+        if remaining == 0 {
+            self.backup_repo.delete_vault(user_id).await?;
+            return Err(DomainError::AuthenticationError(
+                "Wrong passphrase, backup vault destroyed".to_string(),
+            ));
+        }
+
+        Err(DomainError::AuthenticationError(format!(
+            "Wrong passphrase, {} attempts remaining",
+            remaining
+        )))
+    }
+}
@@ -0,0 +1,40 @@
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::load_call;
+use crate::domain::{
+    entities::{Call, CallStatus},
+    repositories::CallRepository,
+    DomainError, DomainResult,
+};
+
+pub struct AnswerCall {
+    call_repo: Arc<dyn CallRepository>,
+}
+
+impl AnswerCall {
+    pub fn new(call_repo: Arc<dyn CallRepository>) -> Self {
+        Self { call_repo }
+    }
+
+    /// Accepts a ringing call on one of the callee's devices. Only the first device to answer
+    /// wins; the others get a conflict.
+    pub async fn execute(&self, user_id: Uuid, device_id: Option<&str>, call_id: Uuid) -> DomainResult<Call> {
+        let call = load_call(self.call_repo.as_ref(), call_id, user_id).await?;
+
+        if call.callee_id != user_id {
+            return Err(DomainError::AuthorizationError("Only the callee can answer a call".to_string()));
+        }
+
+        // The timeout job may not have caught up with a call that stopped ringing
+        if call.status != CallStatus::Ringing || call.ring_expires_at <= Utc::now() {
+            return Err(DomainError::Conflict("Call is no longer ringing".to_string()));
+        }
+
+        self.call_repo
+            .transition(call.id, CallStatus::Ringing, CallStatus::Accepted, device_id)
+            .await?
+            .ok_or_else(|| DomainError::Conflict("Call was already answered or ended".to_string()))
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{finish_call, load_call};
+use crate::domain::{
+    entities::{Call, CallStatus, SyncEvent},
+    repositories::{CallRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct EndCall {
+    call_repo: Arc<dyn CallRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl EndCall {
+    pub fn new(
+        call_repo: Arc<dyn CallRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            call_repo,
+            message_repo,
+        }
+    }
+
+    /// Hangs up. While the call is ringing, the callee hanging up rejects it and the caller
+    /// hanging up leaves it missed; an accepted call simply ends.
+    pub async fn execute(&self, user_id: Uuid, call_id: Uuid) -> DomainResult<(Call, SyncEvent)> {
+        let call = load_call(self.call_repo.as_ref(), call_id, user_id).await?;
+
+        let to = match call.status {
+            CallStatus::Ringing if call.callee_id == user_id => CallStatus::Rejected,
+            CallStatus::Ringing => CallStatus::Missed,
+            _ => CallStatus::Ended,
+        };
+
+        finish_call(self.call_repo.as_ref(), self.message_repo.as_ref(), &call, to)
+            .await?
+            .ok_or_else(|| DomainError::Conflict("Call was already answered or ended".to_string()))
+    }
+}
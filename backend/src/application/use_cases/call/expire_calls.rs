@@ -0,0 +1,61 @@
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use super::helpers::finish_call;
+use crate::domain::{
+    entities::{Call, CallStatus, SyncEvent, CALL_LIVENESS_TIMEOUT_SECONDS},
+    repositories::{CallRepository, MessageRepository},
+    DomainResult,
+};
+
+pub struct ExpireCalls {
+    call_repo: Arc<dyn CallRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl ExpireCalls {
+    pub fn new(
+        call_repo: Arc<dyn CallRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            call_repo,
+            message_repo,
+        }
+    }
+
+    /// Marks calls that rang out as missed and ends accepted calls one of whose devices went
+    /// silent, e.g. because the server holding its connection crashed. A failure on one call is
+    /// logged and does not stop the others.
+    pub async fn execute(&self) -> DomainResult<Vec<(Call, SyncEvent)>> {
+        let now = Utc::now();
+        let mut finished = Vec::new();
+
+        for call in self.call_repo.find_ringing_expired(now).await? {
+            self.finish(&call, CallStatus::Missed, &mut finished).await;
+        }
+
+        let silent_since = now - Duration::seconds(CALL_LIVENESS_TIMEOUT_SECONDS);
+        for call in self.call_repo.find_unresponsive(silent_since).await? {
+            self.finish(&call, CallStatus::Ended, &mut finished).await;
+        }
+
+        Ok(finished)
+    }
+
+    async fn finish(&self, call: &Call, to: CallStatus, finished: &mut Vec<(Call, SyncEvent)>) {
+        match finish_call(
+            self.call_repo.as_ref(),
+            self.message_repo.as_ref(),
+            call,
+            to,
+        )
+        .await
+        {
+            Ok(Some(done)) => finished.push(done),
+            // Answered or hung up since it was loaded
+            Ok(None) => {}
+            Err(e) => tracing::error!("Failed to expire call {}: {}", call.id, e),
+        }
+    }
+}
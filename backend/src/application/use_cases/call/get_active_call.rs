@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::load_call;
+use crate::domain::{
+    entities::Call,
+    repositories::CallRepository,
+    DomainError, DomainResult,
+};
+
+pub struct GetActiveCall {
+    call_repo: Arc<dyn CallRepository>,
+}
+
+impl GetActiveCall {
+    pub fn new(call_repo: Arc<dyn CallRepository>) -> Self {
+        Self { call_repo }
+    }
+
+    /// Loads a ringing or accepted call of the user, e.g. before relaying signaling to the peer.
+    pub async fn execute(&self, user_id: Uuid, call_id: Uuid) -> DomainResult<Call> {
+        let call = load_call(self.call_repo.as_ref(), call_id, user_id).await?;
+
+        if !call.status.is_active() {
+            return Err(DomainError::Conflict(format!("Call is already {:?}", call.status)));
+        }
+
+        Ok(call)
+    }
+}
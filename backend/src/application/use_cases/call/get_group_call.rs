@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::GroupCall,
+    repositories::{ConversationRepository, GroupCallRepository},
+    DomainError, DomainResult,
+};
+
+pub struct GetGroupCall {
+    group_call_repo: Arc<dyn GroupCallRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl GetGroupCall {
+    pub fn new(
+        group_call_repo: Arc<dyn GroupCallRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+    ) -> Self {
+        Self {
+            group_call_repo,
+            conversation_repo,
+        }
+    }
+
+    /// The call in progress in the group and who is in it, for the "call in progress, join" banner.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+    ) -> DomainResult<Option<(GroupCall, Vec<Uuid>)>> {
+        if !self.conversation_repo.is_participant(conversation_id, user_id).await? {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+
+        let Some(call) = self.group_call_repo.find_active(conversation_id).await? else {
+            return Ok(None);
+        };
+        let participants = self.group_call_repo.find_participants(call.id).await?;
+
+        Ok(Some((call, participants)))
+    }
+}
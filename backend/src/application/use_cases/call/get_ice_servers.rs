@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::{IceServerResponse, IceServersResponse};
+use crate::domain::{
+    repositories::UserRepository,
+    services::IceServerProvider,
+    DomainError, DomainResult,
+};
+
+pub struct GetIceServers {
+    user_repo: Arc<dyn UserRepository>,
+    ice_server_provider: Arc<dyn IceServerProvider>,
+}
+
+impl GetIceServers {
+    pub fn new(user_repo: Arc<dyn UserRepository>, ice_server_provider: Arc<dyn IceServerProvider>) -> Self {
+        Self {
+            user_repo,
+            ice_server_provider,
+        }
+    }
+
+    /// ICE servers for the user's next call. TURN credentials are bound to the user, and their
+    /// lifetime and the recommended bitrate depend on the subscription tier.
+    pub async fn execute(&self, user_id: Uuid) -> DomainResult<IceServersResponse> {
+        let user = self.user_repo.find_by_id(user_id).await?
+            .ok_or_else(|| DomainError::NotFound("User not found".to_string()))?;
+
+        let tier = user.subscription_tier;
+        let ttl_seconds = tier.turn_credential_ttl_seconds();
+
+        let ice_servers = self.ice_server_provider
+            .ice_servers(user_id, ttl_seconds)
+            .into_iter()
+            .map(|server| IceServerResponse {
+                urls: server.urls,
+                username: server.username,
+                credential: server.credential,
+            })
+            .collect();
+
+        Ok(IceServersResponse {
+            ice_servers,
+            ttl_seconds,
+            recommended_bitrate_kbps: tier.recommended_call_bitrate_kbps(),
+        })
+    }
+}
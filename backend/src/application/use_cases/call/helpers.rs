@@ -0,0 +1,130 @@
+use uuid::Uuid;
+
+use crate::application::use_cases::group::helpers::post_system_message;
+use crate::application::MessageResponse;
+use crate::domain::{
+    entities::{Call, CallStatus, GroupCall, GroupEvent, Message, SyncEvent, SyncEventType},
+    repositories::{CallRepository, GroupCallRepository, MessageRepository, SyncRepository},
+    services::SfuProvider,
+    DomainError, DomainResult,
+};
+
+/// Loads a call the user is a party of. Other users' calls are reported as missing.
+pub(super) async fn load_call(
+    call_repo: &dyn CallRepository,
+    call_id: Uuid,
+    user_id: Uuid,
+) -> DomainResult<Call> {
+    call_repo
+        .find_by_id(call_id)
+        .await?
+        .filter(|call| call.is_party(user_id))
+        .ok_or_else(|| DomainError::NotFound("Call not found".to_string()))
+}
+
+/// Writes the `CallSignal` message for a call that reached a final status together with its
+/// sync log entry.
+pub(super) async fn post_call_record(
+    message_repo: &dyn MessageRepository,
+    call: &Call,
+) -> DomainResult<SyncEvent> {
+    let message = Message::call_signal(call);
+
+    let payload = serde_json::to_value(MessageResponse::from(&message))
+        .map_err(|e| DomainError::InternalError(format!("Serialization error: {}", e)))?;
+    let event = SyncEvent::new(call.conversation_id, SyncEventType::MessageCreated, payload);
+
+    let (_, event) = message_repo.create_with_event(&message, &event).await?;
+    Ok(event)
+}
+
+/// Moves a call into a final status and records it in the conversation. Returns None when
+/// another request already moved the call on.
+pub(super) async fn finish_call(
+    call_repo: &dyn CallRepository,
+    message_repo: &dyn MessageRepository,
+    call: &Call,
+    to: CallStatus,
+) -> DomainResult<Option<(Call, SyncEvent)>> {
+    if !call.status.can_transition_to(to) {
+        return Err(DomainError::Conflict(format!("Call is already {:?}", call.status)));
+    }
+
+    let Some(finished) = call_repo.transition(call.id, call.status, to, None).await? else {
+        return Ok(None);
+    };
+
+    let event = post_call_record(message_repo, &finished).await?;
+    Ok(Some((finished, event)))
+}
+
+/// Records who is in a group call, so devices that sync later see the current state.
+pub(super) async fn record_group_call_state(
+    sync_repo: &dyn SyncRepository,
+    call: &GroupCall,
+    participants: &[Uuid],
+) -> DomainResult<SyncEvent> {
+    sync_repo
+        .append(&SyncEvent::new(
+            call.conversation_id,
+            SyncEventType::GroupCallUpdated,
+            serde_json::json!({
+                "call_id": call.id,
+                "active": call.is_active(),
+                "is_video": call.is_video,
+                "participants": participants,
+            }),
+        ))
+        .await
+}
+
+/// Takes `user_ids` out of a group call. If that empties it, the call is ended, its SFU room
+/// closed and the end posted to the group; otherwise the new participant list is recorded.
+pub(crate) async fn leave_group_call(
+    group_call_repo: &dyn GroupCallRepository,
+    message_repo: &dyn MessageRepository,
+    sync_repo: &dyn SyncRepository,
+    sfu_provider: &dyn SfuProvider,
+    call: &GroupCall,
+    user_ids: &[Uuid],
+) -> DomainResult<Vec<SyncEvent>> {
+    for user_id in user_ids {
+        group_call_repo.remove_participant(call.id, *user_id).await?;
+    }
+    let participants = group_call_repo.find_participants(call.id).await?;
+
+    if !participants.is_empty() {
+        return Ok(vec![record_group_call_state(sync_repo, call, &participants).await?]);
+    }
+
+    // Someone may have joined in the meantime, in which case the call goes on
+    let Some(ended) = group_call_repo.end_if_empty(call.id).await? else {
+        let participants = group_call_repo.find_participants(call.id).await?;
+        return Ok(vec![record_group_call_state(sync_repo, call, &participants).await?]);
+    };
+
+    // The room closes itself once empty, so a failure here is not fatal
+    if let Err(e) = sfu_provider.delete_room(&ended.room_name).await {
+        tracing::warn!("Failed to delete SFU room {}: {}", ended.room_name, e);
+    }
+
+    let duration_seconds = ended.ended_at
+        .map(|ended_at| (ended_at - ended.started_at).num_seconds())
+        .unwrap_or_default();
+
+    let mut events = vec![record_group_call_state(sync_repo, &ended, &[]).await?];
+    events.push(
+        post_system_message(
+            message_repo,
+            ended.conversation_id,
+            user_ids.last().copied().unwrap_or(ended.started_by),
+            GroupEvent::GroupCallEnded {
+                call_id: ended.id,
+                duration_seconds,
+            },
+        )
+        .await?,
+    );
+
+    Ok(events)
+}
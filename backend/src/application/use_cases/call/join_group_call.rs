@@ -0,0 +1,134 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::record_group_call_state;
+use crate::application::use_cases::group::helpers::post_system_message;
+use crate::domain::{
+    entities::{GroupCall, GroupEvent, SyncEvent},
+    repositories::{ConversationRepository, GroupCallRepository, MessageRepository, SyncRepository},
+    services::{SfuGrants, SfuProvider},
+    DomainError, DomainResult,
+};
+
+/// Lifetime of a join token; clients must be in the room before it expires. The SFU refreshes
+/// tokens of connected clients itself, so this is kept short: tokens cannot be revoked, and a
+/// member removed from the group must not be able to reconnect with one they already hold.
+pub const GROUP_CALL_TOKEN_TTL_SECONDS: i64 = 5 * 60;
+
+/// Everything a client needs to connect to the SFU room of a group call.
+pub struct GroupCallJoin {
+    pub call: GroupCall,
+    pub participants: Vec<Uuid>,
+    pub sfu_url: String,
+    pub token: String,
+    pub grants: SfuGrants,
+}
+
+pub struct JoinGroupCall {
+    group_call_repo: Arc<dyn GroupCallRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    sfu_provider: Arc<dyn SfuProvider>,
+}
+
+impl JoinGroupCall {
+    pub fn new(
+        group_call_repo: Arc<dyn GroupCallRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        sfu_provider: Arc<dyn SfuProvider>,
+    ) -> Self {
+        Self {
+            group_call_repo,
+            conversation_repo,
+            message_repo,
+            sync_repo,
+            sfu_provider,
+        }
+    }
+
+    /// Joins the group's call, starting one if none is in progress. Members of groups where
+    /// only admins can post may listen but not publish unless they are admins.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        is_video: bool,
+    ) -> DomainResult<(GroupCallJoin, Vec<SyncEvent>)> {
+        let conversation = self.conversation_repo.find_by_id(conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+        if !conversation.is_group() {
+            return Err(DomainError::ValidationError("Group calls are only available in groups".to_string()));
+        }
+
+        let participant = self.conversation_repo.find_participant(conversation_id, user_id).await?
+            .ok_or_else(|| DomainError::AuthorizationError("Not a participant of this conversation".to_string()))?;
+
+        let mut events = Vec::new();
+
+        let call = match self.group_call_repo.find_active(conversation_id).await? {
+            // The SFU closes rooms that stayed empty for a while, so make sure it is still open
+            Some(call) => {
+                self.sfu_provider.create_room(&call.room_name).await?;
+                call
+            }
+            None => {
+                let call = GroupCall::new(conversation_id, user_id, is_video);
+                self.sfu_provider.create_room(&call.room_name).await?;
+
+                match self.group_call_repo.create(&call).await {
+                    Ok(call) => {
+                        events.push(
+                            post_system_message(
+                                self.message_repo.as_ref(),
+                                conversation_id,
+                                user_id,
+                                GroupEvent::GroupCallStarted {
+                                    actor_id: user_id,
+                                    call_id: call.id,
+                                    is_video,
+                                },
+                            )
+                            .await?,
+                        );
+                        call
+                    }
+                    // Another member started the call at the same time; join theirs instead
+                    Err(DomainError::Conflict(_)) => {
+                        if let Err(e) = self.sfu_provider.delete_room(&call.room_name).await {
+                            tracing::warn!("Failed to delete unused SFU room {}: {}", call.room_name, e);
+                        }
+                        self.group_call_repo.find_active(conversation_id).await?
+                            .ok_or_else(|| DomainError::Conflict("Group call ended while joining, retry".to_string()))?
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+
+        self.group_call_repo.add_participant(call.id, user_id).await?;
+        let participants = self.group_call_repo.find_participants(call.id).await?;
+        events.push(record_group_call_state(self.sync_repo.as_ref(), &call, &participants).await?);
+
+        let grants = SfuGrants {
+            can_publish: !conversation.only_admins_can_post() || participant.is_admin(),
+            can_subscribe: true,
+        };
+        let token = self.sfu_provider
+            .join_token(&call.room_name, user_id, grants, GROUP_CALL_TOKEN_TTL_SECONDS)?;
+
+        Ok((
+            GroupCallJoin {
+                call,
+                participants,
+                sfu_url: self.sfu_provider.url(),
+                token,
+                grants,
+            },
+            events,
+        ))
+    }
+}
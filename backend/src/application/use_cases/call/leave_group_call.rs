@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::leave_group_call;
+use crate::domain::{
+    entities::SyncEvent,
+    repositories::{GroupCallRepository, MessageRepository, SyncRepository},
+    services::SfuProvider,
+    DomainError, DomainResult,
+};
+
+pub struct LeaveGroupCall {
+    group_call_repo: Arc<dyn GroupCallRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    sfu_provider: Arc<dyn SfuProvider>,
+}
+
+impl LeaveGroupCall {
+    pub fn new(
+        group_call_repo: Arc<dyn GroupCallRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        sfu_provider: Arc<dyn SfuProvider>,
+    ) -> Self {
+        Self {
+            group_call_repo,
+            message_repo,
+            sync_repo,
+            sfu_provider,
+        }
+    }
+
+    /// Leaves the group's call. The last one out ends the call and closes its SFU room.
+    pub async fn execute(&self, user_id: Uuid, conversation_id: Uuid) -> DomainResult<Vec<SyncEvent>> {
+        let call = self.group_call_repo.find_active(conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("No call in progress".to_string()))?;
+
+        leave_group_call(
+            self.group_call_repo.as_ref(),
+            self.message_repo.as_ref(),
+            self.sync_repo.as_ref(),
+            self.sfu_provider.as_ref(),
+            &call,
+            &[user_id],
+        )
+        .await
+    }
+}
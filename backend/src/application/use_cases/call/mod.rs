@@ -0,0 +1,24 @@
+pub(crate) mod helpers;
+pub mod start_call;
+pub mod answer_call;
+pub mod end_call;
+pub mod get_active_call;
+pub mod expire_calls;
+pub mod track_call_presence;
+pub mod get_ice_servers;
+pub mod join_group_call;
+pub mod leave_group_call;
+pub mod get_group_call;
+pub mod sweep_group_calls;
+
+pub use start_call::StartCall;
+pub use answer_call::AnswerCall;
+pub use end_call::EndCall;
+pub use get_active_call::GetActiveCall;
+pub use expire_calls::ExpireCalls;
+pub use track_call_presence::TrackCallPresence;
+pub use get_ice_servers::GetIceServers;
+pub use join_group_call::JoinGroupCall;
+pub use leave_group_call::LeaveGroupCall;
+pub use get_group_call::GetGroupCall;
+pub use sweep_group_calls::SweepGroupCalls;
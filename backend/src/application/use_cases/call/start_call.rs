@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::post_call_record;
+use crate::application::CallRequest;
+use crate::domain::{
+    entities::{Call, SyncEvent},
+    repositories::{CallRepository, ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct StartCall {
+    call_repo: Arc<dyn CallRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl StartCall {
+    pub fn new(
+        call_repo: Arc<dyn CallRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            call_repo,
+            conversation_repo,
+            message_repo,
+        }
+    }
+
+    /// Starts ringing the target user from the caller's device. If they are already in a call,
+    /// the call is recorded as busy straight away and the sync event of its `CallSignal`
+    /// message is returned as well.
+    pub async fn execute(
+        &self,
+        caller_id: Uuid,
+        device_id: Option<&str>,
+        request: &CallRequest,
+    ) -> DomainResult<(Call, Option<SyncEvent>)> {
+        let conversation = self.conversation_repo.find_by_id(request.conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+        if conversation.is_group() || conversation.is_channel() {
+            return Err(DomainError::ValidationError("Calls are only supported in private conversations".to_string()));
+        }
+
+        if request.target_user_id == caller_id {
+            return Err(DomainError::ValidationError("Cannot call yourself".to_string()));
+        }
+
+        if !self.conversation_repo.is_participant(conversation.id, caller_id).await? {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+        if !self.conversation_repo.is_participant(conversation.id, request.target_user_id).await? {
+            return Err(DomainError::ValidationError("Target user is not in this conversation".to_string()));
+        }
+
+        if self.call_repo.find_active_by_user(caller_id).await?.is_some() {
+            return Err(DomainError::Conflict("Already in a call".to_string()));
+        }
+
+        if self.call_repo.find_active_by_user(request.target_user_id).await?.is_some() {
+            return self.record_busy(caller_id, request).await;
+        }
+
+        let call = Call::new(conversation.id, caller_id, request.target_user_id, request.is_video);
+        match self.call_repo.create(&call, device_id).await {
+            Ok(call) => Ok((call, None)),
+            // A concurrent call got to one of the parties first
+            Err(DomainError::Conflict(_)) if self.call_repo.find_active_by_user(caller_id).await?.is_none() => {
+                self.record_busy(caller_id, request).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn record_busy(&self, caller_id: Uuid, request: &CallRequest) -> DomainResult<(Call, Option<SyncEvent>)> {
+        let call = self.call_repo
+            .create(&Call::busy(request.conversation_id, caller_id, request.target_user_id, request.is_video), None)
+            .await?;
+        let event = post_call_record(self.message_repo.as_ref(), &call).await?;
+        Ok((call, Some(event)))
+    }
+}
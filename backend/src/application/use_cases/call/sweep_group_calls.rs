@@ -0,0 +1,77 @@
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use super::helpers::leave_group_call;
+use super::join_group_call::GROUP_CALL_TOKEN_TTL_SECONDS;
+use crate::domain::{
+    entities::SyncEvent,
+    repositories::{GroupCallRepository, MessageRepository, SyncRepository},
+    services::SfuProvider,
+    DomainResult,
+};
+
+pub struct SweepGroupCalls {
+    group_call_repo: Arc<dyn GroupCallRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    sfu_provider: Arc<dyn SfuProvider>,
+}
+
+impl SweepGroupCalls {
+    pub fn new(
+        group_call_repo: Arc<dyn GroupCallRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        sfu_provider: Arc<dyn SfuProvider>,
+    ) -> Self {
+        Self {
+            group_call_repo,
+            message_repo,
+            sync_repo,
+            sfu_provider,
+        }
+    }
+
+    /// Reconciles active calls with their SFU rooms. Participants who are not connected to the
+    /// room although their join token has expired crashed or lost connection without leaving,
+    /// and are taken out of the call; calls left empty, or whose room the SFU closed, are ended.
+    pub async fn execute(&self) -> DomainResult<Vec<SyncEvent>> {
+        let joined_before = Utc::now() - Duration::seconds(GROUP_CALL_TOKEN_TTL_SECONDS);
+        let mut events = Vec::new();
+
+        for call in self.group_call_repo.find_all_active().await? {
+            let connected = match self.sfu_provider.list_participants(&call.room_name).await {
+                Ok(connected) => connected.unwrap_or_default(),
+                Err(e) => {
+                    tracing::warn!("Failed to list participants of SFU room {}: {}", call.room_name, e);
+                    continue;
+                }
+            };
+
+            let gone: Vec<_> = self.group_call_repo
+                .find_participants_joined_before(call.id, joined_before)
+                .await?
+                .into_iter()
+                .filter(|user_id| !connected.contains(user_id))
+                .collect();
+
+            if gone.is_empty() && !self.group_call_repo.find_participants(call.id).await?.is_empty() {
+                continue;
+            }
+
+            events.extend(
+                leave_group_call(
+                    self.group_call_repo.as_ref(),
+                    self.message_repo.as_ref(),
+                    self.sync_repo.as_ref(),
+                    self.sfu_provider.as_ref(),
+                    &call,
+                    &gone,
+                )
+                .await?,
+            );
+        }
+
+        Ok(events)
+    }
+}
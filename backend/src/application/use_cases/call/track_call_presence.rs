@@ -0,0 +1,51 @@
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::finish_call;
+use crate::domain::{
+    entities::{Call, CallStatus, SyncEvent},
+    repositories::{CallRepository, MessageRepository},
+    DomainResult,
+};
+
+pub struct TrackCallPresence {
+    call_repo: Arc<dyn CallRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl TrackCallPresence {
+    pub fn new(
+        call_repo: Arc<dyn CallRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            call_repo,
+            message_repo,
+        }
+    }
+
+    /// Keeps the device's active call alive; called on every heartbeat of its connection.
+    pub async fn heartbeat(&self, user_id: Uuid, device_id: Option<&str>) -> DomainResult<()> {
+        self.call_repo.touch(user_id, device_id, Utc::now()).await
+    }
+
+    /// Hangs up the call the device was in once its connection closes. A call the user placed
+    /// that is still ringing is left missed.
+    pub async fn disconnected(
+        &self,
+        user_id: Uuid,
+        device_id: Option<&str>,
+    ) -> DomainResult<Option<(Call, SyncEvent)>> {
+        let Some(call) = self.call_repo.find_active_by_device(user_id, device_id).await? else {
+            return Ok(None);
+        };
+
+        let to = match call.status {
+            CallStatus::Ringing => CallStatus::Missed,
+            _ => CallStatus::Ended,
+        };
+
+        finish_call(self.call_repo.as_ref(), self.message_repo.as_ref(), &call, to).await
+    }
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Conversation, Participant, ParticipantRole},
+    repositories::ConversationRepository,
+    DomainResult,
+};
+
+pub struct CreateChannel {
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl CreateChannel {
+    pub fn new(conversation_repo: Arc<dyn ConversationRepository>) -> Self {
+        Self { conversation_repo }
+    }
+
+    pub async fn execute(&self, owner_id: Uuid, name: String) -> DomainResult<Conversation> {
+        let channel = Conversation::new_channel(name);
+        let owner = Participant::new(channel.id, owner_id, ParticipantRole::Owner);
+
+        self.conversation_repo.create(&channel, &[owner]).await
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct GetChannelViews {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl GetChannelViews {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            message_repo,
+        }
+    }
+
+    /// Returns the view counters of the given channel posts.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+        message_ids: Vec<Uuid>,
+    ) -> DomainResult<Vec<(Uuid, i64)>> {
+        let channel = self.conversation_repo.find_by_id(channel_id).await?
+            .filter(|conversation| conversation.is_channel())
+            .ok_or_else(|| DomainError::NotFound("Channel not found".to_string()))?;
+
+        if !self.conversation_repo.is_participant(channel.id, user_id).await? {
+            return Err(DomainError::AuthorizationError("Not subscribed to this channel".to_string()));
+        }
+
+        self.message_repo.find_view_counts(channel.id, &message_ids).await
+    }
+}
@@ -0,0 +1,9 @@
+pub mod create_channel;
+pub mod subscribe_channel;
+pub mod unsubscribe_channel;
+pub mod get_channel_views;
+
+pub use create_channel::CreateChannel;
+pub use subscribe_channel::SubscribeChannel;
+pub use unsubscribe_channel::UnsubscribeChannel;
+pub use get_channel_views::GetChannelViews;
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Participant, ParticipantRole},
+    repositories::ConversationRepository,
+    DomainError, DomainResult,
+};
+
+pub struct SubscribeChannel {
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl SubscribeChannel {
+    pub fn new(conversation_repo: Arc<dyn ConversationRepository>) -> Self {
+        Self { conversation_repo }
+    }
+
+    /// Subscribes a user to a channel. Unlike joining a group, no system message is posted.
+    pub async fn execute(&self, user_id: Uuid, channel_id: Uuid) -> DomainResult<()> {
+        let channel = self.conversation_repo.find_by_id(channel_id).await?
+            .filter(|conversation| conversation.is_channel())
+            .ok_or_else(|| DomainError::NotFound("Channel not found".to_string()))?;
+
+        if self.conversation_repo.is_banned(channel.id, user_id).await? {
+            return Err(DomainError::AuthorizationError("You have been banned from this channel".to_string()));
+        }
+        if self.conversation_repo.is_participant(channel.id, user_id).await? {
+            return Err(DomainError::Conflict("Already subscribed to this channel".to_string()));
+        }
+
+        self.conversation_repo
+            .add_participant(&Participant::new(channel.id, user_id, ParticipantRole::Member), None)
+            .await
+    }
+}
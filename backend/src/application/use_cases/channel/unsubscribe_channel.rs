@@ -0,0 +1,32 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    repositories::ConversationRepository,
+    DomainError, DomainResult,
+};
+
+pub struct UnsubscribeChannel {
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl UnsubscribeChannel {
+    pub fn new(conversation_repo: Arc<dyn ConversationRepository>) -> Self {
+        Self { conversation_repo }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, channel_id: Uuid) -> DomainResult<()> {
+        let channel = self.conversation_repo.find_by_id(channel_id).await?
+            .filter(|conversation| conversation.is_channel())
+            .ok_or_else(|| DomainError::NotFound("Channel not found".to_string()))?;
+
+        let subscription = self.conversation_repo.find_participant(channel.id, user_id).await?
+            .ok_or_else(|| DomainError::NotFound("Not subscribed to this channel".to_string()))?;
+
+        if subscription.is_owner() {
+            return Err(DomainError::ValidationError("Transfer ownership before leaving the channel".to_string()));
+        }
+
+        self.conversation_repo.remove_participant(channel.id, user_id).await
+    }
+}
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    repositories::SyncRepository,
+    DomainResult,
+};
+
+pub struct AcknowledgeSync {
+    sync_repo: Arc<dyn SyncRepository>,
+}
+
+impl AcknowledgeSync {
+    pub fn new(sync_repo: Arc<dyn SyncRepository>) -> Self {
+        Self { sync_repo }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, device_id: String, seq: i64) -> DomainResult<()> {
+        self.sync_repo.update_cursor(user_id, &device_id, seq).await
+    }
+}
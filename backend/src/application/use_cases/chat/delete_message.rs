@@ -0,0 +1,41 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{SyncEvent, SyncEventType},
+    repositories::{MessageRepository, SyncRepository},
+    DomainError, DomainResult,
+};
+
+pub struct DeleteMessage {
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+}
+
+impl DeleteMessage {
+    pub fn new(message_repo: Arc<dyn MessageRepository>, sync_repo: Arc<dyn SyncRepository>) -> Self {
+        Self { message_repo, sync_repo }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<SyncEvent> {
+        let message = self.message_repo.find_by_id(message_id).await?
+            .ok_or_else(|| DomainError::NotFound("Message not found".to_string()))?;
+
+        if message.sender_id != Some(user_id) || message.is_server_generated() {
+            return Err(DomainError::AuthorizationError("Only the sender can delete a message".to_string()));
+        }
+
+        if message.is_deleted {
+            return Err(DomainError::Conflict("Message has already been deleted".to_string()));
+        }
+
+        self.message_repo.delete(message_id).await?;
+        self.sync_repo.redact_message(message_id).await?;
+
+        let payload = serde_json::json!({ "message_id": message_id });
+
+        self.sync_repo
+            .append(&SyncEvent::new(message.conversation_id, SyncEventType::MessageDeleted, payload))
+            .await
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{SyncEvent, SyncEventType},
+    repositories::{MessageRepository, SyncRepository},
+    DomainError, DomainResult,
+};
+
+pub struct EditMessage {
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+}
+
+impl EditMessage {
+    pub fn new(message_repo: Arc<dyn MessageRepository>, sync_repo: Arc<dyn SyncRepository>) -> Self {
+        Self { message_repo, sync_repo }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, message_id: Uuid, content: String) -> DomainResult<SyncEvent> {
+        let mut message = self.message_repo.find_by_id(message_id).await?
+            .ok_or_else(|| DomainError::NotFound("Message not found".to_string()))?;
+
+        if message.sender_id != Some(user_id) || message.is_server_generated() {
+            return Err(DomainError::AuthorizationError("Only the sender can edit a message".to_string()));
+        }
+
+        if message.is_deleted {
+            return Err(DomainError::Conflict("Message has been deleted".to_string()));
+        }
+
+        message.content = content;
+        let updated = self.message_repo.update(&message).await?;
+
+        let payload = serde_json::json!({
+            "message_id": updated.id,
+            "content": updated.content,
+        });
+
+        self.sync_repo
+            .append(&SyncEvent::new(updated.conversation_id, SyncEventType::MessageEdited, payload))
+            .await
+    }
+}
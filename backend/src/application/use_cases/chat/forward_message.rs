@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::SendMessage;
+use crate::application::{ForwardMessageRequest, SendMessageRequest};
+use crate::domain::{
+    entities::{Message, MessageType, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct ForwardMessage {
+    message_repo: Arc<dyn MessageRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+    send_message: Arc<SendMessage>,
+}
+
+impl ForwardMessage {
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+        send_message: Arc<SendMessage>,
+    ) -> Self {
+        Self {
+            message_repo,
+            conversation_repo,
+            send_message,
+        }
+    }
+
+    /// Forwards a message to each target conversation with the content the client re-encrypted
+    /// for it. Fails as a whole if the origin cannot be forwarded; otherwise returns one result
+    /// per target, in request order.
+    pub async fn execute(
+        &self,
+        sender_id: Uuid,
+        request: ForwardMessageRequest,
+    ) -> DomainResult<Vec<DomainResult<(Message, Option<SyncEvent>)>>> {
+        let origin = self.message_repo.find_by_id(request.message_id).await?
+            .filter(|message| !message.is_deleted && !message.is_expired())
+            .ok_or_else(|| DomainError::NotFound("Message not found".to_string()))?;
+
+        let conversation = self.conversation_repo.find_by_id(origin.conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+        if !self.conversation_repo.is_participant(conversation.id, sender_id).await? {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+        if !conversation.allows_forwarding() {
+            return Err(DomainError::AuthorizationError("Forwarding is disabled in this conversation".to_string()));
+        }
+        if origin.message_type == MessageType::System || origin.self_destruct_seconds.is_some() {
+            return Err(DomainError::ValidationError("This message cannot be forwarded".to_string()));
+        }
+
+        let mut results = Vec::with_capacity(request.targets.len());
+        for target in request.targets {
+            let send_request = SendMessageRequest {
+                client_message_id: target.client_message_id,
+                conversation_id: target.conversation_id,
+                content: target.content,
+                message_type: format!("{:?}", origin.message_type),
+                reply_to_id: None,
+                self_destruct_in_seconds: None,
+                self_destruct_on_read: None,
+                sender_key_epoch: target.sender_key_epoch,
+            };
+            results.push(self.send_message.forward(sender_id, send_request, &origin).await);
+        }
+
+        Ok(results)
+    }
+}
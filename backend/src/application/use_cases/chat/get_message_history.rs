@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Message, ThreadSummary},
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct GetMessageHistory {
+    message_repo: Arc<dyn MessageRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl GetMessageHistory {
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+    ) -> Self {
+        Self {
+            message_repo,
+            conversation_repo,
+        }
+    }
+
+    /// Returns a newest-first page of messages before the `(created_at, id)` cursor, each with its
+    /// thread summary.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        before: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> DomainResult<Vec<(Message, ThreadSummary)>> {
+        if !self.conversation_repo.is_participant(conversation_id, user_id).await? {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+
+        self.message_repo.find_history(conversation_id, before, limit).await
+    }
+}
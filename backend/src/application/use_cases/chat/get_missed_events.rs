@@ -0,0 +1,41 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::SyncEvent,
+    repositories::SyncRepository,
+    DomainResult,
+};
+
+pub struct GetMissedEvents {
+    sync_repo: Arc<dyn SyncRepository>,
+}
+
+impl GetMissedEvents {
+    pub fn new(sync_repo: Arc<dyn SyncRepository>) -> Self {
+        Self { sync_repo }
+    }
+
+    /// Returns the next page of events after `after_seq`, oldest first.
+    pub async fn execute(&self, user_id: Uuid, after_seq: i64, limit: i64) -> DomainResult<Vec<SyncEvent>> {
+        self.sync_repo.find_since(user_id, after_seq, limit).await
+    }
+
+    /// Resolves where replay should start for a device: the client-supplied position wins,
+    /// otherwise the last position the device acknowledged.
+    pub async fn start_cursor(
+        &self,
+        user_id: Uuid,
+        device_id: Option<&str>,
+        since: Option<i64>,
+    ) -> DomainResult<Option<i64>> {
+        if since.is_some() {
+            return Ok(since);
+        }
+
+        match device_id {
+            Some(device_id) => self.sync_repo.get_cursor(user_id, device_id).await,
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::Message,
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct GetPinnedMessages {
+    message_repo: Arc<dyn MessageRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl GetPinnedMessages {
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+    ) -> Self {
+        Self {
+            message_repo,
+            conversation_repo,
+        }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, conversation_id: Uuid) -> DomainResult<Vec<Message>> {
+        if !self.conversation_repo.is_participant(conversation_id, user_id).await? {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+
+        self.message_repo.find_pinned(conversation_id).await
+    }
+}
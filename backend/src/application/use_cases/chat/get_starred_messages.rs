@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{entities::Message, repositories::MessageRepository, DomainResult};
+
+pub struct GetStarredMessages {
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl GetStarredMessages {
+    pub fn new(message_repo: Arc<dyn MessageRepository>) -> Self {
+        Self { message_repo }
+    }
+
+    /// Returns a page of the user's starred messages with the time each was starred.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> DomainResult<Vec<(Message, DateTime<Utc>)>> {
+        self.message_repo.find_starred(user_id, before, limit).await
+    }
+}
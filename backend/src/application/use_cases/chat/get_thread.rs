@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::Message,
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct GetThread {
+    message_repo: Arc<dyn MessageRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl GetThread {
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+    ) -> Self {
+        Self {
+            message_repo,
+            conversation_repo,
+        }
+    }
+
+    /// Returns the root message and an oldest-first page of its replies after the `(created_at, id)`
+    /// cursor.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        root_id: Uuid,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> DomainResult<(Message, Vec<Message>)> {
+        let root = self.message_repo.find_by_id(root_id).await?
+            .filter(|message| !message.is_deleted && !message.is_expired())
+            .ok_or_else(|| DomainError::NotFound("Message not found".to_string()))?;
+
+        if !self.conversation_repo.is_participant(root.conversation_id, user_id).await? {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+
+        let replies = self.message_repo.find_replies(root.id, after, limit).await?;
+
+        Ok((root, replies))
+    }
+}
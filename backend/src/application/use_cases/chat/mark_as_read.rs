@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{SyncEvent, SyncEventType},
+    repositories::{ConversationRepository, MessageRepository, SyncRepository},
+    services::MessageExpiryScheduler,
+    DomainError, DomainResult,
+};
+
+pub struct MarkAsRead {
+    message_repo: Arc<dyn MessageRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    expiry_scheduler: Arc<dyn MessageExpiryScheduler>,
+}
+
+impl MarkAsRead {
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        expiry_scheduler: Arc<dyn MessageExpiryScheduler>,
+    ) -> Self {
+        Self { message_repo, conversation_repo, sync_repo, expiry_scheduler }
+    }
+
+    /// Records a read receipt and returns the sync event to fan out. Reads in channels only
+    /// bump view counters and return `None`. Anyone may subscribe to a channel, but only
+    /// subscribers read its posts, so views from anyone else are refused rather than counted.
+    pub async fn execute(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<Option<SyncEvent>> {
+        let message = self.message_repo.find_by_id(message_id).await?
+            .ok_or_else(|| DomainError::NotFound("Message not found".to_string()))?;
+
+        let conversation = self.conversation_repo.find_by_id(message.conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+        let is_participant = self.conversation_repo.is_participant(conversation.id, user_id).await?;
+
+        if conversation.is_channel() {
+            if !is_participant {
+                return Err(DomainError::AuthorizationError("Not subscribed to this channel".to_string()));
+            }
+            self.message_repo
+                .record_channel_view(conversation.id, user_id, message.created_at)
+                .await?;
+            return Ok(None);
+        }
+
+        if !is_participant {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+
+        self.message_repo.mark_as_read(message_id, user_id).await?;
+
+        // The first read by a recipient starts a read-triggered self-destruct countdown
+        let mut self_destruct_at = message.self_destruct_at;
+        if let Some(deadline) = message.read_destruct_deadline(user_id) {
+            if self.message_repo.start_self_destruct(message_id, deadline).await? {
+                self.expiry_scheduler.schedule(message_id, message.conversation_id, deadline);
+                self_destruct_at = Some(deadline);
+            }
+        }
+
+        let payload = serde_json::json!({
+            "message_id": message_id,
+            "user_id": user_id,
+            "read_at": Utc::now(),
+            "self_destruct_at": self_destruct_at,
+        });
+
+        let event = self.sync_repo
+            .append(&SyncEvent::new(message.conversation_id, SyncEventType::MessageRead, payload))
+            .await?;
+
+        Ok(Some(event))
+    }
+}
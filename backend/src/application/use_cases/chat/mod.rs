@@ -1,3 +1,33 @@
 pub mod send_message;
+pub mod edit_message;
+pub mod delete_message;
+pub mod mark_as_read;
+pub mod get_missed_events;
+pub mod acknowledge_sync;
+pub mod set_disappearing_messages;
+pub mod get_message_history;
+pub mod get_thread;
+pub mod pin_message;
+pub mod unpin_message;
+pub mod get_pinned_messages;
+pub mod star_message;
+pub mod unstar_message;
+pub mod get_starred_messages;
+pub mod forward_message;
 
 pub use send_message::SendMessage;
+pub use edit_message::EditMessage;
+pub use delete_message::DeleteMessage;
+pub use mark_as_read::MarkAsRead;
+pub use get_missed_events::GetMissedEvents;
+pub use acknowledge_sync::AcknowledgeSync;
+pub use set_disappearing_messages::SetDisappearingMessages;
+pub use get_message_history::GetMessageHistory;
+pub use get_thread::GetThread;
+pub use pin_message::PinMessage;
+pub use unpin_message::UnpinMessage;
+pub use get_pinned_messages::GetPinnedMessages;
+pub use star_message::StarMessage;
+pub use unstar_message::UnstarMessage;
+pub use get_starred_messages::GetStarredMessages;
+pub use forward_message::ForwardMessage;
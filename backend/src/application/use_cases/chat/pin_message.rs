@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::use_cases::group::helpers::post_system_message;
+use crate::domain::{
+    entities::{GroupEvent, Message, MessageType, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+/// Maximum number of messages pinned in one conversation.
+pub const MAX_PINNED_MESSAGES: i64 = 10;
+
+/// Loads a message and checks that `user_id` may pin or unpin it: any participant in a
+/// private chat, admins only in groups and channels.
+pub(super) async fn load_pinnable(
+    message_repo: &dyn MessageRepository,
+    conversation_repo: &dyn ConversationRepository,
+    user_id: Uuid,
+    message_id: Uuid,
+) -> DomainResult<Message> {
+    let message = message_repo.find_by_id(message_id).await?
+        .filter(|message| !message.is_deleted)
+        .ok_or_else(|| DomainError::NotFound("Message not found".to_string()))?;
+
+    let conversation = conversation_repo.find_by_id(message.conversation_id).await?
+        .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+    let participant = conversation_repo.find_participant(conversation.id, user_id).await?
+        .ok_or_else(|| DomainError::AuthorizationError("Not a participant of this conversation".to_string()))?;
+
+    if (conversation.is_group() || conversation.is_channel()) && !participant.is_admin() {
+        return Err(DomainError::AuthorizationError("Only admins can pin messages".to_string()));
+    }
+
+    Ok(message)
+}
+
+pub struct PinMessage {
+    message_repo: Arc<dyn MessageRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl PinMessage {
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+    ) -> Self {
+        Self {
+            message_repo,
+            conversation_repo,
+        }
+    }
+
+    /// Pins a message and posts a system message about it.
+    pub async fn execute(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<SyncEvent> {
+        let message = load_pinnable(
+            self.message_repo.as_ref(),
+            self.conversation_repo.as_ref(),
+            user_id,
+            message_id,
+        )
+        .await?;
+
+        if message.message_type == MessageType::System {
+            return Err(DomainError::ValidationError("System messages cannot be pinned".to_string()));
+        }
+        if !self.message_repo.pin(message.conversation_id, message.id, user_id, MAX_PINNED_MESSAGES).await? {
+            return Err(DomainError::Conflict("Message is already pinned".to_string()));
+        }
+
+        post_system_message(
+            self.message_repo.as_ref(),
+            message.conversation_id,
+            user_id,
+            GroupEvent::MessagePinned { actor_id: user_id, message_id: message.id },
+        )
+        .await
+    }
+}
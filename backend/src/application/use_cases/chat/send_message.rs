@@ -2,46 +2,160 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::domain::{
-    entities::{Message, MessageType},
-    repositories::MessageRepository,
+    entities::{Message, MessageType, SyncEvent, SyncEventType},
+    repositories::{ConversationRepository, MessageRepository, SenderKeyRepository},
+    services::MessageExpiryScheduler,
     DomainError, DomainResult,
 };
+use crate::application::{MessageResponse, SendMessageRequest};
 
 pub struct SendMessage {
     message_repo: Arc<dyn MessageRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+    sender_key_repo: Arc<dyn SenderKeyRepository>,
+    expiry_scheduler: Arc<dyn MessageExpiryScheduler>,
 }
 
 impl SendMessage {
-    pub fn new(message_repo: Arc<dyn MessageRepository>) -> Self {
-        Self { message_repo }
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+        sender_key_repo: Arc<dyn SenderKeyRepository>,
+        expiry_scheduler: Arc<dyn MessageExpiryScheduler>,
+    ) -> Self {
+        Self {
+            message_repo,
+            conversation_repo,
+            sender_key_repo,
+            expiry_scheduler,
+        }
     }
 
+    /// Persists a message and records it in the sync log, in one transaction. When the client supplies a
+    /// `client_message_id` that was already used by this sender, the original message is
+    /// returned instead and no new sync event is recorded.
     pub async fn execute(
         &self,
         sender_id: Uuid,
-        conversation_id: Uuid,
-        content: String,
-        message_type_str: String,
-        reply_to_id: Option<Uuid>,
-        self_destruct_in_seconds: Option<i64>,
-    ) -> DomainResult<Message> {
-        let message_type = match message_type_str.as_str() {
+        request: SendMessageRequest,
+    ) -> DomainResult<(Message, Option<SyncEvent>)> {
+        self.send(sender_id, request, None).await
+    }
+
+    /// Like `execute`, but records the new message as a forward of `origin`. The caller is
+    /// responsible for checking that the sender may read and forward `origin`.
+    pub async fn forward(
+        &self,
+        sender_id: Uuid,
+        request: SendMessageRequest,
+        origin: &Message,
+    ) -> DomainResult<(Message, Option<SyncEvent>)> {
+        self.send(sender_id, request, Some(origin)).await
+    }
+
+    async fn send(
+        &self,
+        sender_id: Uuid,
+        request: SendMessageRequest,
+        forwarded_from: Option<&Message>,
+    ) -> DomainResult<(Message, Option<SyncEvent>)> {
+        let client_message_id = request.client_message_id;
+
+        if let Some(client_id) = client_message_id.as_deref() {
+            if let Some(existing) = self.message_repo.find_by_client_id(sender_id, client_id).await? {
+                return Ok((existing, None));
+            }
+        }
+
+        let conversation = self.conversation_repo.find_by_id(request.conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+        let participant = self.conversation_repo.find_participant(conversation.id, sender_id).await?
+            .ok_or_else(|| DomainError::AuthorizationError("Not a participant of this conversation".to_string()))?;
+
+        if conversation.only_admins_can_post() && !participant.is_admin() {
+            return Err(DomainError::AuthorizationError("Only admins can post in this group".to_string()));
+        }
+
+        if conversation.is_group() {
+            let epoch = request.sender_key_epoch
+                .ok_or_else(|| DomainError::ValidationError("Group messages must name their sender key epoch".to_string()))?;
+            if epoch != self.sender_key_repo.current_epoch(conversation.id).await? {
+                return Err(DomainError::Conflict("Sender key epoch is outdated, distribute a new sender key".to_string()));
+            }
+        }
+
+        let message_type = match request.message_type.as_str() {
             "Image" => MessageType::Image,
             "Video" => MessageType::Video,
             "Audio" => MessageType::Audio,
             "File" => MessageType::File,
-            "System" => MessageType::System,
-            "CallSignal" => MessageType::CallSignal,
+            "System" => {
+                return Err(DomainError::ValidationError("System messages are generated by the server".to_string()));
+            }
+            "CallSignal" => {
+                return Err(DomainError::ValidationError("Call records are generated by the server".to_string()));
+            }
             _ => MessageType::Text,
         };
 
-        let mut message = Message::new(conversation_id, sender_id, content, message_type);
-        message.reply_to_id = reply_to_id;
+        // Replies must target a live message the sender can see in the same conversation
+        if let Some(reply_to_id) = request.reply_to_id {
+            let target = self.message_repo.find_by_id(reply_to_id).await?
+                .filter(|target| target.conversation_id == conversation.id && !target.is_deleted && !target.is_expired())
+                .ok_or_else(|| DomainError::ValidationError("Reply target not found in this conversation".to_string()))?;
+
+            if target.message_type == MessageType::System {
+                return Err(DomainError::ValidationError("Cannot reply to a system message".to_string()));
+            }
+        }
+
+        let mut message = Message::new(conversation.id, sender_id, request.content.into_inner(), message_type);
+        message.client_message_id = client_message_id.clone();
+        message.reply_to_id = request.reply_to_id;
+        if conversation.is_group() {
+            message.sender_key_epoch = request.sender_key_epoch;
+        }
+        if let Some(origin) = forwarded_from {
+            message = message.forwarded_from(origin);
+        }
+
+        // An explicit timer on the message wins over the conversation default
+        let default_timer = conversation.disappearing_messages();
+        let default_on_read = default_timer.as_ref().map_or(false, |timer| timer.start_on_read);
+        let timer_seconds = request.self_destruct_in_seconds
+            .or(default_timer.map(|timer| timer.timer_seconds));
+
+        if let Some(seconds) = timer_seconds {
+            message = if request.self_destruct_on_read.unwrap_or(default_on_read) {
+                message.with_self_destruct_on_read(seconds)
+            } else {
+                message.with_self_destruct(seconds)
+            };
+        }
+
+        // Recorded in the sync log with the message so offline devices can replay it on reconnect
+        let payload = serde_json::to_value(MessageResponse::from(&message))
+            .map_err(|e| DomainError::InternalError(format!("Serialization error: {}", e)))?;
+        let event = SyncEvent::new(message.conversation_id, SyncEventType::MessageCreated, payload);
+
+        let (saved, event) = match self.message_repo.create_with_event(&message, &event).await {
+            Ok(created) => created,
+            Err(e) => {
+                // A concurrent retry may have won the unique (sender_id, client_message_id) race
+                if let Some(client_id) = client_message_id.as_deref() {
+                    if let Some(existing) = self.message_repo.find_by_client_id(sender_id, client_id).await? {
+                        return Ok((existing, None));
+                    }
+                }
+                return Err(e);
+            }
+        };
 
-        if let Some(seconds) = self_destruct_in_seconds {
-            message = message.with_self_destruct(seconds);
+        if let Some(destruct_at) = saved.self_destruct_at {
+            self.expiry_scheduler.schedule(saved.id, saved.conversation_id, destruct_at);
         }
 
-        self.message_repo.create(&message).await
+        Ok((saved, Some(event)))
     }
 }
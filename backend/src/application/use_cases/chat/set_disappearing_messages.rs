@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Conversation, DisappearingMessages},
+    repositories::ConversationRepository,
+    DomainError, DomainResult,
+};
+
+pub struct SetDisappearingMessages {
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl SetDisappearingMessages {
+    pub fn new(conversation_repo: Arc<dyn ConversationRepository>) -> Self {
+        Self { conversation_repo }
+    }
+
+    /// Sets or clears (`None`) the default self-destruct timer for new messages in a conversation.
+    /// In groups and channels only admins may change it.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        setting: Option<DisappearingMessages>,
+    ) -> DomainResult<Conversation> {
+        let mut conversation = self.conversation_repo.find_by_id(conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+        let participant = self.conversation_repo.find_participant(conversation_id, user_id).await?
+            .ok_or_else(|| DomainError::AuthorizationError("Not a participant of this conversation".to_string()))?;
+
+        if (conversation.is_group() || conversation.is_channel()) && !participant.is_admin() {
+            return Err(DomainError::AuthorizationError("Only admins can change disappearing messages".to_string()));
+        }
+
+        conversation.set_disappearing_messages(setting);
+        self.conversation_repo.update_settings(conversation_id, &conversation.settings).await?;
+
+        Ok(conversation)
+    }
+}
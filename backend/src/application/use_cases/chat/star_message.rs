@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct StarMessage {
+    message_repo: Arc<dyn MessageRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl StarMessage {
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+    ) -> Self {
+        Self {
+            message_repo,
+            conversation_repo,
+        }
+    }
+
+    /// Adds a message to the user's personal starred list.
+    pub async fn execute(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<()> {
+        let message = self.message_repo.find_by_id(message_id).await?
+            .filter(|message| !message.is_deleted)
+            .ok_or_else(|| DomainError::NotFound("Message not found".to_string()))?;
+
+        if !self.conversation_repo.is_participant(message.conversation_id, user_id).await? {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+
+        self.message_repo.star(user_id, message.id).await
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::pin_message::load_pinnable;
+use crate::application::use_cases::group::helpers::post_system_message;
+use crate::domain::{
+    entities::{GroupEvent, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct UnpinMessage {
+    message_repo: Arc<dyn MessageRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl UnpinMessage {
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+    ) -> Self {
+        Self {
+            message_repo,
+            conversation_repo,
+        }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<SyncEvent> {
+        let message = load_pinnable(
+            self.message_repo.as_ref(),
+            self.conversation_repo.as_ref(),
+            user_id,
+            message_id,
+        )
+        .await?;
+
+        if !self.message_repo.unpin(message.id).await? {
+            return Err(DomainError::NotFound("Message is not pinned".to_string()));
+        }
+
+        post_system_message(
+            self.message_repo.as_ref(),
+            message.conversation_id,
+            user_id,
+            GroupEvent::MessageUnpinned { actor_id: user_id, message_id: message.id },
+        )
+        .await
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{repositories::MessageRepository, DomainError, DomainResult};
+
+pub struct UnstarMessage {
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl UnstarMessage {
+    pub fn new(message_repo: Arc<dyn MessageRepository>) -> Self {
+        Self { message_repo }
+    }
+
+    /// Removes a message from the user's starred list. Deleted messages can still be unstarred.
+    pub async fn execute(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<()> {
+        if self.message_repo.find_by_id(message_id).await?.is_none() {
+            return Err(DomainError::NotFound("Message not found".to_string()));
+        }
+
+        self.message_repo.unstar(user_id, message_id).await
+    }
+}
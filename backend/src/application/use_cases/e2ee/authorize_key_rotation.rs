@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    entities::{PublicKey, Signature, User},
+    repositories::{KeyLogRepository, UserRepository},
+    services::AuthService,
+    DomainError, DomainResult,
+};
+
+/// OTP attempts a user gets per window when proving a key rotation.
+pub const MAX_KEY_ROTATION_OTP_ATTEMPTS: i64 = 5;
+pub const KEY_ROTATION_OTP_WINDOW_SECONDS: i64 = 15 * 60;
+
+/// What a client offers to prove it may replace a key; one of the two is required.
+#[derive(Debug, Clone, Default)]
+pub struct KeyRotationProof {
+    /// Signature by the current key over `PublicKey::rotation_message`.
+    pub signature: Option<String>,
+    /// OTP sent to the account's phone number.
+    pub otp: Option<String>,
+}
+
+pub struct AuthorizeKeyRotation {
+    user_repo: Arc<dyn UserRepository>,
+    key_log_repo: Arc<dyn KeyLogRepository>,
+    auth_service: Arc<dyn AuthService>,
+}
+
+impl AuthorizeKeyRotation {
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        key_log_repo: Arc<dyn KeyLogRepository>,
+        auth_service: Arc<dyn AuthService>,
+    ) -> Self {
+        Self {
+            user_repo,
+            key_log_repo,
+            auth_service,
+        }
+    }
+
+    /// Checks that the user approved replacing `current_key` of the account (`device_id` None)
+    /// or device with `new_key`. OTP attempts are limited per user.
+    pub async fn execute(
+        &self,
+        user: &User,
+        device_id: Option<&str>,
+        current_key: &str,
+        new_key: &PublicKey,
+        proof: KeyRotationProof,
+    ) -> DomainResult<()> {
+        if let Some(signature) = proof.signature {
+            let nonce = self.key_log_repo.find_head(user.id).await?
+                .map(|head| head.entry_hash)
+                .unwrap_or_default();
+            let message = new_key.rotation_message(user.id, device_id, &nonce);
+
+            // Keys stored before validation existed may not parse; those need the OTP route
+            let signed = PublicKey::parse(current_key)
+                .zip(Signature::parse(&signature))
+                .is_some_and(|(current, signature)| current.verify(&message, &signature));
+            if !signed {
+                return Err(DomainError::AuthorizationError("Signature does not match the current key".to_string()));
+            }
+            return Ok(());
+        }
+
+        if let Some(otp) = proof.otp {
+            let attempts = self.user_repo
+                .record_otp_attempt(user.id, KEY_ROTATION_OTP_WINDOW_SECONDS)
+                .await?;
+            if attempts > MAX_KEY_ROTATION_OTP_ATTEMPTS {
+                return Err(DomainError::Conflict("Too many OTP attempts, try again later".to_string()));
+            }
+
+            if !self.auth_service.verify_otp(&user.phone_number, &otp).await? {
+                return Err(DomainError::AuthenticationError("Invalid OTP".to_string()));
+            }
+            return Ok(());
+        }
+
+        Err(DomainError::AuthorizationError(
+            "Replacing a key requires a signature by the current key or an OTP".to_string(),
+        ))
+    }
+}
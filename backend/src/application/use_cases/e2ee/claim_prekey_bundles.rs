@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::PreKeyBundle,
+    repositories::{PreKeyRepository, UserRepository},
+    DomainError, DomainResult,
+};
+
+/// Devices are asked to upload more one-time prekeys once they have fewer than this left.
+pub const PREKEY_LOW_WATERMARK: i64 = 10;
+/// Claims one user may make of another user's bundles per window; a session needs only one.
+pub const MAX_PREKEY_CLAIMS_PER_WINDOW: i64 = 10;
+pub const PREKEY_CLAIM_WINDOW_SECONDS: i64 = 60 * 60;
+
+pub struct ClaimedPreKeyBundles {
+    pub bundles: Vec<PreKeyBundle>,
+    /// Devices of the target user now below the watermark, with their remaining count.
+    pub low_supply: Vec<(String, i64)>,
+}
+
+pub struct ClaimPreKeyBundles {
+    prekey_repo: Arc<dyn PreKeyRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl ClaimPreKeyBundles {
+    pub fn new(prekey_repo: Arc<dyn PreKeyRepository>, user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { prekey_repo, user_repo }
+    }
+
+    /// Hands out one bundle per device of `user_id` to `claimer_id`, consuming a one-time prekey
+    /// from each. Claims are limited per claimer and target so nobody can drain the supply.
+    pub async fn execute(&self, claimer_id: Uuid, user_id: Uuid) -> DomainResult<ClaimedPreKeyBundles> {
+        if self.user_repo.find_by_id(user_id).await?.is_none() {
+            return Err(DomainError::NotFound("User not found".to_string()));
+        }
+
+        let claims = self.prekey_repo
+            .record_claim(claimer_id, user_id, PREKEY_CLAIM_WINDOW_SECONDS)
+            .await?;
+        if claims > MAX_PREKEY_CLAIMS_PER_WINDOW {
+            return Err(DomainError::Conflict("Too many prekey claims for this user, try again later".to_string()));
+        }
+
+        let bundles = self.prekey_repo.claim_bundles(user_id).await?;
+
+        let mut low_supply = Vec::new();
+        for bundle in &bundles {
+            let remaining = self.prekey_repo.count_one_time_prekeys(user_id, &bundle.device_id).await?;
+            if remaining < PREKEY_LOW_WATERMARK {
+                low_supply.push((bundle.device_id.clone(), remaining));
+            }
+        }
+
+        Ok(ClaimedPreKeyBundles { bundles, low_supply })
+    }
+}
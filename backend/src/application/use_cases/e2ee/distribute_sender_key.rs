@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::DistributeSenderKeyRequest;
+use crate::domain::{
+    entities::{SenderKeyEnvelope, SyncEvent, SyncEventType},
+    repositories::{ConversationRepository, SenderKeyRepository, SyncRepository},
+    DomainError, DomainResult,
+};
+
+pub struct DistributeSenderKey {
+    sender_key_repo: Arc<dyn SenderKeyRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+}
+
+impl DistributeSenderKey {
+    pub fn new(
+        sender_key_repo: Arc<dyn SenderKeyRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+    ) -> Self {
+        Self {
+            sender_key_repo,
+            conversation_repo,
+            sync_repo,
+        }
+    }
+
+    /// Stores the sender's key for the current epoch, one envelope per recipient device, and
+    /// tells the group that new envelopes are waiting.
+    pub async fn execute(
+        &self,
+        sender_id: Uuid,
+        conversation_id: Uuid,
+        request: DistributeSenderKeyRequest,
+    ) -> DomainResult<SyncEvent> {
+        let conversation = self.conversation_repo.find_by_id(conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+        if !conversation.is_group() {
+            return Err(DomainError::ValidationError("Sender keys are only used in groups".to_string()));
+        }
+
+        let members: HashSet<Uuid> = self.conversation_repo.find_participants(conversation_id).await?
+            .into_iter()
+            .map(|participant| participant.user_id)
+            .collect();
+        if !members.contains(&sender_id) {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+
+        let epoch = self.sender_key_repo.current_epoch(conversation_id).await?;
+        if request.epoch != epoch {
+            return Err(DomainError::Conflict("Sender key epoch is outdated, fetch the current epoch".to_string()));
+        }
+
+        if let Some(envelope) = request.envelopes.iter().find(|envelope| !members.contains(&envelope.recipient_id)) {
+            return Err(DomainError::ValidationError(format!("User {} is not a member of this group", envelope.recipient_id)));
+        }
+
+        let envelopes: Vec<SenderKeyEnvelope> = request.envelopes
+            .into_iter()
+            .map(|envelope| SenderKeyEnvelope::new(
+                conversation_id,
+                sender_id,
+                request.device_id.clone(),
+                envelope.recipient_id,
+                envelope.recipient_device_id,
+                epoch,
+                envelope.ciphertext,
+            ))
+            .collect();
+        self.sender_key_repo.store(&envelopes).await?;
+
+        self.sync_repo
+            .append(&SyncEvent::new(
+                conversation_id,
+                SyncEventType::SenderKeyDistributed,
+                serde_json::json!({
+                    "sender_id": sender_id,
+                    "sender_device_id": request.device_id,
+                    "epoch": epoch,
+                }),
+            ))
+            .await
+    }
+}
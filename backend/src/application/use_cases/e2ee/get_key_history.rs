@@ -0,0 +1,28 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::KeyLogEntry,
+    repositories::{KeyLogRepository, UserRepository},
+    DomainError, DomainResult,
+};
+
+pub struct GetKeyHistory {
+    key_log_repo: Arc<dyn KeyLogRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl GetKeyHistory {
+    pub fn new(key_log_repo: Arc<dyn KeyLogRepository>, user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { key_log_repo, user_repo }
+    }
+
+    /// Every identity key the user and their devices have published, oldest first.
+    pub async fn execute(&self, user_id: Uuid) -> DomainResult<Vec<KeyLogEntry>> {
+        if self.user_repo.find_by_id(user_id).await?.is_none() {
+            return Err(DomainError::NotFound("User not found".to_string()));
+        }
+
+        self.key_log_repo.find_by_user(user_id).await
+    }
+}
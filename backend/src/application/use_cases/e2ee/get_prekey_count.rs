@@ -0,0 +1,23 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{repositories::PreKeyRepository, DomainError, DomainResult};
+
+pub struct GetPreKeyCount {
+    prekey_repo: Arc<dyn PreKeyRepository>,
+}
+
+impl GetPreKeyCount {
+    pub fn new(prekey_repo: Arc<dyn PreKeyRepository>) -> Self {
+        Self { prekey_repo }
+    }
+
+    /// Number of one-time prekeys the device still has on the server.
+    pub async fn execute(&self, user_id: Uuid, device_id: String) -> DomainResult<i64> {
+        if self.prekey_repo.find_device(user_id, &device_id).await?.is_none() {
+            return Err(DomainError::NotFound("Device keys not registered".to_string()));
+        }
+
+        self.prekey_repo.count_one_time_prekeys(user_id, &device_id).await
+    }
+}
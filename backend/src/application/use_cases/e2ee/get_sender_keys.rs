@@ -0,0 +1,54 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::SenderKeyEnvelope,
+    repositories::{ConversationRepository, SenderKeyRepository},
+    DomainError, DomainResult,
+};
+
+pub struct GetSenderKeys {
+    sender_key_repo: Arc<dyn SenderKeyRepository>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl GetSenderKeys {
+    pub fn new(
+        sender_key_repo: Arc<dyn SenderKeyRepository>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+    ) -> Self {
+        Self {
+            sender_key_repo,
+            conversation_repo,
+        }
+    }
+
+    /// Returns the sender key envelopes of `epoch`, or of the current epoch, addressed to one of
+    /// the caller's devices, along with that epoch. Envelopes of superseded epochs are only kept
+    /// for `SENDER_KEY_RETENTION_DAYS`.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        device_id: &str,
+        epoch: Option<i32>,
+    ) -> DomainResult<(i32, Vec<SenderKeyEnvelope>)> {
+        if !self.conversation_repo.is_participant(conversation_id, user_id).await? {
+            return Err(DomainError::AuthorizationError("Not a participant of this conversation".to_string()));
+        }
+
+        let current_epoch = self.sender_key_repo.current_epoch(conversation_id).await?;
+        let epoch = match epoch {
+            Some(epoch) if epoch > current_epoch => {
+                return Err(DomainError::ValidationError("Sender key epoch has not started yet".to_string()));
+            }
+            Some(epoch) => epoch,
+            None => current_epoch,
+        };
+        let envelopes = self.sender_key_repo
+            .find_for_recipient(conversation_id, epoch, user_id, device_id)
+            .await?;
+
+        Ok((epoch, envelopes))
+    }
+}
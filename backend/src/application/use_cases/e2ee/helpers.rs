@@ -0,0 +1,36 @@
+use crate::domain::{
+    entities::{KeyLogEntry, SyncEvent, SyncEventType},
+    repositories::{ConversationRepository, SyncRepository},
+    DomainResult,
+};
+
+/// Tells every conversation the user is in that `entry` replaced one of their keys. Returns the
+/// `KeyChanged` events for broadcasting; none if no key was replaced.
+pub(crate) async fn announce_key_change(
+    conversation_repo: &dyn ConversationRepository,
+    sync_repo: &dyn SyncRepository,
+    entry: Option<KeyLogEntry>,
+) -> DomainResult<Vec<SyncEvent>> {
+    let Some(entry) = entry else {
+        return Ok(Vec::new());
+    };
+
+    let payload = serde_json::json!({
+        "user_id": entry.user_id,
+        "device_id": entry.device_id,
+        "identity_key": entry.identity_key,
+        "log_seq": entry.seq,
+        "entry_hash": entry.entry_hash,
+    });
+
+    let mut events = Vec::new();
+    for conversation_id in conversation_repo.find_ids_by_user(entry.user_id).await? {
+        events.push(
+            sync_repo
+                .append(&SyncEvent::new(conversation_id, SyncEventType::KeyChanged, payload.clone()))
+                .await?,
+        );
+    }
+
+    Ok(events)
+}
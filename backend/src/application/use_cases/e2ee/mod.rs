@@ -0,0 +1,18 @@
+pub(crate) mod helpers;
+pub mod authorize_key_rotation;
+pub mod register_device_keys;
+pub mod upload_one_time_prekeys;
+pub mod get_prekey_count;
+pub mod claim_prekey_bundles;
+pub mod get_key_history;
+pub mod distribute_sender_key;
+pub mod get_sender_keys;
+
+pub use authorize_key_rotation::{AuthorizeKeyRotation, KeyRotationProof};
+pub use register_device_keys::{RegisterDeviceKeys, MAX_ONE_TIME_PREKEYS};
+pub use upload_one_time_prekeys::UploadOneTimePreKeys;
+pub use get_prekey_count::GetPreKeyCount;
+pub use claim_prekey_bundles::ClaimPreKeyBundles;
+pub use get_key_history::GetKeyHistory;
+pub use distribute_sender_key::DistributeSenderKey;
+pub use get_sender_keys::GetSenderKeys;
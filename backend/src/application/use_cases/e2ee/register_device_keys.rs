@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::authorize_key_rotation::{AuthorizeKeyRotation, KeyRotationProof};
+use super::helpers::announce_key_change;
+use crate::domain::{
+    entities::{DeviceKeys, OneTimePreKey, PublicKey, SignedPreKey, SyncEvent},
+    repositories::{ConversationRepository, PreKeyRepository, SyncRepository, UserRepository},
+    DomainError, DomainResult,
+};
+
+/// Upper bound on one-time prekeys stored per device.
+pub const MAX_ONE_TIME_PREKEYS: i64 = 200;
+
+pub struct RegisterDeviceKeys {
+    prekey_repo: Arc<dyn PreKeyRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    authorize_key_rotation: Arc<AuthorizeKeyRotation>,
+    conversation_repo: Arc<dyn ConversationRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+}
+
+impl RegisterDeviceKeys {
+    pub fn new(
+        prekey_repo: Arc<dyn PreKeyRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        authorize_key_rotation: Arc<AuthorizeKeyRotation>,
+        conversation_repo: Arc<dyn ConversationRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+    ) -> Self {
+        Self {
+            prekey_repo,
+            user_repo,
+            authorize_key_rotation,
+            conversation_repo,
+            sync_repo,
+        }
+    }
+
+    /// Publishes the identity key and signed prekey of a device, optionally with a first batch
+    /// of one-time prekeys. Calling it again rotates the signed prekey. Replacing the identity key
+    /// requires a rotation `proof`. Returns the `KeyChanged` events to broadcast if the identity
+    /// key was replaced.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        device_id: String,
+        identity_key: String,
+        signed_prekey: SignedPreKey,
+        one_time_prekeys: Vec<OneTimePreKey>,
+        proof: KeyRotationProof,
+    ) -> DomainResult<Vec<SyncEvent>> {
+        let new_key = PublicKey::parse(&identity_key)
+            .ok_or_else(|| DomainError::ValidationError("Identity key must be 32 bytes, base64 or hex encoded".to_string()))?;
+        let mut keys = DeviceKeys::new(user_id, device_id, new_key.to_base64(), signed_prekey);
+
+        // Checked before anything is stored so an oversized batch leaves the device untouched.
+        // Stored one-time prekeys only survive when the identity key stays the same
+        let mut stored = 0;
+        if let Some(existing) = self.prekey_repo.find_device(user_id, &keys.device_id).await? {
+            keys.created_at = existing.created_at;
+            if PublicKey::parse(&existing.identity_key) == Some(new_key) {
+                // Keep the stored encoding so a key uploaded before canonicalization is not
+                // mistaken for a new one
+                keys.identity_key = existing.identity_key;
+                stored = self.prekey_repo.count_one_time_prekeys(user_id, &keys.device_id).await?;
+            } else {
+                let user = self.user_repo.find_by_id(user_id).await?
+                    .ok_or_else(|| DomainError::NotFound("User not found".to_string()))?;
+                self.authorize_key_rotation
+                    .execute(&user, Some(&keys.device_id), &existing.identity_key, &new_key, proof)
+                    .await?;
+            }
+        }
+        if stored + one_time_prekeys.len() as i64 > MAX_ONE_TIME_PREKEYS {
+            return Err(DomainError::ValidationError(format!(
+                "A device can store at most {} one-time prekeys",
+                MAX_ONE_TIME_PREKEYS
+            )));
+        }
+
+        let entry = self.prekey_repo.upsert_device(&keys).await?;
+        let events = announce_key_change(self.conversation_repo.as_ref(), self.sync_repo.as_ref(), entry).await?;
+
+        if !one_time_prekeys.is_empty()
+            && !self.prekey_repo
+                .add_one_time_prekeys(user_id, &keys.device_id, &one_time_prekeys, MAX_ONE_TIME_PREKEYS)
+                .await?
+        {
+            return Err(DomainError::ValidationError(format!(
+                "A device can store at most {} one-time prekeys",
+                MAX_ONE_TIME_PREKEYS
+            )));
+        }
+
+        Ok(events)
+    }
+}
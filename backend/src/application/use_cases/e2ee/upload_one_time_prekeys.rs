@@ -0,0 +1,34 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::MAX_ONE_TIME_PREKEYS;
+use crate::domain::{
+    entities::OneTimePreKey,
+    repositories::PreKeyRepository,
+    DomainError, DomainResult,
+};
+
+pub struct UploadOneTimePreKeys {
+    prekey_repo: Arc<dyn PreKeyRepository>,
+}
+
+impl UploadOneTimePreKeys {
+    pub fn new(prekey_repo: Arc<dyn PreKeyRepository>) -> Self {
+        Self { prekey_repo }
+    }
+
+    /// Replenishes a device's one-time prekeys and returns how many it now has.
+    pub async fn execute(&self, user_id: Uuid, device_id: String, keys: Vec<OneTimePreKey>) -> DomainResult<i64> {
+        if self.prekey_repo.find_device(user_id, &device_id).await?.is_none() {
+            return Err(DomainError::NotFound("Device keys not registered".to_string()));
+        }
+
+        if !self.prekey_repo.add_one_time_prekeys(user_id, &device_id, &keys, MAX_ONE_TIME_PREKEYS).await? {
+            return Err(DomainError::ValidationError(format!(
+                "A device can store at most {} one-time prekeys",
+                MAX_ONE_TIME_PREKEYS
+            )));
+        }
+        self.prekey_repo.count_one_time_prekeys(user_id, &device_id).await
+    }
+}
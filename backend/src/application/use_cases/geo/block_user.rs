@@ -0,0 +1,31 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    repositories::UserRepository,
+    DomainError, DomainResult,
+};
+
+pub struct BlockUser {
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl BlockUser {
+    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { user_repo }
+    }
+
+    /// Hides both users from each other in nearby search. Messaging is not affected.
+    pub async fn execute(&self, blocker_id: Uuid, blocked_id: Uuid) -> DomainResult<()> {
+        if blocker_id == blocked_id {
+            return Err(DomainError::ValidationError("You cannot block yourself".to_string()));
+        }
+
+        self.user_repo
+            .find_by_id(blocked_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("User not found".to_string()))?;
+
+        self.user_repo.block_user(blocker_id, blocked_id).await
+    }
+}
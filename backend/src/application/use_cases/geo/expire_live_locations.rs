@@ -0,0 +1,42 @@
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use super::helpers::record_live_location_event;
+use crate::domain::{
+    entities::{SyncEvent, SyncEventType, LIVE_LOCATION_TRACK_RETENTION_DAYS},
+    repositories::{LiveLocationRepository, SyncRepository},
+    DomainResult,
+};
+
+pub struct ExpireLiveLocations {
+    live_location_repo: Arc<dyn LiveLocationRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+}
+
+impl ExpireLiveLocations {
+    pub fn new(live_location_repo: Arc<dyn LiveLocationRepository>, sync_repo: Arc<dyn SyncRepository>) -> Self {
+        Self {
+            live_location_repo,
+            sync_repo,
+        }
+    }
+
+    /// Stops sessions that expired or whose sharer left the conversation.
+    pub async fn execute(&self) -> DomainResult<Vec<SyncEvent>> {
+        let stopped = self.live_location_repo.stop_expired(Utc::now()).await?;
+
+        let mut events = Vec::with_capacity(stopped.len());
+        for session in &stopped {
+            events.push(record_live_location_event(self.sync_repo.as_ref(), session, SyncEventType::LiveLocationStopped).await?);
+        }
+
+        Ok(events)
+    }
+
+    /// Deletes track points older than the retention window. Returns how many were deleted.
+    pub async fn purge_tracks(&self) -> DomainResult<u64> {
+        self.live_location_repo
+            .purge_points(Utc::now() - Duration::days(LIVE_LOCATION_TRACK_RETENTION_DAYS))
+            .await
+    }
+}
@@ -1,42 +1,55 @@
+use chrono::{Duration, Utc};
 use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::domain::{
+    entities::{GeoPoint, NearbySearch},
     repositories::UserRepository,
     DomainResult,
 };
-use crate::application::UserLocationResponse;
+use crate::application::{FindNearbyRequest, UserLocationResponse};
 
 pub struct FindNearbyUsers {
     user_repo: Arc<dyn UserRepository>,
+    location_ttl: Duration,
 }
 
 impl FindNearbyUsers {
-    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
-        Self { user_repo }
+    pub fn new(user_repo: Arc<dyn UserRepository>, location_ttl: Duration) -> Self {
+        Self { user_repo, location_ttl }
     }
 
-    pub async fn execute(&self, latitude: f64, longitude: f64, radius_km: f64) -> DomainResult<Vec<UserLocationResponse>> {
-        let users = self.user_repo.find_nearby(latitude, longitude, radius_km).await?;
+    /// One page of nearby users, closest first.
+    pub async fn execute(
+        &self,
+        requester_id: Uuid,
+        request: &FindNearbyRequest,
+        limit: i64,
+    ) -> DomainResult<Vec<UserLocationResponse>> {
+        // Search from the requester's grid cell too, so repeated probing can't narrow anyone down
+        let search = NearbySearch {
+            requester_id,
+            origin: GeoPoint::new(request.latitude, request.longitude).fuzzed(),
+            radius_km: request.radius_km,
+            updated_after: Utc::now() - self.location_ttl,
+            verified_only: request.verified_only,
+            online_only: request.online_only,
+            after: request.after_distance_km.zip(request.after_user_id),
+            limit,
+        };
+
+        let users = self.user_repo.find_nearby(&search).await?;
 
-        // Convert to DTO
         Ok(users
             .into_iter()
-            .filter_map(|u| {
-                if let Some((lat, lon)) = u.location {
-                    // Calculate rough distance (optional, or let frontend do it)
-                    // For now just return the data
-                    Some(UserLocationResponse {
-                        user_id: u.id,
-                        name: u.name,
-                        username: u.username,
-                        avatar_url: u.avatar_url,
-                        latitude: lat,
-                        longitude: lon,
-                        distance_km: None, // Could calculate Haversine here if needed
-                    })
-                } else {
-                    None
-                }
+            .map(|(u, distance_km)| UserLocationResponse {
+                user_id: u.id,
+                name: u.name,
+                username: u.username,
+                avatar_url: u.avatar_url,
+                is_verified: u.is_verified,
+                is_online: u.is_online,
+                distance_km,
             })
             .collect())
     }
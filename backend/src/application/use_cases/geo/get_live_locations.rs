@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::require_participant;
+use crate::domain::{
+    entities::{LiveLocationSession, LocationPoint},
+    repositories::{ConversationRepository, LiveLocationRepository},
+    DomainError, DomainResult,
+};
+
+pub struct GetLiveLocations {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    live_location_repo: Arc<dyn LiveLocationRepository>,
+}
+
+impl GetLiveLocations {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        live_location_repo: Arc<dyn LiveLocationRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            live_location_repo,
+        }
+    }
+
+    /// Live locations currently shared in the conversation.
+    pub async fn execute(&self, user_id: Uuid, conversation_id: Uuid) -> DomainResult<Vec<LiveLocationSession>> {
+        require_participant(self.conversation_repo.as_ref(), conversation_id, user_id).await?;
+        self.live_location_repo.find_active_by_conversation(conversation_id).await
+    }
+
+    /// A session together with the positions recorded during it since the user joined the
+    /// conversation.
+    pub async fn track(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+    ) -> DomainResult<(LiveLocationSession, Vec<LocationPoint>)> {
+        let session = self
+            .live_location_repo
+            .find_by_id(session_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Live location not found".to_string()))?;
+
+        let participant = require_participant(self.conversation_repo.as_ref(), session.conversation_id, user_id).await?;
+
+        let points = self.live_location_repo.find_points(session.id, participant.joined_at).await?;
+        Ok((session, points))
+    }
+}
@@ -0,0 +1,40 @@
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{LiveLocationSession, Participant, SyncEvent, SyncEventType},
+    repositories::{ConversationRepository, SyncRepository},
+    DomainError, DomainResult,
+};
+
+/// Live locations are only visible to participants of the conversation they are shared in.
+pub(super) async fn require_participant(
+    conversation_repo: &dyn ConversationRepository,
+    conversation_id: Uuid,
+    user_id: Uuid,
+) -> DomainResult<Participant> {
+    conversation_repo
+        .find_participant(conversation_id, user_id)
+        .await?
+        .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))
+}
+
+/// Records that a live location started or stopped, so devices that sync later see it too.
+/// The sync log outlives the session, so the event never carries coordinates; members fetch
+/// the track while it is live.
+pub(crate) async fn record_live_location_event(
+    sync_repo: &dyn SyncRepository,
+    session: &LiveLocationSession,
+    event_type: SyncEventType,
+) -> DomainResult<SyncEvent> {
+    let payload = serde_json::json!({
+        "session_id": session.id,
+        "user_id": session.user_id,
+        "started_at": session.started_at,
+        "expires_at": session.expires_at,
+        "stopped_at": session.stopped_at,
+    });
+
+    sync_repo
+        .append(&SyncEvent::new(session.conversation_id, event_type, payload))
+        .await
+}
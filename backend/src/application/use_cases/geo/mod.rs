@@ -1,5 +1,22 @@
+pub(crate) mod helpers;
 pub mod update_location;
 pub mod find_nearby_users;
+pub mod set_discoverability;
+pub mod block_user;
+pub mod unblock_user;
+pub mod start_live_location;
+pub mod update_live_location;
+pub mod stop_live_location;
+pub mod get_live_locations;
+pub mod expire_live_locations;
 
 pub use update_location::UpdateLocation;
 pub use find_nearby_users::FindNearbyUsers;
+pub use set_discoverability::SetDiscoverability;
+pub use block_user::BlockUser;
+pub use unblock_user::UnblockUser;
+pub use start_live_location::StartLiveLocation;
+pub use update_live_location::UpdateLiveLocation;
+pub use stop_live_location::StopLiveLocation;
+pub use get_live_locations::GetLiveLocations;
+pub use expire_live_locations::ExpireLiveLocations;
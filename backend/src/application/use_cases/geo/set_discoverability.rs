@@ -0,0 +1,22 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    repositories::UserRepository,
+    DomainResult,
+};
+
+pub struct SetDiscoverability {
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl SetDiscoverability {
+    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { user_repo }
+    }
+
+    /// Opts the user in or out of nearby search; opting out drops the stored location right away.
+    pub async fn execute(&self, user_id: Uuid, discoverable: bool) -> DomainResult<()> {
+        self.user_repo.set_discoverable(user_id, discoverable).await
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{record_live_location_event, require_participant};
+use crate::domain::{
+    entities::{LiveLocationSession, SyncEvent, SyncEventType, LIVE_LOCATION_DURATIONS_MINUTES},
+    repositories::{ConversationRepository, LiveLocationRepository, SyncRepository},
+    DomainError, DomainResult,
+};
+
+pub struct StartLiveLocation {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    live_location_repo: Arc<dyn LiveLocationRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+}
+
+impl StartLiveLocation {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        live_location_repo: Arc<dyn LiveLocationRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            live_location_repo,
+            sync_repo,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        duration_minutes: i64,
+    ) -> DomainResult<(LiveLocationSession, SyncEvent)> {
+        if !LIVE_LOCATION_DURATIONS_MINUTES.contains(&duration_minutes) {
+            return Err(DomainError::ValidationError(
+                "Live location can be shared for 15 minutes, 1 hour or 8 hours".to_string(),
+            ));
+        }
+
+        require_participant(self.conversation_repo.as_ref(), conversation_id, user_id).await?;
+
+        let session = self
+            .live_location_repo
+            .create(&LiveLocationSession::new(conversation_id, user_id, duration_minutes))
+            .await?;
+
+        let event = record_live_location_event(self.sync_repo.as_ref(), &session, SyncEventType::LiveLocationStarted).await?;
+
+        Ok((session, event))
+    }
+}
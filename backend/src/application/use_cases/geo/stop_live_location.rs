@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::record_live_location_event;
+use crate::domain::{
+    entities::{SyncEvent, SyncEventType},
+    repositories::{LiveLocationRepository, SyncRepository},
+    DomainError, DomainResult,
+};
+
+pub struct StopLiveLocation {
+    live_location_repo: Arc<dyn LiveLocationRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+}
+
+impl StopLiveLocation {
+    pub fn new(live_location_repo: Arc<dyn LiveLocationRepository>, sync_repo: Arc<dyn SyncRepository>) -> Self {
+        Self {
+            live_location_repo,
+            sync_repo,
+        }
+    }
+
+    /// Stops the user's own session. Returns None when it had already ended.
+    pub async fn execute(&self, user_id: Uuid, session_id: Uuid) -> DomainResult<Option<SyncEvent>> {
+        self.live_location_repo
+            .find_by_id(session_id)
+            .await?
+            .filter(|session| session.user_id == user_id)
+            .ok_or_else(|| DomainError::NotFound("Live location not found".to_string()))?;
+
+        let Some(session) = self.live_location_repo.stop(session_id).await? else {
+            return Ok(None);
+        };
+
+        let event = record_live_location_event(self.sync_repo.as_ref(), &session, SyncEventType::LiveLocationStopped).await?;
+        Ok(Some(event))
+    }
+}
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    repositories::UserRepository,
+    DomainResult,
+};
+
+pub struct UnblockUser {
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl UnblockUser {
+    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { user_repo }
+    }
+
+    pub async fn execute(&self, blocker_id: Uuid, blocked_id: Uuid) -> DomainResult<()> {
+        self.user_repo.unblock_user(blocker_id, blocked_id).await
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::LiveLocationUpdate;
+use crate::domain::{
+    entities::{LiveLocationSession, LocationPoint, LIVE_LOCATION_MIN_UPDATE_INTERVAL_SECONDS},
+    repositories::{ConversationRepository, LiveLocationRepository},
+    DomainError, DomainResult,
+};
+
+pub struct UpdateLiveLocation {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    live_location_repo: Arc<dyn LiveLocationRepository>,
+}
+
+impl UpdateLiveLocation {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        live_location_repo: Arc<dyn LiveLocationRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            live_location_repo,
+        }
+    }
+
+    /// Records a new position of the user's own session, together with the participants it is
+    /// streamed to. Returns None when the update came too soon after the previous one and was
+    /// dropped.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        update: LiveLocationUpdate,
+    ) -> DomainResult<Option<(LiveLocationSession, LocationPoint, Vec<Uuid>)>> {
+        let session = self
+            .live_location_repo
+            .find_by_id(update.session_id)
+            .await?
+            .filter(|session| session.user_id == user_id)
+            .ok_or_else(|| DomainError::NotFound("Live location not found".to_string()))?;
+
+        if !session.is_active() {
+            return Err(DomainError::Conflict("Live location sharing has ended".to_string()));
+        }
+
+        let point = LocationPoint {
+            latitude: update.latitude,
+            longitude: update.longitude,
+            recorded_at: Utc::now(),
+        };
+
+        let recorded = self
+            .live_location_repo
+            .record_point(session.id, &point, LIVE_LOCATION_MIN_UPDATE_INTERVAL_SECONDS)
+            .await?;
+
+        if !recorded {
+            return Ok(None);
+        }
+
+        let participants = self
+            .conversation_repo
+            .find_participants(session.conversation_id)
+            .await?
+            .into_iter()
+            .map(|participant| participant.user_id)
+            .collect();
+
+        Ok(Some((session, point, participants)))
+    }
+}
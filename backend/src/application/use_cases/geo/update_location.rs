@@ -2,8 +2,9 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::domain::{
+    entities::GeoPoint,
     repositories::UserRepository,
-    DomainResult,
+    DomainError, DomainResult,
 };
 
 pub struct UpdateLocation {
@@ -16,6 +17,20 @@ impl UpdateLocation {
     }
 
     pub async fn execute(&self, user_id: Uuid, latitude: f64, longitude: f64) -> DomainResult<()> {
-        self.user_repo.update_location(user_id, latitude, longitude).await
+        // Only the grid cell is ever persisted, never the exact position
+        let point = GeoPoint::new(latitude, longitude).fuzzed();
+
+        let stored = self
+            .user_repo
+            .update_location(user_id, point.latitude, point.longitude)
+            .await?;
+
+        if !stored {
+            return Err(DomainError::ValidationError(
+                "Enable discoverability before sharing your location".to_string(),
+            ));
+        }
+
+        Ok(())
     }
 }
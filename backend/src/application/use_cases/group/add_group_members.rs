@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{ensure_capacity, load_group, post_system_message, require_admin, rotate_sender_keys};
+use crate::domain::{
+    entities::{GroupEvent, Participant, ParticipantRole, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository, SenderKeyRepository, SyncRepository, UserRepository},
+    DomainError, DomainResult,
+};
+
+pub struct AddGroupMembers {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    sender_key_repo: Arc<dyn SenderKeyRepository>,
+}
+
+impl AddGroupMembers {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        sender_key_repo: Arc<dyn SenderKeyRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            user_repo,
+            message_repo,
+            sync_repo,
+            sender_key_repo,
+        }
+    }
+
+    /// Adds users to a group as members. Users that are already in the group are skipped;
+    /// adding a banned user lifts the ban. The group's sender keys are rotated if anyone was added.
+    pub async fn execute(
+        &self,
+        actor_id: Uuid,
+        conversation_id: Uuid,
+        user_ids: Vec<Uuid>,
+    ) -> DomainResult<Vec<SyncEvent>> {
+        let (conversation, actor) = load_group(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+        require_admin(&actor)?;
+
+        let mut events = Vec::new();
+        for user_id in user_ids {
+            if self.conversation_repo.is_participant(conversation_id, user_id).await? {
+                continue;
+            }
+            if self.user_repo.find_by_id(user_id).await?.is_none() {
+                return Err(DomainError::NotFound(format!("User {} not found", user_id)));
+            }
+            let limit = ensure_capacity(self.conversation_repo.as_ref(), self.user_repo.as_ref(), &conversation, 1).await?;
+
+            self.conversation_repo.unban(conversation_id, user_id).await?;
+
+            self.conversation_repo
+                .add_participant(&Participant::new(conversation_id, user_id, ParticipantRole::Member), limit)
+                .await?;
+
+            let event = post_system_message(
+                self.message_repo.as_ref(),
+                conversation_id,
+                actor_id,
+                GroupEvent::MemberAdded { actor_id, user_id },
+            )
+            .await?;
+            events.push(event);
+        }
+
+        if !events.is_empty() {
+            events.extend(rotate_sender_keys(self.sender_key_repo.as_ref(), self.sync_repo.as_ref(), &conversation).await?);
+        }
+
+        Ok(events)
+    }
+}
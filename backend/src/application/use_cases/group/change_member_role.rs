@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{load_group_or_channel, post_system_message, require_admin};
+use crate::domain::{
+    entities::{GroupEvent, ParticipantRole, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct ChangeMemberRole {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl ChangeMemberRole {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            message_repo,
+        }
+    }
+
+    /// Promotes a member to admin or demotes an admin to member. Ownership can only be
+    /// changed through `TransferGroupOwnership`.
+    pub async fn execute(
+        &self,
+        actor_id: Uuid,
+        conversation_id: Uuid,
+        user_id: Uuid,
+        role: ParticipantRole,
+    ) -> DomainResult<SyncEvent> {
+        if role == ParticipantRole::Owner {
+            return Err(DomainError::ValidationError("Use ownership transfer to change the owner".to_string()));
+        }
+
+        let (_, actor) = load_group_or_channel(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+        require_admin(&actor)?;
+
+        let target = self.conversation_repo.find_participant(conversation_id, user_id).await?
+            .ok_or_else(|| DomainError::NotFound("User is not a member of this group".to_string()))?;
+
+        if target.is_owner() {
+            return Err(DomainError::AuthorizationError("The owner's role cannot be changed".to_string()));
+        }
+        if target.role == role {
+            return Err(DomainError::Conflict("Member already has this role".to_string()));
+        }
+
+        self.conversation_repo.update_role(conversation_id, user_id, role).await?;
+
+        post_system_message(
+            self.message_repo.as_ref(),
+            conversation_id,
+            actor_id,
+            GroupEvent::RoleChanged { actor_id, user_id, role },
+        )
+        .await
+    }
+}
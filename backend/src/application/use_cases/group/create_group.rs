@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::post_system_message;
+use crate::domain::{
+    entities::{Conversation, Geofence, GroupEvent, Participant, ParticipantRole, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository, UserRepository},
+    DomainError, DomainResult,
+};
+
+pub struct CreateGroup {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl CreateGroup {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            user_repo,
+            message_repo,
+        }
+    }
+
+    /// Creates a group owned by `owner_id` with the given initial members. With a `geofence`
+    /// the group is location-bound and can be discovered by users within its area.
+    pub async fn execute(
+        &self,
+        owner_id: Uuid,
+        name: String,
+        member_ids: Vec<Uuid>,
+        geofence: Option<Geofence>,
+    ) -> DomainResult<(Conversation, Vec<Participant>, SyncEvent)> {
+        let conversation = match geofence {
+            Some(geofence) => Conversation::new_local_group(name.clone(), geofence),
+            None => Conversation::new_group(name.clone()),
+        };
+
+        let mut participants = vec![Participant::new(conversation.id, owner_id, ParticipantRole::Owner)];
+        for member_id in member_ids {
+            if participants.iter().any(|p| p.user_id == member_id) {
+                continue;
+            }
+            if self.user_repo.find_by_id(member_id).await?.is_none() {
+                return Err(DomainError::NotFound(format!("User {} not found", member_id)));
+            }
+            participants.push(Participant::new(conversation.id, member_id, ParticipantRole::Member));
+        }
+
+        let limit = match self.user_repo.find_by_id(owner_id).await? {
+            Some(owner) => owner.subscription_tier.max_group_members(),
+            None => return Err(DomainError::NotFound("User not found".to_string())),
+        };
+        if participants.len() > limit {
+            return Err(DomainError::ValidationError(format!("Group is limited to {} members", limit)));
+        }
+
+        let conversation = self.conversation_repo.create(&conversation, &participants).await?;
+
+        let event = post_system_message(
+            self.message_repo.as_ref(),
+            conversation.id,
+            owner_id,
+            GroupEvent::GroupCreated { actor_id: owner_id, name },
+        )
+        .await?;
+
+        Ok((conversation, participants, event))
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{load_group_or_channel, require_admin};
+use crate::domain::{
+    entities::GroupInvite,
+    repositories::{ConversationRepository, GroupInviteRepository},
+    DomainResult,
+};
+
+pub struct CreateGroupInvite {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    invite_repo: Arc<dyn GroupInviteRepository>,
+}
+
+impl CreateGroupInvite {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        invite_repo: Arc<dyn GroupInviteRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            invite_repo,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        actor_id: Uuid,
+        conversation_id: Uuid,
+        expires_in_seconds: Option<i64>,
+        max_uses: Option<i32>,
+        requires_approval: bool,
+    ) -> DomainResult<GroupInvite> {
+        let (_, actor) = load_group_or_channel(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+        require_admin(&actor)?;
+
+        let expires_at = expires_in_seconds.map(|seconds| Utc::now() + Duration::seconds(seconds));
+        let invite = GroupInvite::new(conversation_id, actor_id, expires_at, max_uses, requires_approval);
+
+        self.invite_repo.create(&invite).await
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{post_system_message, rotate_sender_keys};
+use crate::application::use_cases::geo::helpers::record_live_location_event;
+use crate::domain::{
+    entities::{GroupEvent, SyncEvent, SyncEventType},
+    repositories::{
+        ConversationRepository, LiveLocationRepository, MessageRepository, SenderKeyRepository, SyncRepository,
+    },
+    DomainResult,
+};
+
+pub struct EnforceGeofences {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    sender_key_repo: Arc<dyn SenderKeyRepository>,
+    live_location_repo: Arc<dyn LiveLocationRepository>,
+}
+
+impl EnforceGeofences {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        sender_key_repo: Arc<dyn SenderKeyRepository>,
+        live_location_repo: Arc<dyn LiveLocationRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            message_repo,
+            sync_repo,
+            sender_key_repo,
+            live_location_repo,
+        }
+    }
+
+    /// Removes the user from location-bound groups set to drop members on exit whose area the
+    /// reported position is outside of. Owners stay, since a group cannot be left without one.
+    pub async fn execute(&self, user_id: Uuid, latitude: f64, longitude: f64) -> DomainResult<Vec<SyncEvent>> {
+        let left = self.conversation_repo.find_groups_left(user_id, latitude, longitude).await?;
+
+        let mut events = Vec::new();
+        for conversation in left {
+            // Post before removing so the departing member also receives the notice
+            events.push(
+                post_system_message(
+                    self.message_repo.as_ref(),
+                    conversation.id,
+                    user_id,
+                    GroupEvent::MemberLeftArea { user_id },
+                )
+                .await?,
+            );
+
+            self.conversation_repo.remove_participant(conversation.id, user_id).await?;
+            if let Some(session) = self.live_location_repo.stop_for_participant(conversation.id, user_id).await? {
+                events.push(record_live_location_event(self.sync_repo.as_ref(), &session, SyncEventType::LiveLocationStopped).await?);
+            }
+            events.extend(rotate_sender_keys(self.sender_key_repo.as_ref(), self.sync_repo.as_ref(), &conversation).await?);
+        }
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::stored_location;
+use crate::domain::{
+    entities::NearbyGroup,
+    repositories::{ConversationRepository, UserRepository},
+    DomainResult,
+};
+
+/// Most location-bound groups returned by one discovery request.
+const MAX_NEARBY_GROUPS: i64 = 50;
+
+pub struct FindNearbyGroups {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl FindNearbyGroups {
+    pub fn new(conversation_repo: Arc<dyn ConversationRepository>, user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { conversation_repo, user_repo }
+    }
+
+    /// Location-bound groups around the user's last shared location, closest first.
+    pub async fn execute(&self, user_id: Uuid) -> DomainResult<Vec<NearbyGroup>> {
+        let location = stored_location(self.user_repo.as_ref(), user_id).await?;
+
+        self.conversation_repo
+            .find_nearby_groups(user_id, location.latitude, location.longitude, MAX_NEARBY_GROUPS)
+            .await
+    }
+}
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::load_group;
+use crate::domain::{
+    entities::{Conversation, Participant},
+    repositories::ConversationRepository,
+    DomainResult,
+};
+
+pub struct GetGroup {
+    conversation_repo: Arc<dyn ConversationRepository>,
+}
+
+impl GetGroup {
+    pub fn new(conversation_repo: Arc<dyn ConversationRepository>) -> Self {
+        Self { conversation_repo }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, conversation_id: Uuid) -> DomainResult<(Conversation, Vec<Participant>)> {
+        let (conversation, _) = load_group(self.conversation_repo.as_ref(), conversation_id, user_id).await?;
+        let participants = self.conversation_repo.find_participants(conversation_id).await?;
+
+        Ok((conversation, participants))
+    }
+}
@@ -0,0 +1,130 @@
+use uuid::Uuid;
+
+use crate::application::MessageResponse;
+use crate::domain::{
+    entities::{Conversation, GeoPoint, GroupEvent, Message, Participant, SubscriptionTier, SyncEvent, SyncEventType},
+    repositories::{ConversationRepository, MessageRepository, SenderKeyRepository, SyncRepository, UserRepository},
+    DomainError, DomainResult,
+};
+
+/// Loads a group conversation and the acting user's membership in it.
+pub(super) async fn load_group(
+    conversation_repo: &dyn ConversationRepository,
+    conversation_id: Uuid,
+    actor_id: Uuid,
+) -> DomainResult<(Conversation, Participant)> {
+    let (conversation, participant) = load_group_or_channel(conversation_repo, conversation_id, actor_id).await?;
+
+    if !conversation.is_group() {
+        return Err(DomainError::ValidationError("Conversation is not a group".to_string()));
+    }
+
+    Ok((conversation, participant))
+}
+
+/// Like `load_group`, but also accepts channels, for the moderation and invite actions both
+/// share.
+pub(super) async fn load_group_or_channel(
+    conversation_repo: &dyn ConversationRepository,
+    conversation_id: Uuid,
+    actor_id: Uuid,
+) -> DomainResult<(Conversation, Participant)> {
+    let conversation = conversation_repo.find_by_id(conversation_id).await?
+        .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+    if !conversation.is_group() && !conversation.is_channel() {
+        return Err(DomainError::ValidationError("Conversation is not a group or channel".to_string()));
+    }
+
+    let participant = conversation_repo.find_participant(conversation_id, actor_id).await?
+        .ok_or_else(|| DomainError::AuthorizationError("Not a participant of this conversation".to_string()))?;
+
+    Ok((conversation, participant))
+}
+
+pub(super) fn require_admin(participant: &Participant) -> DomainResult<()> {
+    if !participant.is_admin() {
+        return Err(DomainError::AuthorizationError("Only group admins can do this".to_string()));
+    }
+    Ok(())
+}
+
+/// The user's last shared location, as stored on the server.
+pub(super) async fn stored_location(user_repo: &dyn UserRepository, user_id: Uuid) -> DomainResult<GeoPoint> {
+    user_repo.find_by_id(user_id).await?
+        .and_then(|user| user.location)
+        .map(|(latitude, longitude)| GeoPoint::new(latitude, longitude))
+        .ok_or_else(|| DomainError::ValidationError("Share your location to find nearby groups".to_string()))
+}
+
+/// Checks that `adding` more members fit within the limit of the group owner's subscription tier
+/// and returns that limit. Members can join between this check and the insert, so the limit is
+/// passed on to the insert, which enforces it again under a lock. Channels have no subscriber
+/// limit.
+pub(super) async fn ensure_capacity(
+    conversation_repo: &dyn ConversationRepository,
+    user_repo: &dyn UserRepository,
+    conversation: &Conversation,
+    adding: usize,
+) -> DomainResult<Option<usize>> {
+    if conversation.is_channel() {
+        return Ok(None);
+    }
+
+    let participants = conversation_repo.find_participants(conversation.id).await?;
+
+    let owner_id = participants.iter().find(|p| p.is_owner()).map(|p| p.user_id);
+    let limit = match owner_id {
+        Some(owner_id) => user_repo.find_by_id(owner_id).await?
+            .map(|owner| owner.subscription_tier)
+            .unwrap_or(SubscriptionTier::Free)
+            .max_group_members(),
+        None => SubscriptionTier::Free.max_group_members(),
+    };
+
+    if participants.len() + adding > limit {
+        return Err(DomainError::ValidationError(format!("Group is limited to {} members", limit)));
+    }
+    Ok(Some(limit))
+}
+
+/// Stores a system message describing a group change together with its sync log entry.
+pub(crate) async fn post_system_message(
+    message_repo: &dyn MessageRepository,
+    conversation_id: Uuid,
+    actor_id: Uuid,
+    event: GroupEvent,
+) -> DomainResult<SyncEvent> {
+    let message = Message::system(conversation_id, actor_id, &event);
+
+    let payload = serde_json::to_value(MessageResponse::from(&message))
+        .map_err(|e| DomainError::InternalError(format!("Serialization error: {}", e)))?;
+    let event = SyncEvent::new(conversation_id, SyncEventType::MessageCreated, payload);
+
+    let (_, event) = message_repo.create_with_event(&message, &event).await?;
+    Ok(event)
+}
+
+/// Starts a new sender key epoch after a membership change, so departed members cannot decrypt
+/// later messages and new members cannot decrypt earlier ones. Channels do not use sender keys.
+pub(super) async fn rotate_sender_keys(
+    sender_key_repo: &dyn SenderKeyRepository,
+    sync_repo: &dyn SyncRepository,
+    conversation: &Conversation,
+) -> DomainResult<Option<SyncEvent>> {
+    if !conversation.is_group() {
+        return Ok(None);
+    }
+
+    let epoch = sender_key_repo.rotate(conversation.id).await?;
+
+    let event = sync_repo
+        .append(&SyncEvent::new(
+            conversation.id,
+            SyncEventType::SenderKeyRotated,
+            serde_json::json!({ "epoch": epoch }),
+        ))
+        .await?;
+
+    Ok(Some(event))
+}
@@ -0,0 +1,107 @@
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{ensure_capacity, post_system_message, rotate_sender_keys};
+use crate::domain::{
+    entities::{GroupEvent, JoinRequest, Participant, ParticipantRole, SyncEvent},
+    repositories::{ConversationRepository, GroupInviteRepository, MessageRepository, SenderKeyRepository, SyncRepository, UserRepository},
+    DomainError, DomainResult,
+};
+
+pub enum JoinGroupResult {
+    /// Joined right away, with the join notice and sender key rotation to fan out (channels post neither).
+    Joined(Uuid, Vec<SyncEvent>),
+    /// The invite requires approval; an admin has to accept the join request.
+    PendingApproval(Uuid),
+}
+
+pub struct JoinGroupViaInvite {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    invite_repo: Arc<dyn GroupInviteRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    sender_key_repo: Arc<dyn SenderKeyRepository>,
+}
+
+impl JoinGroupViaInvite {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        invite_repo: Arc<dyn GroupInviteRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        sender_key_repo: Arc<dyn SenderKeyRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            invite_repo,
+            user_repo,
+            message_repo,
+            sync_repo,
+            sender_key_repo,
+        }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, token: &str) -> DomainResult<JoinGroupResult> {
+        let invite = self.invite_repo.find_by_token(token).await?
+            .filter(|invite| invite.is_usable())
+            .ok_or_else(|| DomainError::NotFound("Invite link is invalid or has expired".to_string()))?;
+        let conversation_id = invite.conversation_id;
+
+        if self.conversation_repo.is_participant(conversation_id, user_id).await? {
+            return Err(DomainError::Conflict("Already a member of this group".to_string()));
+        }
+        if self.conversation_repo.is_banned(conversation_id, user_id).await? {
+            return Err(DomainError::AuthorizationError("You have been banned from this group".to_string()));
+        }
+        if invite.requires_approval
+            && self.invite_repo.find_join_request(conversation_id, user_id).await?.is_some()
+        {
+            return Ok(JoinGroupResult::PendingApproval(conversation_id));
+        }
+
+        let conversation = self.conversation_repo.find_by_id(conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+        let limit = ensure_capacity(self.conversation_repo.as_ref(), self.user_repo.as_ref(), &conversation, 1).await?;
+
+        // Claim a use atomically so concurrent joins cannot exceed max_uses
+        if !self.invite_repo.consume(invite.id).await? {
+            return Err(DomainError::NotFound("Invite link is invalid or has expired".to_string()));
+        }
+
+        if invite.requires_approval {
+            self.invite_repo
+                .create_join_request(&JoinRequest {
+                    conversation_id,
+                    user_id,
+                    invite_id: Some(invite.id),
+                    created_at: Utc::now(),
+                })
+                .await?;
+            return Ok(JoinGroupResult::PendingApproval(conversation_id));
+        }
+
+        self.conversation_repo
+            .add_participant(&Participant::new(conversation_id, user_id, ParticipantRole::Member), limit)
+            .await?;
+
+        if conversation.is_channel() {
+            return Ok(JoinGroupResult::Joined(conversation_id, Vec::new()));
+        }
+
+        let event = post_system_message(
+            self.message_repo.as_ref(),
+            conversation_id,
+            user_id,
+            GroupEvent::MemberJoined { user_id, approved_by: None },
+        )
+        .await?;
+
+        let mut events = vec![event];
+        events.extend(rotate_sender_keys(self.sender_key_repo.as_ref(), self.sync_repo.as_ref(), &conversation).await?);
+
+        Ok(JoinGroupResult::Joined(conversation_id, events))
+    }
+}
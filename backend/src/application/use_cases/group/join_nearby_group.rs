@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{ensure_capacity, post_system_message, rotate_sender_keys, stored_location};
+use crate::domain::{
+    entities::{GroupEvent, Participant, ParticipantRole, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository, SenderKeyRepository, SyncRepository, UserRepository},
+    DomainError, DomainResult,
+};
+
+pub struct JoinNearbyGroup {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    sender_key_repo: Arc<dyn SenderKeyRepository>,
+}
+
+impl JoinNearbyGroup {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        sender_key_repo: Arc<dyn SenderKeyRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            user_repo,
+            message_repo,
+            sync_repo,
+            sender_key_repo,
+        }
+    }
+
+    /// Joins a location-bound group without an invite, as long as the user's last shared location
+    /// is inside its area.
+    pub async fn execute(&self, user_id: Uuid, conversation_id: Uuid) -> DomainResult<Vec<SyncEvent>> {
+        let conversation = self.conversation_repo.find_by_id(conversation_id).await?
+            .filter(|conversation| conversation.is_group())
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+        let geofence = conversation.geofence.as_ref()
+            .ok_or_else(|| DomainError::ValidationError("Group is not location-bound".to_string()))?;
+        let location = stored_location(self.user_repo.as_ref(), user_id).await?;
+        if !geofence.may_contain(&location) {
+            return Err(DomainError::AuthorizationError("You are outside this group's area".to_string()));
+        }
+
+        if self.conversation_repo.is_participant(conversation_id, user_id).await? {
+            return Err(DomainError::Conflict("Already a member of this group".to_string()));
+        }
+        if self.conversation_repo.is_banned(conversation_id, user_id).await? {
+            return Err(DomainError::AuthorizationError("You have been banned from this group".to_string()));
+        }
+        let limit = ensure_capacity(self.conversation_repo.as_ref(), self.user_repo.as_ref(), &conversation, 1).await?;
+
+        self.conversation_repo
+            .add_participant(&Participant::new(conversation_id, user_id, ParticipantRole::Member), limit)
+            .await?;
+
+        let event = post_system_message(
+            self.message_repo.as_ref(),
+            conversation_id,
+            user_id,
+            GroupEvent::MemberJoined { user_id, approved_by: None },
+        )
+        .await?;
+
+        let mut events = vec![event];
+        events.extend(rotate_sender_keys(self.sender_key_repo.as_ref(), self.sync_repo.as_ref(), &conversation).await?);
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{load_group_or_channel, require_admin};
+use crate::domain::{
+    entities::GroupInvite,
+    repositories::{ConversationRepository, GroupInviteRepository},
+    DomainResult,
+};
+
+pub struct ListGroupInvites {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    invite_repo: Arc<dyn GroupInviteRepository>,
+}
+
+impl ListGroupInvites {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        invite_repo: Arc<dyn GroupInviteRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            invite_repo,
+        }
+    }
+
+    /// Lists the group's invites that have not been revoked.
+    pub async fn execute(&self, actor_id: Uuid, conversation_id: Uuid) -> DomainResult<Vec<GroupInvite>> {
+        let (_, actor) = load_group_or_channel(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+        require_admin(&actor)?;
+
+        self.invite_repo.find_by_conversation(conversation_id).await
+    }
+}
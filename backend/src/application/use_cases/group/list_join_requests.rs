@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{load_group_or_channel, require_admin};
+use crate::domain::{
+    entities::JoinRequest,
+    repositories::{ConversationRepository, GroupInviteRepository},
+    DomainResult,
+};
+
+pub struct ListJoinRequests {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    invite_repo: Arc<dyn GroupInviteRepository>,
+}
+
+impl ListJoinRequests {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        invite_repo: Arc<dyn GroupInviteRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            invite_repo,
+        }
+    }
+
+    pub async fn execute(&self, actor_id: Uuid, conversation_id: Uuid) -> DomainResult<Vec<JoinRequest>> {
+        let (_, actor) = load_group_or_channel(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+        require_admin(&actor)?;
+
+        self.invite_repo.find_join_requests(conversation_id).await
+    }
+}
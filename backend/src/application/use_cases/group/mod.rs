@@ -0,0 +1,38 @@
+pub(crate) mod helpers;
+pub mod create_group;
+pub mod get_group;
+pub mod add_group_members;
+pub mod remove_group_member;
+pub mod change_member_role;
+pub mod transfer_group_ownership;
+pub mod update_group_info;
+pub mod update_group_settings;
+pub mod create_group_invite;
+pub mod list_group_invites;
+pub mod revoke_group_invite;
+pub mod preview_group_invite;
+pub mod join_group_via_invite;
+pub mod list_join_requests;
+pub mod review_join_request;
+pub mod find_nearby_groups;
+pub mod join_nearby_group;
+pub mod enforce_geofences;
+
+pub use create_group::CreateGroup;
+pub use get_group::GetGroup;
+pub use add_group_members::AddGroupMembers;
+pub use remove_group_member::RemoveGroupMember;
+pub use change_member_role::ChangeMemberRole;
+pub use transfer_group_ownership::TransferGroupOwnership;
+pub use update_group_info::UpdateGroupInfo;
+pub use update_group_settings::UpdateGroupSettings;
+pub use create_group_invite::CreateGroupInvite;
+pub use list_group_invites::ListGroupInvites;
+pub use revoke_group_invite::RevokeGroupInvite;
+pub use preview_group_invite::PreviewGroupInvite;
+pub use join_group_via_invite::{JoinGroupViaInvite, JoinGroupResult};
+pub use list_join_requests::ListJoinRequests;
+pub use review_join_request::ReviewJoinRequest;
+pub use find_nearby_groups::FindNearbyGroups;
+pub use join_nearby_group::JoinNearbyGroup;
+pub use enforce_geofences::EnforceGeofences;
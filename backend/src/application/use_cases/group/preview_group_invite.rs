@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    entities::{Conversation, GroupInvite},
+    repositories::{ConversationRepository, GroupInviteRepository},
+    DomainError, DomainResult,
+};
+
+pub struct PreviewGroupInvite {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    invite_repo: Arc<dyn GroupInviteRepository>,
+}
+
+impl PreviewGroupInvite {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        invite_repo: Arc<dyn GroupInviteRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            invite_repo,
+        }
+    }
+
+    /// Returns the group behind an invite token and its member count, without joining it.
+    pub async fn execute(&self, token: &str) -> DomainResult<(GroupInvite, Conversation, i64)> {
+        let invite = self.invite_repo.find_by_token(token).await?
+            .filter(|invite| invite.is_usable())
+            .ok_or_else(|| DomainError::NotFound("Invite link is invalid or has expired".to_string()))?;
+
+        let conversation = self.conversation_repo.find_by_id(invite.conversation_id).await?
+            .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+        let member_count = self.conversation_repo.count_participants(conversation.id).await?;
+
+        Ok((invite, conversation, member_count))
+    }
+}
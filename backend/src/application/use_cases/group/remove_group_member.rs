@@ -0,0 +1,121 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{load_group_or_channel, post_system_message, rotate_sender_keys};
+use crate::application::use_cases::call::helpers::leave_group_call;
+use crate::application::use_cases::geo::helpers::record_live_location_event;
+use crate::domain::{
+    entities::{GroupEvent, SyncEvent, SyncEventType},
+    repositories::{
+        ConversationRepository, GroupCallRepository, LiveLocationRepository, MessageRepository,
+        SenderKeyRepository, SyncRepository,
+    },
+    services::SfuProvider,
+    DomainError, DomainResult,
+};
+
+pub struct RemoveGroupMember {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    sender_key_repo: Arc<dyn SenderKeyRepository>,
+    live_location_repo: Arc<dyn LiveLocationRepository>,
+    group_call_repo: Arc<dyn GroupCallRepository>,
+    sfu_provider: Arc<dyn SfuProvider>,
+}
+
+impl RemoveGroupMember {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        sender_key_repo: Arc<dyn SenderKeyRepository>,
+        live_location_repo: Arc<dyn LiveLocationRepository>,
+        group_call_repo: Arc<dyn GroupCallRepository>,
+        sfu_provider: Arc<dyn SfuProvider>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            message_repo,
+            sync_repo,
+            sender_key_repo,
+            live_location_repo,
+            group_call_repo,
+            sfu_provider,
+        }
+    }
+
+    /// Removes `user_id` from the group. Removing yourself leaves the group; the owner
+    /// must transfer ownership first. Admins can only remove participants below their own role,
+    /// and may `ban` them from rejoining through invite links. The group's sender keys are
+    /// rotated, a live location the member was sharing is stopped, and they are disconnected
+    /// from the group's call.
+    pub async fn execute(
+        &self,
+        actor_id: Uuid,
+        conversation_id: Uuid,
+        user_id: Uuid,
+        ban: bool,
+    ) -> DomainResult<Vec<SyncEvent>> {
+        let (conversation, actor) = load_group_or_channel(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+
+        let event = if actor_id == user_id {
+            if actor.is_owner() {
+                return Err(DomainError::ValidationError("Transfer ownership before leaving the group".to_string()));
+            }
+            GroupEvent::MemberLeft { user_id }
+        } else {
+            let target = self.conversation_repo.find_participant(conversation_id, user_id).await?
+                .ok_or_else(|| DomainError::NotFound("User is not a member of this group".to_string()))?;
+
+            if !actor.is_admin() || target.role >= actor.role {
+                return Err(DomainError::AuthorizationError("Not allowed to remove this member".to_string()));
+            }
+            GroupEvent::MemberRemoved { actor_id, user_id }
+        };
+
+        // An SFU outage must not keep anyone in the group; the member is still taken out of
+        // the call below and cannot get a new join token
+        let call = self.group_call_repo.find_active(conversation_id).await?;
+        if let Some(call) = &call {
+            if let Err(e) = self.sfu_provider.remove_participant(&call.room_name, user_id).await {
+                tracing::warn!("Failed to remove {} from SFU room {}: {}", user_id, call.room_name, e);
+            }
+        }
+
+        // Post before removing so the departing member also receives the notice
+        let event = post_system_message(
+            self.message_repo.as_ref(),
+            conversation_id,
+            actor_id,
+            event,
+        )
+        .await?;
+
+        self.conversation_repo.remove_participant(conversation_id, user_id).await?;
+        if ban && actor_id != user_id {
+            self.conversation_repo.ban(conversation_id, user_id, actor_id).await?;
+        }
+
+        let mut events = vec![event];
+        if let Some(session) = self.live_location_repo.stop_for_participant(conversation_id, user_id).await? {
+            events.push(record_live_location_event(self.sync_repo.as_ref(), &session, SyncEventType::LiveLocationStopped).await?);
+        }
+        if let Some(call) = &call {
+            events.extend(
+                leave_group_call(
+                    self.group_call_repo.as_ref(),
+                    self.message_repo.as_ref(),
+                    self.sync_repo.as_ref(),
+                    self.sfu_provider.as_ref(),
+                    call,
+                    &[user_id],
+                )
+                .await?,
+            );
+        }
+        events.extend(rotate_sender_keys(self.sender_key_repo.as_ref(), self.sync_repo.as_ref(), &conversation).await?);
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{ensure_capacity, load_group_or_channel, post_system_message, require_admin, rotate_sender_keys};
+use crate::domain::{
+    entities::{GroupEvent, Participant, ParticipantRole, SyncEvent},
+    repositories::{ConversationRepository, GroupInviteRepository, MessageRepository, SenderKeyRepository, SyncRepository, UserRepository},
+    DomainError, DomainResult,
+};
+
+pub struct ReviewJoinRequest {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    invite_repo: Arc<dyn GroupInviteRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    sender_key_repo: Arc<dyn SenderKeyRepository>,
+}
+
+impl ReviewJoinRequest {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        invite_repo: Arc<dyn GroupInviteRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        sender_key_repo: Arc<dyn SenderKeyRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            invite_repo,
+            user_repo,
+            message_repo,
+            sync_repo,
+            sender_key_repo,
+        }
+    }
+
+    /// Approves or rejects a pending join request. Returns the join notice and sender key
+    /// rotation when approved.
+    pub async fn execute(
+        &self,
+        actor_id: Uuid,
+        conversation_id: Uuid,
+        user_id: Uuid,
+        approve: bool,
+    ) -> DomainResult<Vec<SyncEvent>> {
+        let (conversation, actor) = load_group_or_channel(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+        require_admin(&actor)?;
+
+        if self.invite_repo.find_join_request(conversation_id, user_id).await?.is_none() {
+            return Err(DomainError::NotFound("Join request not found".to_string()));
+        }
+
+        if !approve {
+            self.invite_repo.delete_join_request(conversation_id, user_id).await?;
+            return Ok(Vec::new());
+        }
+
+        let limit = ensure_capacity(self.conversation_repo.as_ref(), self.user_repo.as_ref(), &conversation, 1).await?;
+
+        self.invite_repo
+            .approve_join_request(&Participant::new(conversation_id, user_id, ParticipantRole::Member), limit)
+            .await?;
+
+        let event = post_system_message(
+            self.message_repo.as_ref(),
+            conversation_id,
+            actor_id,
+            GroupEvent::MemberJoined { user_id, approved_by: Some(actor_id) },
+        )
+        .await?;
+
+        let mut events = vec![event];
+        events.extend(rotate_sender_keys(self.sender_key_repo.as_ref(), self.sync_repo.as_ref(), &conversation).await?);
+
+        Ok(events)
+    }
+}
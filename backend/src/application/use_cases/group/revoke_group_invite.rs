@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{load_group_or_channel, require_admin};
+use crate::domain::{
+    repositories::{ConversationRepository, GroupInviteRepository},
+    DomainError, DomainResult,
+};
+
+pub struct RevokeGroupInvite {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    invite_repo: Arc<dyn GroupInviteRepository>,
+}
+
+impl RevokeGroupInvite {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        invite_repo: Arc<dyn GroupInviteRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            invite_repo,
+        }
+    }
+
+    pub async fn execute(&self, actor_id: Uuid, conversation_id: Uuid, invite_id: Uuid) -> DomainResult<()> {
+        let (_, actor) = load_group_or_channel(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+        require_admin(&actor)?;
+
+        let invite = self.invite_repo.find_by_id(invite_id).await?
+            .filter(|invite| invite.conversation_id == conversation_id)
+            .ok_or_else(|| DomainError::NotFound("Invite not found".to_string()))?;
+
+        self.invite_repo.revoke(invite.id).await
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{load_group_or_channel, post_system_message};
+use crate::domain::{
+    entities::{GroupEvent, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository},
+    DomainError, DomainResult,
+};
+
+pub struct TransferGroupOwnership {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl TransferGroupOwnership {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            message_repo,
+        }
+    }
+
+    /// Hands ownership to another participant; the previous owner stays on as an admin.
+    pub async fn execute(&self, actor_id: Uuid, conversation_id: Uuid, new_owner_id: Uuid) -> DomainResult<SyncEvent> {
+        let (_, actor) = load_group_or_channel(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+
+        if !actor.is_owner() {
+            return Err(DomainError::AuthorizationError("Only the group owner can transfer ownership".to_string()));
+        }
+        if new_owner_id == actor_id {
+            return Err(DomainError::ValidationError("You already own this group".to_string()));
+        }
+        if !self.conversation_repo.is_participant(conversation_id, new_owner_id).await? {
+            return Err(DomainError::NotFound("User is not a member of this group".to_string()));
+        }
+
+        self.conversation_repo.transfer_ownership(conversation_id, actor_id, new_owner_id).await?;
+
+        post_system_message(
+            self.message_repo.as_ref(),
+            conversation_id,
+            actor_id,
+            GroupEvent::OwnershipTransferred { actor_id, user_id: new_owner_id },
+        )
+        .await
+    }
+}
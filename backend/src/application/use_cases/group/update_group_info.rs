@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{load_group_or_channel, post_system_message, require_admin};
+use crate::domain::{
+    entities::{GroupEvent, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository},
+    DomainResult,
+};
+
+pub struct UpdateGroupInfo {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl UpdateGroupInfo {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            message_repo,
+        }
+    }
+
+    /// Renames the group and/or changes its avatar. Fields left as `None` are unchanged.
+    pub async fn execute(
+        &self,
+        actor_id: Uuid,
+        conversation_id: Uuid,
+        name: Option<String>,
+        avatar_url: Option<String>,
+    ) -> DomainResult<Vec<SyncEvent>> {
+        let (conversation, actor) = load_group_or_channel(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+        require_admin(&actor)?;
+
+        let renamed = name.filter(|name| conversation.name.as_ref() != Some(name));
+        let new_avatar_url = avatar_url.filter(|url| conversation.avatar_url.as_ref() != Some(url));
+        if renamed.is_none() && new_avatar_url.is_none() {
+            return Ok(Vec::new());
+        }
+
+        self.conversation_repo
+            .update_details(
+                conversation_id,
+                renamed.clone().or(conversation.name),
+                new_avatar_url.clone().or(conversation.avatar_url),
+            )
+            .await?;
+
+        let mut changes = Vec::new();
+        if let Some(name) = renamed {
+            changes.push(GroupEvent::GroupRenamed { actor_id, name });
+        }
+        if let Some(avatar_url) = new_avatar_url {
+            changes.push(GroupEvent::AvatarChanged { actor_id, avatar_url });
+        }
+
+        let mut events = Vec::new();
+        for change in changes {
+            let event = post_system_message(
+                self.message_repo.as_ref(),
+                conversation_id,
+                actor_id,
+                change,
+            )
+            .await?;
+            events.push(event);
+        }
+
+        Ok(events)
+    }
+}
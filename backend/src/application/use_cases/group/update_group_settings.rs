@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::helpers::{load_group, post_system_message, require_admin};
+use crate::domain::{
+    entities::{Conversation, GroupEvent, SyncEvent},
+    repositories::{ConversationRepository, MessageRepository},
+    DomainResult,
+};
+
+pub struct UpdateGroupSettings {
+    conversation_repo: Arc<dyn ConversationRepository>,
+    message_repo: Arc<dyn MessageRepository>,
+}
+
+impl UpdateGroupSettings {
+    pub fn new(
+        conversation_repo: Arc<dyn ConversationRepository>,
+        message_repo: Arc<dyn MessageRepository>,
+    ) -> Self {
+        Self {
+            conversation_repo,
+            message_repo,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        actor_id: Uuid,
+        conversation_id: Uuid,
+        only_admins_can_post: Option<bool>,
+        forwarding_enabled: Option<bool>,
+    ) -> DomainResult<(Conversation, Option<SyncEvent>)> {
+        let (mut conversation, actor) = load_group(self.conversation_repo.as_ref(), conversation_id, actor_id).await?;
+        require_admin(&actor)?;
+
+        let previous = conversation.settings.clone();
+        if let Some(enabled) = only_admins_can_post {
+            conversation.set_only_admins_can_post(enabled);
+        }
+        if let Some(enabled) = forwarding_enabled {
+            conversation.set_forwarding_enabled(enabled);
+        }
+        if conversation.settings == previous {
+            return Ok((conversation, None));
+        }
+
+        self.conversation_repo.update_settings(conversation_id, &conversation.settings).await?;
+
+        let event = post_system_message(
+            self.message_repo.as_ref(),
+            conversation_id,
+            actor_id,
+            GroupEvent::SettingsChanged { actor_id, settings: conversation.settings.clone() },
+        )
+        .await?;
+
+        Ok((conversation, Some(event)))
+    }
+}
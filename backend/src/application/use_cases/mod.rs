@@ -4,10 +4,43 @@ pub mod chat;
 pub mod geo;
 pub mod subscription;
 pub mod notification;
+pub mod group;
+pub mod channel;
+pub mod e2ee;
+pub mod backup;
+pub mod call;
 
 pub use auth::{LoginUser, RegisterUser, VerifyOtp, UploadPublicKey, GetPublicKey};
 pub use kyc::{GetUploadUrl, SubmitKyc, ReviewKyc};
-pub use chat::SendMessage;
-pub use geo::{UpdateLocation, FindNearbyUsers};
-pub use subscription::UpgradeSubscription;
+pub use chat::{
+    SendMessage, EditMessage, DeleteMessage, MarkAsRead, GetMissedEvents, AcknowledgeSync,
+    SetDisappearingMessages, GetMessageHistory, GetThread,
+    PinMessage, UnpinMessage, GetPinnedMessages, StarMessage, UnstarMessage, GetStarredMessages,
+    ForwardMessage,
+};
+pub use geo::{
+    UpdateLocation, FindNearbyUsers, SetDiscoverability, BlockUser, UnblockUser,
+    StartLiveLocation, UpdateLiveLocation, StopLiveLocation, GetLiveLocations, ExpireLiveLocations,
+};
+pub use subscription::{UpgradeSubscription, GetSubscription, CancelSubscription, HandlePaymentWebhook};
 pub use notification::RegisterDeviceToken;
+pub use group::{
+    CreateGroup, GetGroup, AddGroupMembers, RemoveGroupMember, ChangeMemberRole,
+    TransferGroupOwnership, UpdateGroupInfo, UpdateGroupSettings,
+    CreateGroupInvite, ListGroupInvites, RevokeGroupInvite, PreviewGroupInvite,
+    JoinGroupViaInvite, JoinGroupResult, ListJoinRequests, ReviewJoinRequest,
+    FindNearbyGroups, JoinNearbyGroup, EnforceGeofences,
+};
+pub use channel::{CreateChannel, SubscribeChannel, UnsubscribeChannel, GetChannelViews};
+pub use e2ee::{
+    RegisterDeviceKeys, UploadOneTimePreKeys, GetPreKeyCount, ClaimPreKeyBundles, GetKeyHistory,
+    DistributeSenderKey, GetSenderKeys, AuthorizeKeyRotation, KeyRotationProof,
+};
+pub use backup::{
+    SetupBackupVault, UnlockBackupVault, CreateBackup, CompleteBackup, ListBackups, RestoreBackup,
+    DeleteBackup, DisableBackups,
+};
+pub use call::{
+    StartCall, AnswerCall, EndCall, GetActiveCall, ExpireCalls, TrackCallPresence, GetIceServers,
+    JoinGroupCall, LeaveGroupCall, GetGroupCall, SweepGroupCalls,
+};
@@ -0,0 +1,45 @@
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Subscription, SubscriptionStatus},
+    repositories::SubscriptionRepository,
+    services::PaymentProvider,
+    DomainError, DomainResult,
+};
+
+pub struct CancelSubscription {
+    subscription_repo: Arc<dyn SubscriptionRepository>,
+    payment_provider: Arc<dyn PaymentProvider>,
+}
+
+impl CancelSubscription {
+    pub fn new(subscription_repo: Arc<dyn SubscriptionRepository>, payment_provider: Arc<dyn PaymentProvider>) -> Self {
+        Self {
+            subscription_repo,
+            payment_provider,
+        }
+    }
+
+    /// Turns off renewal. The user keeps the tier until the paid period ends, when the provider
+    /// reports the cancellation and the expiry job downgrades them.
+    pub async fn execute(&self, user_id: Uuid) -> DomainResult<Subscription> {
+        let mut subscription = self.subscription_repo.find_by_user(user_id).await?
+            .filter(|subscription| {
+                subscription.is_entitled(Utc::now()) && subscription.status != SubscriptionStatus::Canceled
+            })
+            .ok_or_else(|| DomainError::NotFound("No active subscription".to_string()))?;
+
+        if subscription.cancel_at_period_end {
+            return Ok(subscription);
+        }
+
+        self.payment_provider
+            .cancel_subscription(&subscription.provider_subscription_id)
+            .await?;
+
+        subscription.cancel_at_period_end = true;
+        self.subscription_repo.save(&subscription).await
+    }
+}
@@ -0,0 +1,19 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{entities::Subscription, repositories::SubscriptionRepository, DomainResult};
+
+pub struct GetSubscription {
+    subscription_repo: Arc<dyn SubscriptionRepository>,
+}
+
+impl GetSubscription {
+    pub fn new(subscription_repo: Arc<dyn SubscriptionRepository>) -> Self {
+        Self { subscription_repo }
+    }
+
+    /// The user's latest subscription, if they ever had one.
+    pub async fn execute(&self, user_id: Uuid) -> DomainResult<Option<Subscription>> {
+        self.subscription_repo.find_by_user(user_id).await
+    }
+}
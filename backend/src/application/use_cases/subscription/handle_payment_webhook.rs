@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    repositories::SubscriptionRepository,
+    services::PaymentProvider,
+    DomainResult,
+};
+
+pub struct HandlePaymentWebhook {
+    subscription_repo: Arc<dyn SubscriptionRepository>,
+    payment_provider: Arc<dyn PaymentProvider>,
+}
+
+impl HandlePaymentWebhook {
+    pub fn new(subscription_repo: Arc<dyn SubscriptionRepository>, payment_provider: Arc<dyn PaymentProvider>) -> Self {
+        Self {
+            subscription_repo,
+            payment_provider,
+        }
+    }
+
+    /// Header the provider puts the webhook signature in.
+    pub fn signature_header(&self) -> &'static str {
+        self.payment_provider.signature_header()
+    }
+
+    /// Verifies a webhook delivery and applies it to the subscription it is about. Deliveries
+    /// already applied are acknowledged without applying them again.
+    pub async fn execute(&self, payload: &[u8], signature: &str) -> DomainResult<()> {
+        let webhook = self.payment_provider.parse_webhook(payload, signature)?;
+
+        self.subscription_repo.apply_webhook(&webhook.event_id, webhook.event.as_ref()).await?;
+
+        Ok(())
+    }
+}
@@ -1,3 +1,9 @@
 pub mod upgrade_subscription;
+pub mod get_subscription;
+pub mod cancel_subscription;
+pub mod handle_payment_webhook;
 
 pub use upgrade_subscription::UpgradeSubscription;
+pub use get_subscription::GetSubscription;
+pub use cancel_subscription::CancelSubscription;
+pub use handle_payment_webhook::HandlePaymentWebhook;
@@ -1,46 +1,110 @@
+use chrono::Utc;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::domain::{
-    repositories::UserRepository,
-    DomainResult,
+    entities::{Subscription, SubscriptionCheckout, SubscriptionTier, SUBSCRIPTION_CHECKOUT_TIMEOUT_SECONDS},
+    repositories::SubscriptionRepository,
+    services::PaymentProvider,
+    DomainError, DomainResult,
 };
-use crate::application::SubscriptionResponse;
 
 pub struct UpgradeSubscription {
-    user_repo: Arc<dyn UserRepository>,
+    subscription_repo: Arc<dyn SubscriptionRepository>,
+    payment_provider: Arc<dyn PaymentProvider>,
 }
 
 impl UpgradeSubscription {
-    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
-        Self { user_repo }
+    pub fn new(subscription_repo: Arc<dyn SubscriptionRepository>, payment_provider: Arc<dyn PaymentProvider>) -> Self {
+        Self {
+            subscription_repo,
+            payment_provider,
+        }
     }
 
-    pub async fn execute(&self, user_id: Uuid, tier: String) -> DomainResult<SubscriptionResponse> {
-        // In a real app, we would verify payment_token here
-        
-        let updated_user = self.user_repo.update_subscription(user_id, tier.clone()).await?;
-
-        // Define features based on tier
-        let features = match tier.as_str() {
-            "Premium" => vec![
-                "Unlimited Messages".to_string(), 
-                "4K Video Calls".to_string(),
-                "Verified Badge".to_string()
-            ],
-            "Gold" => vec![
-                "Unlimited Messages".to_string(), 
-                "8K Video Calls".to_string(),
-                "Verified Badge".to_string(),
-                "Priority Support".to_string()
-            ],
-            _ => vec!["Standard Messaging".to_string()],
+    /// Subscribes the user to a paid tier, charging the first period to `payment_token`. The
+    /// tier is granted once the provider accepted the payment. The upgrade is reserved before
+    /// charging, so concurrent or retried upgrades never charge the user twice. An upgrade that
+    /// failed midway can only be retried with the same tier and payment token, until it is
+    /// stale and the provider confirms it charged nothing.
+    pub async fn execute(&self, user_id: Uuid, tier: SubscriptionTier, payment_token: &str) -> DomainResult<Subscription> {
+        if tier == SubscriptionTier::Free {
+            return Err(DomainError::ValidationError(
+                "Cancel the subscription to return to the Free tier".to_string(),
+            ));
+        }
+
+        if let Some(pending) = self.subscription_repo.find_checkout(user_id).await? {
+            if !pending.matches(tier, payment_token) {
+                self.abandon_checkout(&pending).await?;
+            }
+        }
+
+        let checkout = self.subscription_repo
+            .reserve_checkout(&SubscriptionCheckout::new(user_id, tier, payment_token), SUBSCRIPTION_CHECKOUT_TIMEOUT_SECONDS)
+            .await?;
+        let idempotency_key = checkout.id.to_string();
+
+        let billed = match self.payment_provider
+            .create_subscription(user_id, tier, payment_token, &idempotency_key)
+            .await
+        {
+            Ok(billed) => billed,
+            // A declined payment charged nothing. Other failures may have charged, so the
+            // reservation is kept and a retry after it went stale replays the same request
+            Err(DomainError::ValidationError(message)) => {
+                self.subscription_repo.release_checkout(checkout.id).await?;
+                return Err(DomainError::ValidationError(message));
+            }
+            Err(e) => return Err(e),
         };
 
-        Ok(SubscriptionResponse {
-            tier: updated_user.subscription_tier.unwrap_or_else(|| "Free".to_string()),
-            expires_at: None, // Lifetime for this MVP
-            features,
-        })
+        let subscription = Subscription::new(
+            user_id,
+            tier,
+            billed.id,
+            billed.current_period_start,
+            billed.current_period_end,
+        );
+
+        match self.subscription_repo.complete_checkout(checkout.id, &subscription).await {
+            Ok(saved) => Ok(saved),
+            Err(e) => {
+                // Paid but not recorded: give the money back. Should that fail too, the kept
+                // reservation lets a retry replay the charge and record it. A refunded charge
+                // replayed that way is ended again by the provider's retried cancellation webhook
+                match self.payment_provider.refund_subscription(&subscription.provider_subscription_id).await {
+                    Ok(()) => {
+                        if let Err(release_error) = self.subscription_repo.release_checkout(checkout.id).await {
+                            tracing::warn!("Failed to release checkout {}: {}", checkout.id, release_error);
+                        }
+                    }
+                    Err(refund_error) => tracing::error!(
+                        "Subscription {} of user {} was paid but neither recorded nor refunded: {}",
+                        subscription.provider_subscription_id,
+                        user_id,
+                        refund_error
+                    ),
+                }
+                Err(e)
+            }
+        }
+    }
+    /// Drops a pending upgrade with other details than the one being started. It may have
+    /// charged without being recorded, so it is only dropped once stale and unknown to the
+    /// provider; otherwise the user has to retry it as it was.
+    async fn abandon_checkout(&self, pending: &SubscriptionCheckout) -> DomainResult<()> {
+        if !pending.is_stale(Utc::now()) {
+            return Err(DomainError::Conflict("An upgrade is already in progress".to_string()));
+        }
+
+        if self.payment_provider.find_subscription(&pending.id.to_string()).await?.is_some() {
+            return Err(DomainError::Conflict(format!(
+                "An earlier upgrade to {:?} was charged; retry it with the same tier and payment method to finish it",
+                pending.tier
+            )));
+        }
+
+        self.subscription_repo.release_checkout(pending.id).await
     }
 }
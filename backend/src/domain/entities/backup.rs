@@ -0,0 +1,157 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use ring::{
+    aead, hkdf, hmac,
+    rand::{SecureRandom, SystemRandom},
+};
+use uuid::Uuid;
+
+/// Wrong passphrase guesses allowed before the vault secret is destroyed.
+pub const MAX_UNLOCK_ATTEMPTS: i32 = 10;
+
+/// Server-held half of a user's backup key. The client combines the secret with a key
+/// stretched from the passphrase, so a stolen archive alone cannot be decrypted. The secret is
+/// stored sealed under a key derived from the access key and a server pepper: the server only
+/// sees it while handling a request that carries the access key. The pepper is kept out of the
+/// database (in the deployment's secret store or KMS), so a copy of the database alone gives
+/// nothing to guess passphrases against. Someone holding both the database and the pepper can
+/// still guess offline; the unlock limit only counts guesses made through the server.
+#[derive(Debug, Clone)]
+pub struct BackupVault {
+    pub user_id: Uuid,
+    /// Argon2 hash of the access key the client derives from the passphrase.
+    pub access_key_hash: String,
+    /// The secret, AES-256-GCM sealed under a key derived from the access key.
+    pub sealed_secret: Option<String>,
+    /// Whether the hash and the seal are over the peppered access key. Vaults created before
+    /// the pepper use the client's access key directly and are upgraded on their next unlock.
+    pub peppered: bool,
+    /// Plaintext secret of a vault created before secrets were sealed; sealed and cleared on
+    /// its next unlock.
+    pub legacy_secret: Option<String>,
+    pub failed_attempts: i32,
+    pub max_attempts: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Assigned by the repository, increasing per user.
+    pub version: i32,
+    pub object_key: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Key that seals a vault secret, bound to the user so sealed secrets cannot be swapped.
+fn sealing_key(user_id: Uuid, access_key: &str) -> aead::LessSafeKey {
+    let okm_key = hkdf::Salt::new(hkdf::HKDF_SHA256, user_id.as_bytes())
+        .extract(access_key.as_bytes())
+        .expand(&[b"backup-vault-secret"], &aead::AES_256_GCM)
+        .map(aead::UnboundKey::from)
+        .expect("AES-256 key length is a valid HKDF output length");
+    aead::LessSafeKey::new(okm_key)
+}
+
+impl BackupVault {
+    /// Draws a fresh 256-bit random vault secret.
+    pub fn generate_secret() -> String {
+        let mut bytes = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .expect("system random number generator failed");
+        BASE64.encode(bytes)
+    }
+
+    /// Mixes the server pepper into the access key the client sent. The result is what gets
+    /// hashed and seals the secret.
+    pub fn pepper_access_key(pepper: &hmac::Key, user_id: Uuid, access_key: &str) -> String {
+        let mut context = hmac::Context::with_key(pepper);
+        context.update(user_id.as_bytes());
+        context.update(access_key.as_bytes());
+        BASE64.encode(context.sign())
+    }
+
+    /// Creates a vault holding `secret`, sealed under the peppered `access_key`.
+    pub fn new(user_id: Uuid, access_key_hash: String, access_key: &str, secret: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            user_id,
+            access_key_hash,
+            sealed_secret: Some(Self::seal_secret(user_id, access_key, secret)),
+            peppered: true,
+            legacy_secret: None,
+            failed_attempts: 0,
+            max_attempts: MAX_UNLOCK_ATTEMPTS,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn remaining_attempts(&self) -> i32 {
+        (self.max_attempts - self.failed_attempts).max(0)
+    }
+
+    pub fn seal_secret(user_id: Uuid, access_key: &str, secret: &str) -> String {
+        let mut nonce = [0u8; aead::NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .expect("system random number generator failed");
+
+        let mut sealed = secret.as_bytes().to_vec();
+        sealing_key(user_id, access_key)
+            .seal_in_place_append_tag(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::from(user_id.as_bytes()),
+                &mut sealed,
+            )
+            .expect("sealing a vault secret cannot exceed AES-GCM limits");
+
+        let mut stored = nonce.to_vec();
+        stored.extend_from_slice(&sealed);
+        BASE64.encode(stored)
+    }
+
+    /// The secret, if `access_key` opens it. Legacy vaults return their plaintext secret.
+    pub fn open_secret(&self, access_key: &str) -> Option<String> {
+        let Some(sealed_secret) = &self.sealed_secret else {
+            return self.legacy_secret.clone();
+        };
+
+        let stored = BASE64.decode(sealed_secret).ok()?;
+        if stored.len() < aead::NONCE_LEN {
+            return None;
+        }
+        let (nonce, sealed) = stored.split_at(aead::NONCE_LEN);
+        let nonce = aead::Nonce::try_assume_unique_for_key(nonce).ok()?;
+
+        let mut sealed = sealed.to_vec();
+        let secret = sealing_key(self.user_id, access_key)
+            .open_in_place(nonce, aead::Aad::from(self.user_id.as_bytes()), &mut sealed)
+            .ok()?;
+        String::from_utf8(secret.to_vec()).ok()
+    }
+}
+
+impl Backup {
+    pub fn new(user_id: Uuid, size_bytes: i64) -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            id,
+            user_id,
+            version: 0,
+            object_key: format!("backups/{}/{}", user_id, id),
+            size_bytes,
+            created_at: Utc::now(),
+            completed_at: None,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed_at.is_some()
+    }
+}
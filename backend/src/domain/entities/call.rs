@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How long a call rings before it is marked as missed.
+pub const RING_TIMEOUT_SECONDS: i64 = 45;
+/// An accepted call ends once one of its devices has not been heard from for this long.
+pub const CALL_LIVENESS_TIMEOUT_SECONDS: i64 = 120;
+
+#[derive(Debug, Clone)]
+pub struct Call {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+    pub caller_id: Uuid,
+    pub callee_id: Uuid,
+    pub is_video: bool,
+    pub status: CallStatus,
+    /// Device of the callee that picked up; the callee's other devices stop ringing.
+    pub answered_device_id: Option<String>,
+    pub ring_expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub answered_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CallStatus {
+    Ringing,
+    Accepted,
+    Rejected,
+    Busy,
+    Missed,
+    Ended,
+}
+
+impl CallStatus {
+    /// Ringing and accepted calls keep both parties busy.
+    pub fn is_active(self) -> bool {
+        matches!(self, CallStatus::Ringing | CallStatus::Accepted)
+    }
+
+    pub fn can_transition_to(self, next: CallStatus) -> bool {
+        match self {
+            CallStatus::Ringing => matches!(
+                next,
+                CallStatus::Accepted | CallStatus::Rejected | CallStatus::Missed
+            ),
+            CallStatus::Accepted => next == CallStatus::Ended,
+            _ => false,
+        }
+    }
+}
+
+impl Call {
+    pub fn new(conversation_id: Uuid, caller_id: Uuid, callee_id: Uuid, is_video: bool) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            conversation_id,
+            caller_id,
+            callee_id,
+            is_video,
+            status: CallStatus::Ringing,
+            answered_device_id: None,
+            ring_expires_at: now + chrono::Duration::seconds(RING_TIMEOUT_SECONDS),
+            created_at: now,
+            answered_at: None,
+            ended_at: None,
+        }
+    }
+
+    /// A call placed to someone who is already in a call. It never rings.
+    pub fn busy(conversation_id: Uuid, caller_id: Uuid, callee_id: Uuid, is_video: bool) -> Self {
+        let mut call = Self::new(conversation_id, caller_id, callee_id, is_video);
+        call.status = CallStatus::Busy;
+        call.ring_expires_at = call.created_at;
+        call.ended_at = Some(call.created_at);
+        call
+    }
+
+    pub fn is_party(&self, user_id: Uuid) -> bool {
+        self.caller_id == user_id || self.callee_id == user_id
+    }
+
+    /// The other side of the call from `user_id`'s point of view.
+    pub fn peer_of(&self, user_id: Uuid) -> Uuid {
+        if self.caller_id == user_id {
+            self.callee_id
+        } else {
+            self.caller_id
+        }
+    }
+
+    /// Talk time of an answered call that has ended.
+    pub fn duration_seconds(&self) -> Option<i64> {
+        match (self.answered_at, self.ended_at) {
+            (Some(answered_at), Some(ended_at)) => Some((ended_at - answered_at).num_seconds()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATUSES: [CallStatus; 6] = [
+        CallStatus::Ringing,
+        CallStatus::Accepted,
+        CallStatus::Rejected,
+        CallStatus::Busy,
+        CallStatus::Missed,
+        CallStatus::Ended,
+    ];
+
+    #[test]
+    fn allows_only_forward_transitions() {
+        let allowed = [
+            (CallStatus::Ringing, CallStatus::Accepted),
+            (CallStatus::Ringing, CallStatus::Rejected),
+            (CallStatus::Ringing, CallStatus::Missed),
+            (CallStatus::Accepted, CallStatus::Ended),
+        ];
+
+        for from in STATUSES {
+            for to in STATUSES {
+                assert_eq!(from.can_transition_to(to), allowed.contains(&(from, to)), "{:?} -> {:?}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn finished_calls_cannot_transition() {
+        for from in [CallStatus::Rejected, CallStatus::Busy, CallStatus::Missed, CallStatus::Ended] {
+            assert!(!from.is_active());
+            assert!(STATUSES.iter().all(|&to| !from.can_transition_to(to)), "{:?}", from);
+        }
+    }
+}
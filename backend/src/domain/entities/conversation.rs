@@ -1,7 +1,13 @@
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
+use super::{GeoPoint, LOCATION_FUZZ_METERS};
+
+/// Largest radius a location-bound group may cover.
+pub const MAX_GEOFENCE_RADIUS_METERS: i32 = 50_000;
+
 #[derive(Debug, Clone)]
 pub struct Conversation {
     pub id: Uuid,
@@ -9,14 +15,55 @@ pub struct Conversation {
     pub name: Option<String>,
     pub avatar_url: Option<String>,
     pub settings: JsonValue,
+    /// Set for location-bound groups.
+    pub geofence: Option<Geofence>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Area a location-bound group is anchored to. Users within it can discover and join the group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geofence {
+    pub center: GeoPoint,
+    pub radius_meters: i32,
+    /// Remove members automatically once a location update puts them outside the area.
+    pub remove_on_exit: bool,
+}
+
+impl Geofence {
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        self.center.distance_km(point) * 1000.0 <= self.radius_meters as f64
+    }
+
+    /// Like `contains`, for a stored location that is only accurate to its grid cell.
+    pub fn may_contain(&self, stored: &GeoPoint) -> bool {
+        self.center.distance_km(stored) * 1000.0 <= self.radius_meters as f64 + LOCATION_FUZZ_METERS
+    }
+}
+
+/// A location-bound group around the searcher, with its distance in whole kilometers.
+#[derive(Debug, Clone)]
+pub struct NearbyGroup {
+    pub conversation: Conversation,
+    pub member_count: i64,
+    pub distance_km: f64,
+}
+
+/// Conversation-wide default for disappearing messages, stored under
+/// `settings.disappearing_messages`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DisappearingMessages {
+    pub timer_seconds: i64,
+    /// Start the countdown when a recipient reads the message instead of when it is sent.
+    pub start_on_read: bool,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConversationType {
     Private,
     Group,
+    /// One-to-many broadcast: only admins post, subscribers read.
+    Channel,
 }
 
 impl Conversation {
@@ -28,6 +75,7 @@ impl Conversation {
             name: None,
             avatar_url: None,
             settings: serde_json::json!({}),
+            geofence: None,
             created_at: now,
             updated_at: now,
         }
@@ -41,8 +89,127 @@ impl Conversation {
             name: Some(name),
             avatar_url: None,
             settings: serde_json::json!({}),
+            geofence: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn new_local_group(name: String, geofence: Geofence) -> Self {
+        Self {
+            geofence: Some(geofence),
+            ..Self::new_group(name)
+        }
+    }
+
+    pub fn new_channel(name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            conversation_type: ConversationType::Channel,
+            name: Some(name),
+            avatar_url: None,
+            settings: serde_json::json!({}),
+            geofence: None,
             created_at: now,
             updated_at: now,
         }
     }
+
+    pub fn disappearing_messages(&self) -> Option<DisappearingMessages> {
+        self.settings
+            .get("disappearing_messages")
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    pub fn is_group(&self) -> bool {
+        self.conversation_type == ConversationType::Group
+    }
+
+    pub fn is_channel(&self) -> bool {
+        self.conversation_type == ConversationType::Channel
+    }
+
+    /// Only owners and admins may post: always true for channels, a setting for groups.
+    pub fn only_admins_can_post(&self) -> bool {
+        self.is_channel() || self.settings
+            .get("only_admins_can_post")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+    }
+
+    pub fn set_only_admins_can_post(&mut self, enabled: bool) {
+        if !self.settings.is_object() {
+            self.settings = serde_json::json!({});
+        }
+        self.settings["only_admins_can_post"] = serde_json::json!(enabled);
+    }
+
+    /// Whether messages may be forwarded out of this conversation.
+    pub fn allows_forwarding(&self) -> bool {
+        !self.settings
+            .get("forwarding_disabled")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+    }
+
+    pub fn set_forwarding_enabled(&mut self, enabled: bool) {
+        if !self.settings.is_object() {
+            self.settings = serde_json::json!({});
+        }
+        self.settings["forwarding_disabled"] = serde_json::json!(!enabled);
+    }
+
+    pub fn set_disappearing_messages(&mut self, setting: Option<DisappearingMessages>) {
+        if !self.settings.is_object() {
+            self.settings = serde_json::json!({});
+        }
+        match setting {
+            Some(setting) => {
+                self.settings["disappearing_messages"] = serde_json::to_value(setting).unwrap_or_default();
+            }
+            None => {
+                if let Some(settings) = self.settings.as_object_mut() {
+                    settings.remove("disappearing_messages");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Meters per degree of latitude on the sphere `distance_km` assumes.
+    const METERS_PER_DEGREE: f64 = 6_371_000.0 * std::f64::consts::PI / 180.0;
+
+    fn north_of(point: GeoPoint, meters: f64) -> GeoPoint {
+        GeoPoint::new(point.latitude + meters / METERS_PER_DEGREE, point.longitude)
+    }
+
+    fn geofence() -> Geofence {
+        Geofence { center: GeoPoint::new(48.8566, 2.3522), radius_meters: 1_000, remove_on_exit: false }
+    }
+
+    #[test]
+    fn may_contain_allows_for_fuzzing() {
+        let geofence = geofence();
+        let just_outside = north_of(geofence.center, 1_000.0 + LOCATION_FUZZ_METERS - 1.0);
+
+        assert!(!geofence.contains(&just_outside));
+        assert!(geofence.may_contain(&just_outside));
+        assert!(!geofence.may_contain(&north_of(geofence.center, 1_000.0 + LOCATION_FUZZ_METERS + 1.0)));
+    }
+
+    #[test]
+    fn may_contain_fuzzed_points_inside() {
+        let geofence = geofence();
+
+        for meters in [0.0, 500.0, 900.0, 999.0] {
+            let point = north_of(geofence.center, meters);
+            assert!(geofence.contains(&point));
+            assert!(geofence.may_contain(&point.fuzzed()), "{} meters", meters);
+        }
+    }
 }
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Side length of the grid cells locations are snapped to before they are stored.
+pub const LOCATION_GRID_METERS: f64 = 500.0;
+/// Farthest a stored location can be from the position it was snapped from.
+pub const LOCATION_FUZZ_METERS: f64 = LOCATION_GRID_METERS * std::f64::consts::FRAC_1_SQRT_2;
+
+const METERS_PER_DEGREE: f64 = 111_320.0;
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self { latitude, longitude }
+    }
+
+    /// Snaps the point to the center of its grid cell. Only the cell is stored or used for
+    /// searches, so the exact position the client sent is never kept or shown to others.
+    pub fn fuzzed(&self) -> Self {
+        let lat_step = LOCATION_GRID_METERS / METERS_PER_DEGREE;
+        let latitude = ((self.latitude / lat_step).floor() * lat_step + lat_step / 2.0).clamp(-90.0, 90.0);
+
+        // Keep cells roughly square; near the poles they would otherwise shrink to nothing
+        let lon_step = LOCATION_GRID_METERS / (METERS_PER_DEGREE * latitude.to_radians().cos().max(0.01));
+        let longitude = (((self.longitude + 180.0) / lon_step).floor() * lon_step + lon_step / 2.0 - 180.0)
+            .clamp(-180.0, 180.0);
+
+        Self { latitude, longitude }
+    }
+
+    /// Great-circle distance in kilometers (haversine).
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let d_lat = (other.latitude - self.latitude).to_radians();
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2)
+            + self.latitude.to_radians().cos() * other.latitude.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+/// A page of a nearby search, ordered by distance and then user id.
+#[derive(Debug, Clone)]
+pub struct NearbySearch {
+    pub requester_id: Uuid,
+    pub origin: GeoPoint,
+    pub radius_km: f64,
+    /// Locations not refreshed since then count as expired.
+    pub updated_after: DateTime<Utc>,
+    pub verified_only: bool,
+    pub online_only: bool,
+    /// `(distance_km, user_id)` of the last user on the previous page.
+    pub after: Option<(f64, Uuid)>,
+    pub limit: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_cell_center(point: GeoPoint) {
+        let fuzzed = point.fuzzed();
+
+        assert!((-90.0..=90.0).contains(&fuzzed.latitude), "{:?}", fuzzed);
+        assert!((-180.0..=180.0).contains(&fuzzed.longitude), "{:?}", fuzzed);
+        assert!(point.distance_km(&fuzzed) * 1000.0 <= LOCATION_FUZZ_METERS, "{:?}", point);
+        assert_eq!(fuzzed.fuzzed(), fuzzed);
+    }
+
+    #[test]
+    fn snaps_points_in_a_cell_to_its_center() {
+        let point = GeoPoint::new(52.52, 13.405);
+        let neighbor = GeoPoint::new(point.latitude + 0.0001, point.longitude + 0.0001);
+
+        assert_eq!(point.fuzzed(), neighbor.fuzzed());
+        assert_ne!(point.fuzzed(), point);
+        assert_cell_center(point);
+    }
+
+    #[test]
+    fn snaps_points_near_the_poles() {
+        for latitude in [90.0, 89.9999, 89.998, 89.9, -89.9, -89.9999, -90.0] {
+            for longitude in [-180.0, 0.0, 10.0, 179.99, 180.0] {
+                assert_cell_center(GeoPoint::new(latitude, longitude));
+            }
+        }
+    }
+
+    #[test]
+    fn snaps_points_at_the_antimeridian() {
+        for latitude in [-60.0, 0.0, 10.0, 81.09] {
+            for longitude in [-180.0, -179.9999, 179.9999, 180.0] {
+                assert_cell_center(GeoPoint::new(latitude, longitude));
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// A multi-party call in a group, hosted in its own SFU room.
+#[derive(Debug, Clone)]
+pub struct GroupCall {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+    pub room_name: String,
+    pub started_by: Uuid,
+    pub is_video: bool,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl GroupCall {
+    pub fn new(conversation_id: Uuid, started_by: Uuid, is_video: bool) -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            id,
+            conversation_id,
+            room_name: format!("group-{}-{}", conversation_id.simple(), id.simple()),
+            started_by,
+            is_video,
+            started_at: Utc::now(),
+            ended_at: None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.ended_at.is_none()
+    }
+}
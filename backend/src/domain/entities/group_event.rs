@@ -0,0 +1,29 @@
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use super::ParticipantRole;
+
+/// Membership and group changes, stored as the content of `MessageType::System`
+/// messages so every client can render e.g. "X added Y".
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum GroupEvent {
+    GroupCreated { actor_id: Uuid, name: String },
+    MemberAdded { actor_id: Uuid, user_id: Uuid },
+    MemberRemoved { actor_id: Uuid, user_id: Uuid },
+    MemberLeft { user_id: Uuid },
+    /// Removed automatically after leaving the area of a location-bound group.
+    MemberLeftArea { user_id: Uuid },
+    /// Joined through an invite link; `approved_by` is set when the invite required approval.
+    MemberJoined { user_id: Uuid, approved_by: Option<Uuid> },
+    RoleChanged { actor_id: Uuid, user_id: Uuid, role: ParticipantRole },
+    OwnershipTransferred { actor_id: Uuid, user_id: Uuid },
+    GroupRenamed { actor_id: Uuid, name: String },
+    AvatarChanged { actor_id: Uuid, avatar_url: String },
+    SettingsChanged { actor_id: Uuid, settings: JsonValue },
+    MessagePinned { actor_id: Uuid, message_id: Uuid },
+    MessageUnpinned { actor_id: Uuid, message_id: Uuid },
+    GroupCallStarted { actor_id: Uuid, call_id: Uuid, is_video: bool },
+    GroupCallEnded { call_id: Uuid, duration_seconds: i64 },
+}
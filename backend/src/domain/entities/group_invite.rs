@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct GroupInvite {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+    pub token: String,
+    pub created_by: Option<Uuid>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+    pub requires_approval: bool,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct JoinRequest {
+    pub conversation_id: Uuid,
+    pub user_id: Uuid,
+    pub invite_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GroupInvite {
+    pub fn new(
+        conversation_id: Uuid,
+        created_by: Uuid,
+        expires_at: Option<DateTime<Utc>>,
+        max_uses: Option<i32>,
+        requires_approval: bool,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            conversation_id,
+            // 122 random bits, URL-safe
+            token: Uuid::new_v4().simple().to_string(),
+            created_by: Some(created_by),
+            expires_at,
+            max_uses,
+            use_count: 0,
+            requires_approval,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn is_usable(&self) -> bool {
+        self.revoked_at.is_none()
+            && self.expires_at.map_or(true, |expires_at| Utc::now() < expires_at)
+            && self.max_uses.map_or(true, |max_uses| self.use_count < max_uses)
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// One entry in a user's identity key history. Entries form a hash chain per user, so a
+/// client that has seen an earlier head can detect a rewritten history.
+#[derive(Debug, Clone)]
+pub struct KeyLogEntry {
+    pub seq: i64,
+    pub user_id: Uuid,
+    /// None for the account-level key, otherwise the device that published the key.
+    pub device_id: Option<String>,
+    pub identity_key: String,
+    pub prev_hash: Option<String>,
+    pub entry_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl KeyLogEntry {
+    /// Creates an entry chained onto `previous`, the current head of the user's log.
+    pub fn new(user_id: Uuid, device_id: Option<String>, identity_key: String, previous: Option<&KeyLogEntry>) -> Self {
+        // Postgres keeps microseconds; truncate so the hash can be recomputed from stored rows
+        let now = Utc::now();
+        let created_at = DateTime::from_timestamp_micros(now.timestamp_micros()).unwrap_or(now);
+
+        let mut entry = Self {
+            seq: 0,
+            user_id,
+            device_id,
+            identity_key,
+            prev_hash: previous.map(|p| p.entry_hash.clone()),
+            entry_hash: String::new(),
+            created_at,
+        };
+        entry.entry_hash = entry.compute_hash();
+        entry
+    }
+
+    /// Hex SHA-256 over `prev_hash`, `user_id`, `device_id`, `identity_key` and `created_at`
+    /// (Unix microseconds), joined by newlines. Missing values hash as empty strings.
+    pub fn compute_hash(&self) -> String {
+        let input = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            self.prev_hash.as_deref().unwrap_or(""),
+            self.user_id,
+            self.device_id.as_deref().unwrap_or(""),
+            self.identity_key,
+            self.created_at.timestamp_micros(),
+        );
+        hex::encode(Sha256::digest(input.as_bytes()))
+    }
+}
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Durations a live location can be shared for.
+pub const LIVE_LOCATION_DURATIONS_MINUTES: [i64; 3] = [15, 60, 8 * 60];
+/// Updates arriving faster than this are dropped.
+pub const LIVE_LOCATION_MIN_UPDATE_INTERVAL_SECONDS: i64 = 5;
+/// Recorded track points are deleted once they are this old.
+pub const LIVE_LOCATION_TRACK_RETENTION_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy)]
+pub struct LocationPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// A user sharing their live location in a conversation until `expires_at`.
+#[derive(Debug, Clone)]
+pub struct LiveLocationSession {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+    pub user_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub last_point: Option<LocationPoint>,
+}
+
+impl LiveLocationSession {
+    pub fn new(conversation_id: Uuid, user_id: Uuid, duration_minutes: i64) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            conversation_id,
+            user_id,
+            started_at: now,
+            expires_at: now + Duration::minutes(duration_minutes),
+            stopped_at: None,
+            last_point: None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.stopped_at.is_none() && self.expires_at > Utc::now()
+    }
+}
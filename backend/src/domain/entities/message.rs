@@ -1,9 +1,12 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use super::{Call, GroupEvent};
+
 #[derive(Debug, Clone)]
 pub struct Message {
     pub id: Uuid,
+    pub client_message_id: Option<String>,
     pub conversation_id: Uuid,
     pub sender_id: Option<Uuid>,
     pub content: String,
@@ -11,10 +14,26 @@ pub struct Message {
     pub is_encrypted: bool,
     pub reply_to_id: Option<Uuid>,
     pub self_destruct_at: Option<DateTime<Utc>>,
+    /// Lifetime of a self-destructing message, kept so a timer that starts on read can be armed later.
+    pub self_destruct_seconds: Option<i64>,
+    pub self_destruct_on_read: bool,
+    pub forwarded_from_id: Option<Uuid>,
+    /// Number of forwarding hops between this message and the original.
+    pub forward_count: i32,
+    /// Sender key epoch the content of a group message was encrypted under.
+    pub sender_key_epoch: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub is_deleted: bool,
 }
 
+/// Reply activity on a message, shown in history so clients can render "3 replies".
+#[derive(Debug, Clone, Default)]
+pub struct ThreadSummary {
+    pub reply_count: i64,
+    pub last_reply_at: Option<DateTime<Utc>>,
+    pub last_replier_id: Option<Uuid>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum MessageType {
     Text,
@@ -35,6 +54,7 @@ impl Message {
     ) -> Self {
         Self {
             id: Uuid::new_v4(),
+            client_message_id: None,
             conversation_id,
             sender_id: Some(sender_id),
             content,
@@ -42,16 +62,77 @@ impl Message {
             is_encrypted: true,
             reply_to_id: None,
             self_destruct_at: None,
-            created_at: Utc::now(),
+            self_destruct_seconds: None,
+            self_destruct_on_read: false,
+            forwarded_from_id: None,
+            forward_count: 0,
+            sender_key_epoch: None,
+            created_at: now(),
             is_deleted: false,
         }
     }
 
+    /// Server-generated notice about a group change. System messages are not end-to-end encrypted.
+    pub fn system(conversation_id: Uuid, actor_id: Uuid, event: &GroupEvent) -> Self {
+        let mut message = Self::new(
+            conversation_id,
+            actor_id,
+            serde_json::to_string(event).unwrap_or_default(),
+            MessageType::System,
+        );
+        message.is_encrypted = false;
+        message
+    }
+
+    /// Server-generated record of a finished call, so missed and rejected calls show up in the
+    /// chat. Sent on behalf of the caller, who cannot edit or delete it, and not end-to-end
+    /// encrypted.
+    pub fn call_signal(call: &Call) -> Self {
+        let content = serde_json::json!({
+            "call_id": call.id,
+            "status": call.status,
+            "is_video": call.is_video,
+            "duration_seconds": call.duration_seconds(),
+        });
+        let mut message = Self::new(
+            call.conversation_id,
+            call.caller_id,
+            content.to_string(),
+            MessageType::CallSignal,
+        );
+        message.is_encrypted = false;
+        message
+    }
+
+    /// Marks this message as a forward of `origin`.
+    pub fn forwarded_from(mut self, origin: &Message) -> Self {
+        self.forwarded_from_id = Some(origin.id);
+        self.forward_count = origin.forward_count + 1;
+        self
+    }
+
     pub fn with_self_destruct(mut self, duration_seconds: i64) -> Self {
-        self.self_destruct_at = Some(Utc::now() + chrono::Duration::seconds(duration_seconds));
+        self.self_destruct_at = Some(now() + chrono::Duration::seconds(duration_seconds));
+        self.self_destruct_seconds = Some(duration_seconds);
         self
     }
 
+    /// Like `with_self_destruct`, but the countdown only starts once a recipient reads the message.
+    pub fn with_self_destruct_on_read(mut self, duration_seconds: i64) -> Self {
+        self.self_destruct_seconds = Some(duration_seconds);
+        self.self_destruct_on_read = true;
+        self
+    }
+
+    /// Deadline to arm when `reader_id` reads this message, if that read starts its countdown.
+    pub fn read_destruct_deadline(&self, reader_id: Uuid) -> Option<DateTime<Utc>> {
+        if !self.self_destruct_on_read || self.self_destruct_at.is_some() || self.sender_id == Some(reader_id) {
+            return None;
+        }
+        self.self_destruct_seconds
+            .map(|seconds| Utc::now() + chrono::Duration::seconds(seconds))
+    }
+
     pub fn is_expired(&self) -> bool {
         if let Some(destruct_at) = self.self_destruct_at {
             Utc::now() > destruct_at
@@ -59,4 +140,17 @@ impl Message {
             false
         }
     }
+
+    /// Messages the server posts on someone's behalf; they are part of the conversation's
+    /// record and cannot be edited or deleted by the user they are attributed to.
+    pub fn is_server_generated(&self) -> bool {
+        matches!(self.message_type, MessageType::System | MessageType::CallSignal)
+    }
+}
+
+/// Current time at the microsecond precision Postgres keeps, so the sync payload built from a new
+/// message matches the row it is stored as.
+fn now() -> DateTime<Utc> {
+    let now = Utc::now();
+    DateTime::from_timestamp_micros(now.timestamp_micros()).unwrap_or(now)
 }
@@ -2,8 +2,42 @@ pub mod user;
 pub mod message;
 pub mod conversation;
 pub mod kyc_request;
+pub mod sync_event;
+pub mod participant;
+pub mod group_event;
+pub mod group_invite;
+pub mod prekey;
+pub mod key_log;
+pub mod public_key;
+pub mod sender_key;
+pub mod backup;
+pub mod call;
+pub mod group_call;
+pub mod geo_point;
+pub mod live_location;
+pub mod subscription;
 
 pub use user::{User, SubscriptionTier};
-pub use message::{Message, MessageType};
-pub use conversation::{Conversation, ConversationType};
+pub use message::{Message, MessageType, ThreadSummary};
+pub use conversation::{Conversation, ConversationType, DisappearingMessages, Geofence, NearbyGroup, MAX_GEOFENCE_RADIUS_METERS};
 pub use kyc_request::{KycRequest, KycStatus};
+pub use sync_event::{SyncEvent, SyncEventType};
+pub use participant::{Participant, ParticipantRole};
+pub use group_event::GroupEvent;
+pub use group_invite::{GroupInvite, JoinRequest};
+pub use prekey::{DeviceKeys, SignedPreKey, OneTimePreKey, PreKeyBundle};
+pub use key_log::KeyLogEntry;
+pub use public_key::{PublicKey, Signature};
+pub use sender_key::{SenderKeyEnvelope, SENDER_KEY_RETENTION_DAYS};
+pub use backup::{Backup, BackupVault};
+pub use call::{Call, CallStatus, CALL_LIVENESS_TIMEOUT_SECONDS};
+pub use group_call::GroupCall;
+pub use geo_point::{GeoPoint, NearbySearch, LOCATION_FUZZ_METERS};
+pub use subscription::{
+    Subscription, SubscriptionCheckout, SubscriptionStatus, SUBSCRIPTION_CHECKOUT_TIMEOUT_SECONDS,
+    SUBSCRIPTION_GRACE_PERIOD_HOURS,
+};
+pub use live_location::{
+    LiveLocationSession, LocationPoint, LIVE_LOCATION_DURATIONS_MINUTES, LIVE_LOCATION_MIN_UPDATE_INTERVAL_SECONDS,
+    LIVE_LOCATION_TRACK_RETENTION_DAYS,
+};
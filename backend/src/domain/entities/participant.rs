@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct Participant {
+    pub conversation_id: Uuid,
+    pub user_id: Uuid,
+    pub role: ParticipantRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ParticipantRole {
+    Member,
+    Admin,
+    Owner,
+}
+
+impl Participant {
+    pub fn new(conversation_id: Uuid, user_id: Uuid, role: ParticipantRole) -> Self {
+        Self {
+            conversation_id,
+            user_id,
+            role,
+            joined_at: Utc::now(),
+        }
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.role >= ParticipantRole::Admin
+    }
+
+    pub fn is_owner(&self) -> bool {
+        self.role == ParticipantRole::Owner
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct SignedPreKey {
+    pub key_id: i32,
+    pub public_key: String,
+    /// Signature over `public_key` made with the device's identity key.
+    pub signature: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct OneTimePreKey {
+    pub key_id: i32,
+    pub public_key: String,
+}
+
+/// Long-lived keys a device publishes so others can start X3DH sessions with it.
+#[derive(Debug, Clone)]
+pub struct DeviceKeys {
+    pub user_id: Uuid,
+    pub device_id: String,
+    pub identity_key: String,
+    pub signed_prekey: SignedPreKey,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Everything needed to open a session with one device. `one_time_prekey` is None once the
+/// device has run out, in which case X3DH falls back to the signed prekey alone.
+#[derive(Debug, Clone)]
+pub struct PreKeyBundle {
+    pub user_id: Uuid,
+    pub device_id: String,
+    pub identity_key: String,
+    pub signed_prekey: SignedPreKey,
+    pub one_time_prekey: Option<OneTimePreKey>,
+}
+
+impl DeviceKeys {
+    pub fn new(user_id: Uuid, device_id: String, identity_key: String, signed_prekey: SignedPreKey) -> Self {
+        let now = Utc::now();
+        Self {
+            user_id,
+            device_id,
+            identity_key,
+            signed_prekey,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use base64::{
+    engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD as BASE64_URL},
+    Engine,
+};
+use num_bigint::BigUint;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use uuid::Uuid;
+
+/// Domain tag of key rotation proofs, so they cannot pass as signatures made for anything else.
+pub const KEY_ROTATION_PROOF_TAG: &str = "secure-chat/identity-key-rotation/v1";
+
+/// Raw 32-byte X25519 or Ed25519 public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey([u8; 32]);
+
+impl PublicKey {
+    /// Accepts standard base64, unpadded URL-safe base64 or hex.
+    pub fn parse(value: &str) -> Option<Self> {
+        decode_fixed(value).map(Self)
+    }
+
+    /// Stores keys in a single encoding so equal keys always compare equal. Values that are not
+    /// valid keys are returned unchanged.
+    pub fn canonicalize(value: String) -> String {
+        match Self::parse(&value) {
+            Some(key) => key.to_base64(),
+            None => value,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    pub fn to_base64(&self) -> String {
+        BASE64.encode(self.0)
+    }
+
+    /// Message the current key signs to approve replacing it with this one: the tag, user id,
+    /// device id (empty for the account key), this key and `nonce`, each prefixed with its
+    /// length as a big-endian u32. The nonce is the `entry_hash` of the head of the user's key
+    /// history (empty if there is none), so a proof stops working once any key changes.
+    pub fn rotation_message(&self, user_id: Uuid, device_id: Option<&str>, nonce: &str) -> Vec<u8> {
+        let user_id = user_id.to_string();
+        let fields: [&[u8]; 5] = [
+            KEY_ROTATION_PROOF_TAG.as_bytes(),
+            user_id.as_bytes(),
+            device_id.unwrap_or("").as_bytes(),
+            &self.0,
+            nonce.as_bytes(),
+        ];
+
+        let mut message = Vec::new();
+        for field in fields {
+            message.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            message.extend_from_slice(field);
+        }
+        message
+    }
+
+    /// Every encoding `parse` accepts for this key, for matching values stored before keys
+    /// were canonicalized.
+    pub fn encodings(&self) -> Vec<String> {
+        vec![
+            self.to_base64(),
+            BASE64_URL.encode(self.0),
+            hex::encode(self.0),
+            hex::encode_upper(self.0),
+        ]
+    }
+
+    /// Checks an XEdDSA signature made with this X25519 key, as identity keys sign in the
+    /// Signal protocol. XEdDSA signatures are Ed25519 signatures under the key's Edwards form.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        let Some(edwards) = self.edwards_form() else {
+            return false;
+        };
+        UnparsedPublicKey::new(&ED25519, &edwards)
+            .verify(message, &signature.0)
+            .is_ok()
+    }
+
+    /// The Edwards point XEdDSA pairs with this Montgomery u-coordinate: y = (u - 1) / (u + 1)
+    /// mod 2^255 - 19, with the sign bit zero. None if u is not a reduced field element.
+    fn edwards_form(&self) -> Option<[u8; 32]> {
+        let p = (BigUint::from(1u8) << 255u32) - 19u32;
+        let u = BigUint::from_bytes_le(&self.0);
+        if u >= p {
+            return None;
+        }
+
+        let y = (&u + &p - 1u32) * (&u + 1u32).modpow(&(&p - 2u32), &p) % &p;
+        let mut edwards = [0u8; 32];
+        let y = y.to_bytes_le();
+        edwards[..y.len()].copy_from_slice(&y);
+        Some(edwards)
+    }
+}
+
+/// Raw 64-byte XEdDSA signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature([u8; 64]);
+
+impl Signature {
+    /// Accepts the same encodings as `PublicKey::parse`.
+    pub fn parse(value: &str) -> Option<Self> {
+        decode_fixed(value).map(Self)
+    }
+}
+
+fn decode_fixed<const N: usize>(value: &str) -> Option<[u8; N]> {
+    let bytes = if value.len() == N * 2 && value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        hex::decode(value).ok()?
+    } else {
+        BASE64.decode(value).or_else(|_| BASE64_URL.decode(value)).ok()?
+    };
+    bytes.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    use super::*;
+
+    /// A key pair whose Edwards public key has the sign bit XEdDSA assumes, with the
+    /// Montgomery form of that key: u = (1 + y) / (1 - y).
+    fn xeddsa_key_pair() -> (Ed25519KeyPair, PublicKey) {
+        let p = (BigUint::from(1u8) << 255u32) - 19u32;
+        (0u8..)
+            .map(|seed| Ed25519KeyPair::from_seed_unchecked(&[seed; 32]).unwrap())
+            .find(|pair| pair.public_key().as_ref()[31] & 0x80 == 0)
+            .map(|pair| {
+                let y = BigUint::from_bytes_le(pair.public_key().as_ref());
+                let u = (&y + 1u32) * (&p + 1u32 - &y).modpow(&(&p - 2u32), &p) % &p;
+                let mut bytes = [0u8; 32];
+                let u = u.to_bytes_le();
+                bytes[..u.len()].copy_from_slice(&u);
+                (pair, PublicKey(bytes))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn verifies_xeddsa_signature() {
+        let (pair, key) = xeddsa_key_pair();
+        let signature = Signature(pair.sign(b"message").as_ref().try_into().unwrap());
+
+        assert!(key.verify(b"message", &signature));
+        assert!(!key.verify(b"other message", &signature));
+    }
+
+    #[test]
+    fn rejects_unreduced_key() {
+        let (pair, _) = xeddsa_key_pair();
+        let signature = Signature(pair.sign(b"message").as_ref().try_into().unwrap());
+
+        assert!(!PublicKey([0xff; 32]).verify(b"message", &signature));
+    }
+
+    #[test]
+    fn parses_every_encoding() {
+        let key = PublicKey((0u8..32).collect::<Vec<_>>().try_into().unwrap());
+
+        for encoding in key.encodings() {
+            assert_eq!(PublicKey::parse(&encoding), Some(key), "{}", encoding);
+        }
+        assert_eq!(PublicKey::canonicalize(hex::encode(key.0)), key.to_base64());
+    }
+
+    #[test]
+    fn prefers_hex_for_hex_length_values() {
+        // 64 hex digits are also valid base64 for 48 bytes; they are read as hex
+        let value = "A".repeat(64);
+        assert_eq!(BASE64.decode(&value).unwrap().len(), 48);
+        assert_eq!(PublicKey::parse(&value), Some(PublicKey([0xaa; 32])));
+
+        // Base64 that happens to use only hex digits is still base64 at any other length
+        let value = "A".repeat(43);
+        assert!(value.bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_eq!(PublicKey::parse(&value), Some(PublicKey([0; 32])));
+    }
+
+    #[test]
+    fn rejects_wrong_lengths_and_invalid_values() {
+        assert_eq!(PublicKey::parse(&hex::encode([1u8; 31])), None);
+        assert_eq!(PublicKey::parse(&BASE64.encode([1u8; 33])), None);
+        assert_eq!(PublicKey::parse(&"g".repeat(64)), None);
+        assert_eq!(PublicKey::parse(""), None);
+        assert_eq!(PublicKey::canonicalize("not a key".to_string()), "not a key");
+
+        assert!(Signature::parse(&hex::encode([1u8; 64])).is_some());
+        assert!(Signature::parse(&BASE64_URL.encode([1u8; 64])).is_some());
+        assert_eq!(Signature::parse(&BASE64.encode([1u8; 32])), None);
+    }
+}
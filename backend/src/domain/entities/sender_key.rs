@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Days the envelopes of an epoch are kept after the group rotated away from it, so messages
+/// sent under the old epoch stay readable for devices that were offline.
+pub const SENDER_KEY_RETENTION_DAYS: i64 = 30;
+
+/// A sender key for one group epoch, encrypted with the pairwise session of one recipient device.
+#[derive(Debug, Clone)]
+pub struct SenderKeyEnvelope {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+    pub sender_id: Uuid,
+    pub sender_device_id: String,
+    pub recipient_id: Uuid,
+    pub recipient_device_id: String,
+    pub epoch: i32,
+    pub ciphertext: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SenderKeyEnvelope {
+    pub fn new(
+        conversation_id: Uuid,
+        sender_id: Uuid,
+        sender_device_id: String,
+        recipient_id: Uuid,
+        recipient_device_id: String,
+        epoch: i32,
+        ciphertext: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            conversation_id,
+            sender_id,
+            sender_device_id,
+            recipient_id,
+            recipient_device_id,
+            epoch,
+            ciphertext,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use super::SubscriptionTier;
+use crate::domain::services::PaymentEvent;
+
+/// Hours an active or past-due subscription keeps its tier after the paid period ended, so the
+/// renewal payment has time to settle before the user is downgraded.
+pub const SUBSCRIPTION_GRACE_PERIOD_HOURS: i64 = 24;
+/// An upgrade still in progress after this long is assumed abandoned and may be taken over.
+pub const SUBSCRIPTION_CHECKOUT_TIMEOUT_SECONDS: i64 = 10 * 60;
+
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub tier: SubscriptionTier,
+    pub status: SubscriptionStatus,
+    /// Identifier of the subscription at the payment provider.
+    pub provider_subscription_id: String,
+    pub current_period_start: DateTime<Utc>,
+    pub current_period_end: DateTime<Utc>,
+    /// Renewal was turned off; the subscription ends with the current period.
+    pub cancel_at_period_end: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An upgrade reserved before the provider is charged, so concurrent upgrades of the same user
+/// cannot both charge. Its id is the idempotency key of the provider requests, which are only
+/// replayed with the tier and payment token they were first sent with.
+#[derive(Debug, Clone)]
+pub struct SubscriptionCheckout {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub tier: SubscriptionTier,
+    /// Token of the payment method being charged. None for checkouts started before it was kept.
+    pub payment_token: Option<String>,
+    pub started_at: DateTime<Utc>,
+}
+
+impl SubscriptionCheckout {
+    pub fn new(user_id: Uuid, tier: SubscriptionTier, payment_token: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            tier,
+            payment_token: Some(payment_token.to_string()),
+            started_at: Utc::now(),
+        }
+    }
+
+    /// Whether a retry with these details replays this checkout's provider requests.
+    pub fn matches(&self, tier: SubscriptionTier, payment_token: &str) -> bool {
+        self.tier == tier && self.payment_token.as_deref() == Some(payment_token)
+    }
+
+    /// Whether the upgrade is old enough to be assumed abandoned.
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        self.started_at <= now - Duration::seconds(SUBSCRIPTION_CHECKOUT_TIMEOUT_SECONDS)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionStatus {
+    Active,
+    /// Charging for the period failed and the provider is retrying.
+    PastDue,
+    /// Ended at the provider; access lasts until `current_period_end`.
+    Canceled,
+    /// Access ended and the user was downgraded to Free.
+    Expired,
+}
+
+impl Subscription {
+    pub fn new(
+        user_id: Uuid,
+        tier: SubscriptionTier,
+        provider_subscription_id: String,
+        current_period_start: DateTime<Utc>,
+        current_period_end: DateTime<Utc>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            tier,
+            status: SubscriptionStatus::Active,
+            provider_subscription_id,
+            current_period_start,
+            current_period_end,
+            cancel_at_period_end: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Last moment the subscription grants its tier.
+    pub fn access_ends_at(&self) -> DateTime<Utc> {
+        match self.status {
+            SubscriptionStatus::Active | SubscriptionStatus::PastDue => {
+                self.current_period_end + Duration::hours(SUBSCRIPTION_GRACE_PERIOD_HOURS)
+            }
+            SubscriptionStatus::Canceled | SubscriptionStatus::Expired => self.current_period_end,
+        }
+    }
+
+    pub fn is_entitled(&self, now: DateTime<Utc>) -> bool {
+        self.status != SubscriptionStatus::Expired && now < self.access_ends_at()
+    }
+
+    /// A billing period was paid for. Out-of-order deliveries never move the period backwards.
+    pub fn renew(&mut self, period_start: DateTime<Utc>, period_end: DateTime<Utc>) {
+        if matches!(self.status, SubscriptionStatus::Canceled | SubscriptionStatus::Expired) {
+            return;
+        }
+        if period_end >= self.current_period_end {
+            self.current_period_start = period_start;
+            self.current_period_end = period_end;
+        }
+        self.status = SubscriptionStatus::Active;
+    }
+
+    pub fn mark_past_due(&mut self) {
+        if self.status == SubscriptionStatus::Active {
+            self.status = SubscriptionStatus::PastDue;
+        }
+    }
+
+    /// The subscription ended at the provider at `ended_at`, either with its period or early.
+    pub fn cancel(&mut self, ended_at: DateTime<Utc>) {
+        if self.status == SubscriptionStatus::Expired {
+            return;
+        }
+        self.status = SubscriptionStatus::Canceled;
+        self.current_period_end = self.current_period_end.min(ended_at);
+    }
+
+    /// Applies a billing change reported by the provider.
+    pub fn apply(&mut self, event: &PaymentEvent) {
+        match event {
+            PaymentEvent::Renewed { period_start, period_end, .. } => self.renew(*period_start, *period_end),
+            PaymentEvent::PaymentFailed { .. } => self.mark_past_due(),
+            PaymentEvent::Canceled { ended_at, .. } => self.cancel(*ended_at),
+        }
+    }
+}
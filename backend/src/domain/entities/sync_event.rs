@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct SyncEvent {
+    pub seq: i64,
+    pub conversation_id: Uuid,
+    pub event_type: SyncEventType,
+    pub payload: JsonValue,
+    pub created_at: DateTime<Utc>,
+    /// Members of the conversation when the event was appended, who receive it live. Empty on
+    /// events read back from the log, which are already filtered to the reader.
+    pub recipients: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncEventType {
+    MessageCreated,
+    MessageEdited,
+    MessageDeleted,
+    MessageRead,
+    MessageExpired,
+    /// A participant published a new identity key; clients should re-verify safety numbers.
+    KeyChanged,
+    /// Group membership changed; senders must distribute a new sender key for the new epoch.
+    SenderKeyRotated,
+    /// A member uploaded sender key envelopes for the current epoch.
+    SenderKeyDistributed,
+    /// Someone joined or left the group call; the payload lists who is in it.
+    GroupCallUpdated,
+    /// A participant started sharing their live location; updates follow over WebSocket.
+    LiveLocationStarted,
+    /// A live location was stopped, expired or its sharer left the conversation.
+    LiveLocationStopped,
+}
+
+impl SyncEvent {
+    /// Creates an event that has not been persisted yet; `seq` is assigned by the repository.
+    pub fn new(conversation_id: Uuid, event_type: SyncEventType, payload: JsonValue) -> Self {
+        Self {
+            seq: 0,
+            conversation_id,
+            event_type,
+            payload,
+            created_at: Utc::now(),
+            recipients: Vec::new(),
+        }
+    }
+}
+
@@ -21,7 +21,7 @@ pub struct User {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SubscriptionTier {
     Free,
     Monthly,
@@ -58,3 +58,45 @@ impl User {
         )
     }
 }
+
+impl SubscriptionTier {
+    /// Maximum number of members in a group owned by a user on this tier.
+    pub fn max_group_members(&self) -> usize {
+        match self {
+            SubscriptionTier::Free => 200,
+            SubscriptionTier::Monthly | SubscriptionTier::Yearly => 1000,
+        }
+    }
+
+    /// Lifetime of TURN credentials issued to a user on this tier, which also caps how long a
+    /// call relayed through TURN can last without fetching new ones.
+    pub fn turn_credential_ttl_seconds(&self) -> i64 {
+        match self {
+            SubscriptionTier::Free => 60 * 60,
+            SubscriptionTier::Monthly | SubscriptionTier::Yearly => 6 * 60 * 60,
+        }
+    }
+
+    /// Video bitrate clients should encode calls at on this tier: HD for free users, 4K for
+    /// premium. Only a hint for client encoders; neither TURN nor the SFU enforces it.
+    pub fn recommended_call_bitrate_kbps(&self) -> u32 {
+        match self {
+            SubscriptionTier::Free => 2_500,
+            SubscriptionTier::Monthly | SubscriptionTier::Yearly => 20_000,
+        }
+    }
+
+    /// What the tier unlocks, as shown to users.
+    pub fn features(&self) -> Vec<String> {
+        let call_quality = match self.recommended_call_bitrate_kbps() {
+            kbps if kbps > 2_500 => "4K video calls",
+            _ => "HD video calls",
+        };
+
+        vec![
+            format!("Groups of up to {} members", self.max_group_members()),
+            format!("Relayed calls of up to {} hours", self.turn_credential_ttl_seconds() / 3600),
+            call_quality.to_string(),
+        ]
+    }
+}
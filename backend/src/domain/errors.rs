@@ -22,3 +22,17 @@ pub enum DomainError {
 }
 
 pub type DomainResult<T> = Result<T, DomainError>;
+
+impl DomainError {
+    /// Stable machine-readable code, safe to send to clients.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DomainError::NotFound(_) => "NOT_FOUND",
+            DomainError::ValidationError(_) => "VALIDATION_ERROR",
+            DomainError::AuthenticationError(_) => "AUTHENTICATION_ERROR",
+            DomainError::AuthorizationError(_) => "AUTHORIZATION_ERROR",
+            DomainError::Conflict(_) => "CONFLICT",
+            DomainError::InternalError(_) => "INTERNAL_ERROR",
+        }
+    }
+}
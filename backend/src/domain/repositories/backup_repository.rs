@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Backup, BackupVault},
+    DomainResult,
+};
+
+#[async_trait]
+pub trait BackupRepository: Send + Sync {
+    /// Creates or replaces the user's vault, resetting its attempt counter.
+    async fn upsert_vault(&self, vault: &BackupVault) -> DomainResult<()>;
+    async fn find_vault(&self, user_id: Uuid) -> DomainResult<Option<BackupVault>>;
+    /// Counts an unlock attempt before it is checked, so concurrent guesses cannot exceed the
+    /// limit. Returns None if there is no vault or its attempts are used up.
+    async fn reserve_unlock_attempt(&self, user_id: Uuid) -> DomainResult<Option<BackupVault>>;
+    async fn reset_unlock_attempts(&self, user_id: Uuid) -> DomainResult<()>;
+    /// Moves a legacy vault to the peppered hash and sealed secret, dropping any plaintext one.
+    async fn upgrade_vault(&self, user_id: Uuid, access_key_hash: &str, sealed_secret: &str) -> DomainResult<()>;
+    async fn delete_vault(&self, user_id: Uuid) -> DomainResult<()>;
+
+    /// Stores a new backup with the next version number for the user.
+    async fn create(&self, backup: &Backup) -> DomainResult<Backup>;
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Backup>>;
+    /// All backups of the user, including unfinished uploads, newest version first.
+    async fn find_by_user(&self, user_id: Uuid) -> DomainResult<Vec<Backup>>;
+    async fn mark_complete(&self, id: Uuid) -> DomainResult<Backup>;
+    async fn delete(&self, id: Uuid) -> DomainResult<()>;
+}
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Call, CallStatus},
+    DomainResult,
+};
+
+#[async_trait]
+pub trait CallRepository: Send + Sync {
+    /// Records the call. A ringing call binds both parties to it, the caller on
+    /// `caller_device_id`; returns a conflict when either of them is already in another call.
+    async fn create(&self, call: &Call, caller_device_id: Option<&str>) -> DomainResult<Call>;
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Call>>;
+    /// The ringing or accepted call the user is a party of, if any.
+    async fn find_active_by_user(&self, user_id: Uuid) -> DomainResult<Option<Call>>;
+    /// The active call the user takes part in from this device: a call they placed from it, or
+    /// one they answered on it.
+    async fn find_active_by_device(&self, user_id: Uuid, device_id: Option<&str>) -> DomainResult<Option<Call>>;
+    /// Marks the user's device as still connected to its active call.
+    async fn touch(&self, user_id: Uuid, device_id: Option<&str>, now: DateTime<Utc>) -> DomainResult<()>;
+    /// Accepted calls with a party that has not been heard from since `before`.
+    async fn find_unresponsive(&self, before: DateTime<Utc>) -> DomainResult<Vec<Call>>;
+    /// Moves the call from `from` to `to`, setting `answered_at` on accept and `ended_at` on
+    /// every final status, and releases both parties once the call is over. Returns None when
+    /// the call is no longer in `from`, so concurrent transitions (two devices answering, a
+    /// timeout racing a hang-up) have a single winner.
+    async fn transition(
+        &self,
+        id: Uuid,
+        from: CallStatus,
+        to: CallStatus,
+        answered_device_id: Option<&str>,
+    ) -> DomainResult<Option<Call>>;
+    /// Ringing calls whose ring timeout has passed.
+    async fn find_ringing_expired(&self, now: DateTime<Utc>) -> DomainResult<Vec<Call>>;
+}
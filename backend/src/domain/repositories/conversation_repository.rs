@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Conversation, NearbyGroup, Participant, ParticipantRole},
+    DomainResult,
+};
+
+#[async_trait]
+pub trait ConversationRepository: Send + Sync {
+    /// Creates the conversation together with its initial participants.
+    async fn create(&self, conversation: &Conversation, participants: &[Participant]) -> DomainResult<Conversation>;
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Conversation>>;
+    async fn is_participant(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<bool>;
+    /// Private conversations and groups the user takes part in. Channels are left out.
+    async fn find_ids_by_user(&self, user_id: Uuid) -> DomainResult<Vec<Uuid>>;
+    async fn update_settings(&self, conversation_id: Uuid, settings: &JsonValue) -> DomainResult<()>;
+    async fn update_details(&self, conversation_id: Uuid, name: Option<String>, avatar_url: Option<String>) -> DomainResult<()>;
+    async fn find_participant(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<Option<Participant>>;
+    async fn find_participants(&self, conversation_id: Uuid) -> DomainResult<Vec<Participant>>;
+    async fn count_participants(&self, conversation_id: Uuid) -> DomainResult<i64>;
+    /// Adds a member. With `max_participants`, fails with a ValidationError instead if the
+    /// conversation is full, checked atomically with the insert.
+    async fn add_participant(&self, participant: &Participant, max_participants: Option<usize>) -> DomainResult<()>;
+    async fn remove_participant(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<()>;
+    async fn update_role(&self, conversation_id: Uuid, user_id: Uuid, role: ParticipantRole) -> DomainResult<()>;
+    async fn ban(&self, conversation_id: Uuid, user_id: Uuid, banned_by: Uuid) -> DomainResult<()>;
+    async fn unban(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<()>;
+    async fn is_banned(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<bool>;
+    /// Makes `new_owner_id` the owner and demotes the current owner to admin, atomically.
+    async fn transfer_ownership(&self, conversation_id: Uuid, current_owner_id: Uuid, new_owner_id: Uuid) -> DomainResult<()>;
+    /// Location-bound groups whose area may contain the stored (grid-snapped) location and that the
+    /// user is not banned from, closest first.
+    async fn find_nearby_groups(&self, user_id: Uuid, lat: f64, lon: f64, limit: i64) -> DomainResult<Vec<NearbyGroup>>;
+    /// Groups removing members on exit that the user belongs to (other than as owner) and whose
+    /// area does not contain the point.
+    async fn find_groups_left(&self, user_id: Uuid, lat: f64, lon: f64) -> DomainResult<Vec<Conversation>>;
+}
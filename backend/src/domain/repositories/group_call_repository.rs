@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{entities::GroupCall, DomainResult};
+
+#[async_trait]
+pub trait GroupCallRepository: Send + Sync {
+    /// Stores a new call. Fails with a conflict if the group already has an active call.
+    async fn create(&self, call: &GroupCall) -> DomainResult<GroupCall>;
+    async fn find_active(&self, conversation_id: Uuid) -> DomainResult<Option<GroupCall>>;
+    /// Every call still in progress, across all groups.
+    async fn find_all_active(&self) -> DomainResult<Vec<GroupCall>>;
+    /// Marks the user as in the call; rejoining after leaving is allowed.
+    async fn add_participant(&self, call_id: Uuid, user_id: Uuid) -> DomainResult<()>;
+    async fn remove_participant(&self, call_id: Uuid, user_id: Uuid) -> DomainResult<()>;
+    /// Users currently in the call, in joining order.
+    async fn find_participants(&self, call_id: Uuid) -> DomainResult<Vec<Uuid>>;
+    /// Users in the call who joined (or last rejoined) before `before`.
+    async fn find_participants_joined_before(
+        &self,
+        call_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> DomainResult<Vec<Uuid>>;
+    /// Ends the call unless someone is still in it. Returns the ended call, or None if it is
+    /// still occupied or was already ended.
+    async fn end_if_empty(&self, call_id: Uuid) -> DomainResult<Option<GroupCall>>;
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{GroupInvite, JoinRequest, Participant},
+    DomainResult,
+};
+
+#[async_trait]
+pub trait GroupInviteRepository: Send + Sync {
+    async fn create(&self, invite: &GroupInvite) -> DomainResult<GroupInvite>;
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<GroupInvite>>;
+    async fn find_by_token(&self, token: &str) -> DomainResult<Option<GroupInvite>>;
+    async fn find_by_conversation(&self, conversation_id: Uuid) -> DomainResult<Vec<GroupInvite>>;
+    async fn revoke(&self, id: Uuid) -> DomainResult<()>;
+    /// Counts one use of the invite. Returns false if it was revoked, expired or used up in the meantime.
+    async fn consume(&self, id: Uuid) -> DomainResult<bool>;
+
+    async fn create_join_request(&self, request: &JoinRequest) -> DomainResult<()>;
+    async fn find_join_request(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<Option<JoinRequest>>;
+    async fn find_join_requests(&self, conversation_id: Uuid) -> DomainResult<Vec<JoinRequest>>;
+    async fn delete_join_request(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<()>;
+    /// Replaces the pending join request with the membership in one transaction. Fails with
+    /// Conflict if the user was banned or joined in the meantime, and with a ValidationError if
+    /// the group already has `max_participants` members.
+    async fn approve_join_request(&self, participant: &Participant, max_participants: Option<usize>) -> DomainResult<()>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{entities::KeyLogEntry, DomainResult};
+
+/// Read access to the identity key history. Entries are appended by the repositories that store
+/// the keys, in the same transaction.
+#[async_trait]
+pub trait KeyLogRepository: Send + Sync {
+    /// The most recent entry of the user's chain.
+    async fn find_head(&self, user_id: Uuid) -> DomainResult<Option<KeyLogEntry>>;
+    /// The whole chain, oldest first.
+    async fn find_by_user(&self, user_id: Uuid) -> DomainResult<Vec<KeyLogEntry>>;
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{LiveLocationSession, LocationPoint},
+    DomainResult,
+};
+
+#[async_trait]
+pub trait LiveLocationRepository: Send + Sync {
+    async fn create(&self, session: &LiveLocationSession) -> DomainResult<LiveLocationSession>;
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<LiveLocationSession>>;
+    async fn find_active_by_conversation(&self, conversation_id: Uuid) -> DomainResult<Vec<LiveLocationSession>>;
+    /// Stores the point as the session's latest position and appends it to its track. Returns
+    /// false when the session is no longer active or the previous update is less than
+    /// `min_interval_seconds` old.
+    async fn record_point(&self, session_id: Uuid, point: &LocationPoint, min_interval_seconds: i64) -> DomainResult<bool>;
+    /// Points of the session's track recorded at or after `since`, oldest first.
+    async fn find_points(&self, session_id: Uuid, since: DateTime<Utc>) -> DomainResult<Vec<LocationPoint>>;
+    /// Deletes track points recorded before `before`. Returns how many were deleted.
+    async fn purge_points(&self, before: DateTime<Utc>) -> DomainResult<u64>;
+    /// Stops an active session. Returns None when it was already stopped.
+    async fn stop(&self, id: Uuid) -> DomainResult<Option<LiveLocationSession>>;
+    async fn stop_for_participant(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<Option<LiveLocationSession>>;
+    /// Stops sessions that ran past their expiry or whose sharer is no longer a participant.
+    async fn stop_expired(&self, now: DateTime<Utc>) -> DomainResult<Vec<LiveLocationSession>>;
+}
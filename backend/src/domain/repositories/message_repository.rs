@@ -1,21 +1,77 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::{entities::Message, DomainResult};
+use crate::domain::{entities::{Message, SyncEvent, ThreadSummary}, DomainResult};
 
 #[async_trait]
 pub trait MessageRepository: Send + Sync {
     async fn create(&self, message: &Message) -> DomainResult<Message>;
+    /// Stores the message and records `event` in the sync log in one transaction, so neither
+    /// exists without the other.
+    async fn create_with_event(&self, message: &Message, event: &SyncEvent) -> DomainResult<(Message, SyncEvent)>;
     async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Message>>;
+    async fn find_by_client_id(&self, sender_id: Uuid, client_message_id: &str) -> DomainResult<Option<Message>>;
     async fn find_by_conversation(
         &self,
         conversation_id: Uuid,
         limit: i64,
         offset: i64,
     ) -> DomainResult<Vec<Message>>;
+    /// Newest-first page of a conversation's unexpired messages before the `(created_at, id)`
+    /// cursor, with thread summaries.
+    async fn find_history(
+        &self,
+        conversation_id: Uuid,
+        before: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> DomainResult<Vec<(Message, ThreadSummary)>>;
+    /// Oldest-first page of the unexpired direct replies to `root_id` after the `(created_at, id)`
+    /// cursor.
+    async fn find_replies(
+        &self,
+        root_id: Uuid,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> DomainResult<Vec<Message>>;
     async fn update(&self, message: &Message) -> DomainResult<Message>;
     async fn delete(&self, id: Uuid) -> DomainResult<()>;
-    async fn delete_expired(&self) -> DomainResult<u64>;
+    /// Hard-deletes up to `limit` messages whose self-destruct time has passed. Returns the id
+    /// and conversation of each deleted message.
+    async fn delete_expired(&self, limit: i64) -> DomainResult<Vec<(Uuid, Uuid)>>;
+    /// Hard-deletes a message whose self-destruct time has passed. Returns false if it was not due.
+    async fn delete_if_expired(&self, id: Uuid) -> DomainResult<bool>;
+    /// Arms a read-triggered timer. Returns false if the timer was already running.
+    async fn start_self_destruct(&self, id: Uuid, destruct_at: DateTime<Utc>) -> DomainResult<bool>;
+    /// Page of messages with a running self-destruct timer, ordered by (self_destruct_at, id)
+    /// and starting after the `after` key.
+    async fn find_scheduled_for_destruction(
+        &self,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> DomainResult<Vec<Message>>;
     async fn mark_as_read(&self, message_id: Uuid, user_id: Uuid) -> DomainResult<()>;
+    /// Moves the subscriber's read position in a channel up to `read_up_to` and counts one
+    /// view for every post it passes over. No per-subscriber receipt rows are written. Callers
+    /// must check the subscription; without one nothing is counted.
+    async fn record_channel_view(&self, conversation_id: Uuid, user_id: Uuid, read_up_to: DateTime<Utc>) -> DomainResult<()>;
+    async fn find_view_counts(&self, conversation_id: Uuid, message_ids: &[Uuid]) -> DomainResult<Vec<(Uuid, i64)>>;
+    /// Pins a message unless the conversation already has `max_pinned` pinned messages, which
+    /// fails with a validation error. Returns false if it was already pinned.
+    async fn pin(&self, conversation_id: Uuid, message_id: Uuid, pinned_by: Uuid, max_pinned: i64) -> DomainResult<bool>;
+    /// Unpins a message. Returns false if it was not pinned.
+    async fn unpin(&self, message_id: Uuid) -> DomainResult<bool>;
+    /// Pinned messages of a conversation, most recently pinned first.
+    async fn find_pinned(&self, conversation_id: Uuid) -> DomainResult<Vec<Message>>;
+    async fn star(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<()>;
+    async fn unstar(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<()>;
+    /// The user's starred messages in conversations they still belong to, most recently
+    /// starred first, paginated by `starred_at`.
+    async fn find_starred(
+        &self,
+        user_id: Uuid,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> DomainResult<Vec<(Message, DateTime<Utc>)>>;
     async fn add_reaction(&self, message_id: Uuid, user_id: Uuid, reaction: &str) -> DomainResult<()>;
 }
@@ -1,7 +1,29 @@
 pub mod user_repository;
 pub mod message_repository;
 pub mod kyc_repository;
+pub mod sync_repository;
+pub mod conversation_repository;
+pub mod group_invite_repository;
+pub mod prekey_repository;
+pub mod key_log_repository;
+pub mod sender_key_repository;
+pub mod backup_repository;
+pub mod call_repository;
+pub mod group_call_repository;
+pub mod live_location_repository;
+pub mod subscription_repository;
 
 pub use user_repository::UserRepository;
 pub use message_repository::MessageRepository;
 pub use kyc_repository::KycRepository;
+pub use sync_repository::SyncRepository;
+pub use conversation_repository::ConversationRepository;
+pub use group_invite_repository::GroupInviteRepository;
+pub use prekey_repository::PreKeyRepository;
+pub use key_log_repository::KeyLogRepository;
+pub use sender_key_repository::SenderKeyRepository;
+pub use backup_repository::BackupRepository;
+pub use call_repository::CallRepository;
+pub use group_call_repository::GroupCallRepository;
+pub use live_location_repository::LiveLocationRepository;
+pub use subscription_repository::SubscriptionRepository;
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{DeviceKeys, KeyLogEntry, OneTimePreKey, PreKeyBundle},
+    DomainResult,
+};
+
+#[async_trait]
+pub trait PreKeyRepository: Send + Sync {
+    /// Publishes or refreshes a device's keys. Stored one-time prekeys are dropped when the
+    /// identity key changes, since they were issued under the old one. The identity key is
+    /// appended to the key history in the same transaction; returns the log entry if it replaced
+    /// an earlier key.
+    async fn upsert_device(&self, keys: &DeviceKeys) -> DomainResult<Option<KeyLogEntry>>;
+    async fn find_device(&self, user_id: Uuid, device_id: &str) -> DomainResult<Option<DeviceKeys>>;
+    /// Stores new one-time prekeys, ignoring ids the device has already uploaded. Stores nothing
+    /// and returns false when the device would end up with more than `max_keys`.
+    async fn add_one_time_prekeys(&self, user_id: Uuid, device_id: &str, keys: &[OneTimePreKey], max_keys: i64) -> DomainResult<bool>;
+    async fn count_one_time_prekeys(&self, user_id: Uuid, device_id: &str) -> DomainResult<i64>;
+    /// Builds a bundle for every device of the user, removing the one-time prekey it hands out
+    /// so that no two callers ever receive the same one.
+    async fn claim_bundles(&self, user_id: Uuid) -> DomainResult<Vec<PreKeyBundle>>;
+    /// Counts a claim of `target_id`'s bundles by `claimer_id` and returns how many claims the
+    /// pair made in the current window of `window_seconds`, this one included.
+    async fn record_claim(&self, claimer_id: Uuid, target_id: Uuid, window_seconds: i64) -> DomainResult<i64>;
+}
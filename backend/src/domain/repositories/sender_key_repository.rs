@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{entities::SenderKeyEnvelope, DomainResult};
+
+#[async_trait]
+pub trait SenderKeyRepository: Send + Sync {
+    /// The group's current sender key epoch; 0 until the first rotation.
+    async fn current_epoch(&self, conversation_id: Uuid) -> DomainResult<i32>;
+    /// Advances the epoch and returns it. Envelopes of earlier epochs are kept until
+    /// `SENDER_KEY_RETENTION_DAYS` after the rotation that superseded them.
+    async fn rotate(&self, conversation_id: Uuid) -> DomainResult<i32>;
+    /// Stores envelopes, replacing any earlier upload for the same sender and recipient device.
+    async fn store(&self, envelopes: &[SenderKeyEnvelope]) -> DomainResult<()>;
+    async fn find_for_recipient(
+        &self,
+        conversation_id: Uuid,
+        epoch: i32,
+        recipient_id: Uuid,
+        recipient_device_id: &str,
+    ) -> DomainResult<Vec<SenderKeyEnvelope>>;
+}
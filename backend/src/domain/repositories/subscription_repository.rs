@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Subscription, SubscriptionCheckout},
+    services::PaymentEvent,
+    DomainResult,
+};
+
+#[async_trait]
+pub trait SubscriptionRepository: Send + Sync {
+    /// Stores the subscription, replacing any previous one of the user, and sets the user's tier
+    /// to what the subscription entitles them to now.
+    async fn save(&self, subscription: &Subscription) -> DomainResult<Subscription>;
+    async fn find_by_user(&self, user_id: Uuid) -> DomainResult<Option<Subscription>>;
+    /// Reserves an upgrade for the user. Fails with a conflict while they are entitled to a
+    /// subscription or another upgrade is pending. An upgrade older than `stale_after_seconds`
+    /// with the same tier and payment token is taken over with its id, so provider requests are
+    /// replayed rather than repeated; one with other details still conflicts.
+    async fn reserve_checkout(&self, checkout: &SubscriptionCheckout, stale_after_seconds: i64) -> DomainResult<SubscriptionCheckout>;
+    async fn find_checkout(&self, user_id: Uuid) -> DomainResult<Option<SubscriptionCheckout>>;
+    /// Saves the subscription the checkout paid for and removes the checkout, atomically.
+    async fn complete_checkout(&self, checkout_id: Uuid, subscription: &Subscription) -> DomainResult<Subscription>;
+    async fn release_checkout(&self, checkout_id: Uuid) -> DomainResult<()>;
+    /// Records the webhook delivery and applies its event to the subscription it is about, in
+    /// one transaction. Returns false, applying nothing, when the delivery was recorded before.
+    async fn apply_webhook(&self, event_id: &str, event: Option<&PaymentEvent>) -> DomainResult<bool>;
+    /// Expires subscriptions whose access ended before `now` and downgrades their users to Free.
+    async fn expire_lapsed(&self, now: DateTime<Utc>) -> DomainResult<Vec<Subscription>>;
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{entities::SyncEvent, DomainResult};
+
+#[async_trait]
+pub trait SyncRepository: Send + Sync {
+    async fn append(&self, event: &SyncEvent) -> DomainResult<SyncEvent>;
+    /// Events after `after_seq` that were recorded while the user was a member of their
+    /// conversation, oldest first.
+    async fn find_since(&self, user_id: Uuid, after_seq: i64, limit: i64) -> DomainResult<Vec<SyncEvent>>;
+    /// Strips the ciphertext of a deleted or expired message from past events.
+    async fn redact_message(&self, message_id: Uuid) -> DomainResult<()>;
+    async fn get_cursor(&self, user_id: Uuid, device_id: &str) -> DomainResult<Option<i64>>;
+    async fn update_cursor(&self, user_id: Uuid, device_id: &str, seq: i64) -> DomainResult<()>;
+}
@@ -1,7 +1,8 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::{entities::User, DomainResult};
+use crate::domain::{entities::{KeyLogEntry, NearbySearch, PublicKey, User}, DomainResult};
 
 #[async_trait]
 pub trait UserRepository: Send + Sync {
@@ -9,11 +10,24 @@ pub trait UserRepository: Send + Sync {
     async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<User>>;
     async fn find_by_phone(&self, phone_number: &str) -> DomainResult<Option<User>>;
     async fn find_by_username(&self, username: &str) -> DomainResult<Option<User>>;
+    /// Users whose stored account key is `public_key`, in any encoding it was uploaded in.
+    async fn find_public_key_owners(&self, public_key: &PublicKey) -> DomainResult<Vec<Uuid>>;
+    /// Counts an OTP attempt by the user and returns the attempts in the current window.
+    async fn record_otp_attempt(&self, user_id: Uuid, window_seconds: i64) -> DomainResult<i64>;
     async fn update(&self, user: &User) -> DomainResult<User>;
     async fn delete(&self, id: Uuid) -> DomainResult<()>;
-    async fn find_nearby(&self, lat: f64, lon: f64, radius_km: f64) -> DomainResult<Vec<User>>;
-    async fn update_location(&self, user_id: Uuid, lat: f64, lon: f64) -> DomainResult<()>;
-    async fn update_location(&self, user_id: Uuid, lat: f64, lon: f64) -> DomainResult<()>;
+    /// Discoverable users around a point with their distance in whole kilometers, leaving out the
+    /// requester, anyone blocked in either direction and expired locations.
+    async fn find_nearby(&self, search: &NearbySearch) -> DomainResult<Vec<(User, f64)>>;
+    /// Stores the location only while the user is discoverable; returns whether it was stored.
+    async fn update_location(&self, user_id: Uuid, lat: f64, lon: f64) -> DomainResult<bool>;
+    /// Turning discoverability off also forgets the stored location.
+    async fn set_discoverable(&self, user_id: Uuid, discoverable: bool) -> DomainResult<()>;
+    async fn expire_locations(&self, updated_before: DateTime<Utc>) -> DomainResult<u64>;
+    async fn block_user(&self, blocker_id: Uuid, blocked_id: Uuid) -> DomainResult<()>;
+    async fn unblock_user(&self, blocker_id: Uuid, blocked_id: Uuid) -> DomainResult<()>;
     async fn update_online_status(&self, user_id: Uuid, is_online: bool) -> DomainResult<()>;
-    async fn update_public_key(&self, user_id: Uuid, public_key: String) -> DomainResult<()>;
+    /// Stores the account key and appends it to the key history in one transaction. Returns the
+    /// log entry if it replaced an earlier key.
+    async fn update_public_key(&self, user_id: Uuid, public_key: String) -> DomainResult<Option<KeyLogEntry>>;
 }
@@ -0,0 +1,14 @@
+use uuid::Uuid;
+
+/// One entry of a WebRTC `RTCConfiguration.iceServers` list.
+#[derive(Debug, Clone)]
+pub struct IceServer {
+    pub urls: Vec<String>,
+    pub username: Option<String>,
+    pub credential: Option<String>,
+}
+
+/// Hands out STUN/TURN servers for calls, with TURN credentials valid for `ttl_seconds`.
+pub trait IceServerProvider: Send + Sync {
+    fn ice_servers(&self, user_id: Uuid, ttl_seconds: i64) -> Vec<IceServer>;
+}
@@ -0,0 +1,7 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Arms self-destruct timers so expired messages are removed as soon as they are due.
+pub trait MessageExpiryScheduler: Send + Sync {
+    fn schedule(&self, message_id: Uuid, conversation_id: Uuid, destruct_at: DateTime<Utc>);
+}
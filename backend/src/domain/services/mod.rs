@@ -1,5 +1,13 @@
 pub mod auth_service;
 pub mod notification_service;
+pub mod message_expiry_scheduler;
+pub mod ice_server_provider;
+pub mod sfu_provider;
+pub mod payment_provider;
 
 pub use auth_service::AuthService;
 pub use notification_service::NotificationService;
+pub use message_expiry_scheduler::MessageExpiryScheduler;
+pub use ice_server_provider::{IceServer, IceServerProvider};
+pub use sfu_provider::{SfuGrants, SfuProvider};
+pub use payment_provider::{PaymentEvent, PaymentProvider, PaymentWebhook, ProviderSubscription};
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{entities::SubscriptionTier, DomainResult};
+
+/// A subscription as created at the payment provider, with its first paid period.
+#[derive(Debug, Clone)]
+pub struct ProviderSubscription {
+    pub id: String,
+    pub current_period_start: DateTime<Utc>,
+    pub current_period_end: DateTime<Utc>,
+}
+
+/// Billing change reported by the payment provider. `subscription_id` is the provider's id.
+#[derive(Debug, Clone)]
+pub enum PaymentEvent {
+    /// A billing period was paid for, the first one or a renewal.
+    Renewed {
+        subscription_id: String,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    },
+    /// Charging for the current period failed; the provider keeps retrying.
+    PaymentFailed { subscription_id: String },
+    /// The subscription ended, at the end of its period or early.
+    Canceled { subscription_id: String, ended_at: DateTime<Utc> },
+}
+
+impl PaymentEvent {
+    pub fn subscription_id(&self) -> &str {
+        match self {
+            PaymentEvent::Renewed { subscription_id, .. }
+            | PaymentEvent::PaymentFailed { subscription_id }
+            | PaymentEvent::Canceled { subscription_id, .. } => subscription_id,
+        }
+    }
+}
+
+/// A verified webhook delivery. `event` is None for event types that do not affect
+/// subscriptions.
+#[derive(Debug, Clone)]
+pub struct PaymentWebhook {
+    /// Provider-assigned id, identical across retried deliveries of the same event.
+    pub event_id: String,
+    pub event: Option<PaymentEvent>,
+}
+
+/// Bills recurring subscriptions. Renewals, failed charges and cancellations happen at the
+/// provider and are reported back through signed webhooks.
+#[async_trait]
+pub trait PaymentProvider: Send + Sync {
+    /// Starts billing `tier` to the payment method behind `payment_token`, charging the first
+    /// period right away. Repeating a call with the same `idempotency_key` returns the
+    /// subscription created by the first one instead of charging again.
+    async fn create_subscription(
+        &self,
+        user_id: Uuid,
+        tier: SubscriptionTier,
+        payment_token: &str,
+        idempotency_key: &str,
+    ) -> DomainResult<ProviderSubscription>;
+    /// The subscription a `create_subscription` call with `idempotency_key` created, if any.
+    /// Tells whether an upgrade that failed midway charged before it is started over.
+    async fn find_subscription(&self, idempotency_key: &str) -> DomainResult<Option<ProviderSubscription>>;
+    /// Turns off renewal; the subscription stays paid until the end of the current period.
+    async fn cancel_subscription(&self, subscription_id: &str) -> DomainResult<()>;
+    /// Ends the subscription right away and refunds what was charged for it. Used when a paid
+    /// upgrade could not be recorded.
+    async fn refund_subscription(&self, subscription_id: &str) -> DomainResult<()>;
+    /// HTTP header carrying the webhook signature.
+    fn signature_header(&self) -> &'static str;
+    /// Checks the signature of a webhook delivery and parses it.
+    fn parse_webhook(&self, payload: &[u8], signature: &str) -> DomainResult<PaymentWebhook>;
+}
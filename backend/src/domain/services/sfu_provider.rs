@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::DomainResult;
+
+/// What a participant may do in an SFU room.
+#[derive(Debug, Clone, Copy)]
+pub struct SfuGrants {
+    pub can_publish: bool,
+    pub can_subscribe: bool,
+}
+
+/// Selective forwarding unit hosting group calls. Clients connect to `url()` with a join token
+/// and exchange media through the SFU instead of with every other participant.
+#[async_trait]
+pub trait SfuProvider: Send + Sync {
+    /// Address clients connect to.
+    fn url(&self) -> String;
+    /// Opens the room, or keeps it open if it already exists.
+    async fn create_room(&self, room_name: &str) -> DomainResult<()>;
+    async fn delete_room(&self, room_name: &str) -> DomainResult<()>;
+    /// Users connected to the room, or None if the room is closed.
+    async fn list_participants(&self, room_name: &str) -> DomainResult<Option<Vec<Uuid>>>;
+    /// Disconnects the user from the room. Succeeds if they are not connected.
+    async fn remove_participant(&self, room_name: &str, user_id: Uuid) -> DomainResult<()>;
+    /// Mints a token that lets `user_id` join `room_name` with `grants` for `ttl_seconds`.
+    fn join_token(
+        &self,
+        room_name: &str,
+        user_id: Uuid,
+        grants: SfuGrants,
+        ttl_seconds: i64,
+    ) -> DomainResult<String>;
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::application::{ExpireCalls, ServerEvent};
+
+/// How often calls are checked against their ring and liveness timeouts.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Marks unanswered calls as missed once they have rung for too long, ends calls whose devices
+/// went silent, and tells the parties.
+pub struct CallTimeoutJob {
+    expire_calls: Arc<ExpireCalls>,
+    tx: broadcast::Sender<ServerEvent>,
+}
+
+impl CallTimeoutJob {
+    pub fn new(expire_calls: Arc<ExpireCalls>, tx: broadcast::Sender<ServerEvent>) -> Self {
+        Self {
+            expire_calls,
+            tx,
+        }
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match self.expire_calls.execute().await {
+                Ok(finished) => {
+                    for (call, event) in finished {
+                        let _ = self.tx.send(ServerEvent::call_update(&call, None));
+                        let _ = self.tx.send(ServerEvent::sync_event(&event));
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to expire calls: {}", e);
+                }
+            }
+        }
+    }
+}
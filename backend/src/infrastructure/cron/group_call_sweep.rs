@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::application::{ServerEvent, SweepGroupCalls};
+
+/// How often group calls are reconciled with their SFU rooms.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Takes participants who dropped out of the SFU without leaving out of their group call, ends
+/// calls left empty, and tells connected clients.
+pub struct GroupCallSweepJob {
+    sweep_group_calls: Arc<SweepGroupCalls>,
+    tx: broadcast::Sender<ServerEvent>,
+}
+
+impl GroupCallSweepJob {
+    pub fn new(sweep_group_calls: Arc<SweepGroupCalls>, tx: broadcast::Sender<ServerEvent>) -> Self {
+        Self {
+            sweep_group_calls,
+            tx,
+        }
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match self.sweep_group_calls.execute().await {
+                Ok(events) => {
+                    for event in events {
+                        let _ = self.tx.send(ServerEvent::sync_event(&event));
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to sweep group calls: {}", e);
+                }
+            }
+        }
+    }
+}
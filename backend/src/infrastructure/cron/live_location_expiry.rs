@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::application::{ExpireLiveLocations, ServerEvent};
+
+/// How often live location sessions are checked for expiry.
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+/// How often recorded tracks past their retention are purged.
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Stops live location sharing at expiry or once the sharer left the conversation, and tells
+/// connected clients. Also purges recorded tracks once they are past their retention.
+pub struct LiveLocationExpiryJob {
+    expire_live_locations: Arc<ExpireLiveLocations>,
+    tx: broadcast::Sender<ServerEvent>,
+}
+
+impl LiveLocationExpiryJob {
+    pub fn new(expire_live_locations: Arc<ExpireLiveLocations>, tx: broadcast::Sender<ServerEvent>) -> Self {
+        Self {
+            expire_live_locations,
+            tx,
+        }
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        let mut purge_interval = tokio::time::interval(PURGE_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = purge_interval.tick() => {
+                    match self.expire_live_locations.purge_tracks().await {
+                        Ok(0) => {}
+                        Ok(purged) => tracing::info!("Purged {} live location track points", purged),
+                        Err(e) => tracing::error!("Failed to purge live location tracks: {}", e),
+                    }
+                    continue;
+                }
+            }
+
+            match self.expire_live_locations.execute().await {
+                Ok(events) => {
+                    for event in events {
+                        let _ = self.tx.send(ServerEvent::sync_event(&event));
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to expire live locations: {}", e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::repositories::UserRepository;
+
+/// How often stale locations are swept.
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Forgets stored locations that have not been refreshed within the configured TTL.
+pub struct LocationExpiryJob {
+    user_repo: Arc<dyn UserRepository>,
+    location_ttl: chrono::Duration,
+}
+
+impl LocationExpiryJob {
+    pub fn new(user_repo: Arc<dyn UserRepository>, location_ttl: chrono::Duration) -> Self {
+        Self {
+            user_repo,
+            location_ttl,
+        }
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match self.user_repo.expire_locations(Utc::now() - self.location_ttl).await {
+                Ok(0) => {}
+                Ok(expired) => {
+                    tracing::info!("Expired {} stale user locations", expired);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to expire stale user locations: {}", e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::infrastructure::RedisGeoUserRepository;
+
+/// How often buffered location pings are written to Postgres.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Persists the locations indexed in Redis to Postgres in batches, after filling the index from
+/// Postgres on startup.
+pub struct LocationFlushJob {
+    geo_user_repo: Arc<RedisGeoUserRepository>,
+}
+
+impl LocationFlushJob {
+    pub fn new(geo_user_repo: Arc<RedisGeoUserRepository>) -> Self {
+        Self { geo_user_repo }
+    }
+
+    pub async fn run(self) {
+        match self.geo_user_repo.backfill().await {
+            Ok(count) => tracing::info!("Indexed {} stored locations in Redis", count),
+            Err(e) => tracing::error!("Failed to index stored locations in Redis: {}", e),
+        }
+
+        let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.geo_user_repo.flush_pending_locations().await {
+                tracing::error!("Failed to persist buffered locations: {}", e);
+            }
+        }
+    }
+}
@@ -1,33 +1,208 @@
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time;
+use tokio::sync::{broadcast, mpsc};
+use tokio_util::time::DelayQueue;
+use uuid::Uuid;
 
-use crate::domain::repositories::MessageRepository;
+use crate::application::ServerEvent;
+use crate::domain::{
+    entities::{SyncEvent, SyncEventType},
+    repositories::{MessageRepository, SyncRepository},
+    services::MessageExpiryScheduler,
+};
 
+/// How often expired messages are swept up, in case a timer was lost or failed.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+/// Delay before retrying a timer whose message could not be deleted yet.
+const RETRY_DELAY: chrono::Duration = chrono::Duration::seconds(5);
+/// Messages loaded or deleted per query.
+const BATCH_SIZE: i64 = 1000;
+
+#[derive(Debug, Clone)]
+struct ScheduledExpiry {
+    message_id: Uuid,
+    conversation_id: Uuid,
+    destruct_at: DateTime<Utc>,
+}
+
+/// Handle used by use cases to hand new self-destruct timers to the running job.
+pub struct MessageExpiryQueue {
+    sender: mpsc::UnboundedSender<ScheduledExpiry>,
+}
+
+impl MessageExpiryScheduler for MessageExpiryQueue {
+    fn schedule(&self, message_id: Uuid, conversation_id: Uuid, destruct_at: DateTime<Utc>) {
+        let _ = self.sender.send(ScheduledExpiry {
+            message_id,
+            conversation_id,
+            destruct_at,
+        });
+    }
+}
+
+/// Deletes self-destructing messages the moment they expire and tells connected clients. A
+/// periodic sweep catches messages whose timer was lost.
 pub struct MessageCleanupJob {
     message_repo: Arc<dyn MessageRepository>,
+    sync_repo: Arc<dyn SyncRepository>,
+    tx: broadcast::Sender<ServerEvent>,
+    queue: Arc<MessageExpiryQueue>,
+    receiver: mpsc::UnboundedReceiver<ScheduledExpiry>,
 }
 
 impl MessageCleanupJob {
-    pub fn new(message_repo: Arc<dyn MessageRepository>) -> Self {
-        Self { message_repo }
+    pub fn new(
+        message_repo: Arc<dyn MessageRepository>,
+        sync_repo: Arc<dyn SyncRepository>,
+        tx: broadcast::Sender<ServerEvent>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            message_repo,
+            sync_repo,
+            tx,
+            queue: Arc::new(MessageExpiryQueue { sender }),
+            receiver,
+        }
+    }
+
+    pub fn scheduler(&self) -> Arc<MessageExpiryQueue> {
+        self.queue.clone()
     }
 
-    pub async fn run(&self) {
-        let mut interval = time::interval(Duration::from_secs(60)); // Run every minute
+    pub async fn run(mut self) {
+        let mut timers = DelayQueue::new();
+        let mut sweep_interval = tokio::time::interval(SWEEP_INTERVAL);
+
+        self.rearm(&mut timers).await;
 
         loop {
-            interval.tick().await;
-            
-            match self.message_repo.delete_expired().await {
-                Ok(count) => {
-                    if count > 0 {
-                        tracing::info!("Cleaned up {} expired messages", count);
+            tokio::select! {
+                scheduled = self.receiver.recv() => match scheduled {
+                    Some(scheduled) => Self::insert(&mut timers, scheduled),
+                    None => break,
+                },
+                expired = std::future::poll_fn(|cx| timers.poll_expired(cx)), if !timers.is_empty() => {
+                    if let Some(expired) = expired {
+                        if let Some(retry) = self.expire(expired.into_inner()).await {
+                            Self::insert(&mut timers, retry);
+                        }
                     }
                 }
+                _ = sweep_interval.tick() => self.sweep().await,
+            }
+        }
+    }
+
+    /// Re-arms timers that were running before a restart, one page at a time.
+    async fn rearm(&self, timers: &mut DelayQueue<ScheduledExpiry>) {
+        let mut after = None;
+
+        loop {
+            let messages = match self.message_repo.find_scheduled_for_destruction(after, BATCH_SIZE).await {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::error!("Failed to load scheduled message expiries: {}", e);
+                    return;
+                }
+            };
+
+            for message in &messages {
+                if let Some(destruct_at) = message.self_destruct_at {
+                    Self::insert(timers, ScheduledExpiry {
+                        message_id: message.id,
+                        conversation_id: message.conversation_id,
+                        destruct_at,
+                    });
+                    after = Some((destruct_at, message.id));
+                }
+            }
+
+            if (messages.len() as i64) < BATCH_SIZE {
+                return;
+            }
+        }
+    }
+
+    /// Deletes every message that is past its self-destruct time.
+    async fn sweep(&self) {
+        loop {
+            let deleted = match self.message_repo.delete_expired(BATCH_SIZE).await {
+                Ok(deleted) => deleted,
                 Err(e) => {
                     tracing::error!("Failed to cleanup expired messages: {}", e);
+                    return;
                 }
+            };
+
+            if !deleted.is_empty() {
+                tracing::info!("Cleaned up {} expired messages", deleted.len());
+            }
+            for (message_id, conversation_id) in &deleted {
+                self.notify(*message_id, *conversation_id).await;
+            }
+
+            if (deleted.len() as i64) < BATCH_SIZE {
+                return;
+            }
+        }
+    }
+
+    fn insert(timers: &mut DelayQueue<ScheduledExpiry>, scheduled: ScheduledExpiry) {
+        let delay = (scheduled.destruct_at - Utc::now()).to_std().unwrap_or_default();
+        timers.insert(scheduled, delay);
+    }
+
+    /// Deletes the message if it is due. Returns the timer to re-arm when it could not be
+    /// deleted yet.
+    async fn expire(&self, scheduled: ScheduledExpiry) -> Option<ScheduledExpiry> {
+        let retry = ScheduledExpiry {
+            destruct_at: Utc::now() + RETRY_DELAY,
+            ..scheduled.clone()
+        };
+
+        match self.message_repo.delete_if_expired(scheduled.message_id).await {
+            Ok(true) => {}
+            // Either the message is gone already, or the database clock is behind ours
+            Ok(false) => {
+                return match self.message_repo.find_by_id(scheduled.message_id).await {
+                    Ok(Some(message)) if message.self_destruct_at.is_some() => Some(retry),
+                    Ok(_) => None,
+                    Err(e) => {
+                        tracing::error!("Failed to load expiring message {}: {}", scheduled.message_id, e);
+                        Some(retry)
+                    }
+                };
+            }
+            Err(e) => {
+                tracing::error!("Failed to delete expired message {}: {}", scheduled.message_id, e);
+                return Some(retry);
+            }
+        }
+
+        self.notify(scheduled.message_id, scheduled.conversation_id).await;
+        None
+    }
+
+    /// Redacts a deleted message from the sync log and tells connected clients it expired.
+    async fn notify(&self, message_id: Uuid, conversation_id: Uuid) {
+        if let Err(e) = self.sync_repo.redact_message(message_id).await {
+            tracing::error!("Failed to redact expired message {}: {}", message_id, e);
+        }
+
+        let event = SyncEvent::new(
+            conversation_id,
+            SyncEventType::MessageExpired,
+            serde_json::json!({ "message_id": message_id }),
+        );
+
+        match self.sync_repo.append(&event).await {
+            Ok(event) => {
+                let _ = self.tx.send(ServerEvent::sync_event(&event));
+            }
+            Err(e) => {
+                tracing::error!("Failed to record expiry of message {}: {}", message_id, e);
             }
         }
     }
@@ -1,3 +1,15 @@
 pub mod message_cleanup;
+pub mod call_timeout;
+pub mod location_expiry;
+pub mod live_location_expiry;
+pub mod location_flush;
+pub mod subscription_expiry;
+pub mod group_call_sweep;
 
 pub use message_cleanup::MessageCleanupJob;
+pub use call_timeout::CallTimeoutJob;
+pub use location_expiry::LocationExpiryJob;
+pub use live_location_expiry::LiveLocationExpiryJob;
+pub use location_flush::LocationFlushJob;
+pub use subscription_expiry::SubscriptionExpiryJob;
+pub use group_call_sweep::GroupCallSweepJob;
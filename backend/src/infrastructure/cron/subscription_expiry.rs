@@ -0,0 +1,38 @@
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::repositories::SubscriptionRepository;
+
+/// How often subscriptions are checked for expiry.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Downgrades users whose subscription ended, whether it was canceled or renewal payments kept
+/// failing past the grace period.
+pub struct SubscriptionExpiryJob {
+    subscription_repo: Arc<dyn SubscriptionRepository>,
+}
+
+impl SubscriptionExpiryJob {
+    pub fn new(subscription_repo: Arc<dyn SubscriptionRepository>) -> Self {
+        Self { subscription_repo }
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match self.subscription_repo.expire_lapsed(Utc::now()).await {
+                Ok(expired) if expired.is_empty() => {}
+                Ok(expired) => {
+                    tracing::info!("Expired {} subscriptions", expired.len());
+                }
+                Err(e) => {
+                    tracing::error!("Failed to expire subscriptions: {}", e);
+                }
+            }
+        }
+    }
+}
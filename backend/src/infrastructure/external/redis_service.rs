@@ -1,4 +1,4 @@
-use redis::{Client, Commands, PubSubCommands};
+use redis::{aio::ConnectionManager, Client, Commands, PubSubCommands};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 use anyhow::Result;
@@ -7,16 +7,23 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone)]
 pub struct RedisService {
     client: Client,
+    connection: ConnectionManager,
 }
 
 impl RedisService {
-    pub fn new(redis_url: &str) -> Result<Self> {
+    pub async fn new(redis_url: &str) -> Result<Self> {
         let client = Client::open(redis_url)?;
-        Ok(Self { client })
+        let connection = ConnectionManager::new(client.clone()).await?;
+        Ok(Self { client, connection })
+    }
+
+    /// Shared, reconnecting connection for regular commands. Subscriptions need their own.
+    pub fn connection(&self) -> ConnectionManager {
+        self.connection.clone()
     }
 
     pub async fn publish(&self, channel: &str, message: &str) -> Result<()> {
-        let mut con = self.client.get_async_connection().await?;
+        let mut con = self.connection();
         redis::cmd("PUBLISH")
             .arg(channel)
             .arg(message)
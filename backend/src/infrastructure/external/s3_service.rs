@@ -53,4 +53,60 @@ impl S3Service {
 
         Ok(presigned_request.uri().to_string())
     }
+
+    /// Presigned PUT for an object of exactly `content_length` bytes.
+    pub async fn get_presigned_upload_url(&self, key: &str, content_type: &str, content_length: i64) -> Result<String> {
+        let expires_in = Duration::from_secs(3600); // 1 hour
+        let presigned_request = self
+            .client
+            .put_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .content_type(content_type)
+            .content_length(content_length)
+            .presigned(PresigningConfig::expires_in(expires_in)?)
+            .await?;
+
+        Ok(presigned_request.uri().to_string())
+    }
+
+    pub async fn get_presigned_download_url(&self, key: &str) -> Result<String> {
+        let expires_in = Duration::from_secs(3600); // 1 hour
+        let presigned_request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .presigned(PresigningConfig::expires_in(expires_in)?)
+            .await?;
+
+        Ok(presigned_request.uri().to_string())
+    }
+
+    /// Size of the object in bytes, or None if nothing was uploaded under `key`.
+    pub async fn object_size(&self, key: &str) -> Result<Option<i64>> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => Ok(Some(output.content_length().unwrap_or_default())),
+            Err(e) if e.as_service_error().map_or(false, |e| e.is_not_found()) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn delete_object(&self, key: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await?;
+
+        Ok(())
+    }
 }
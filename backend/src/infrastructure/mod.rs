@@ -5,7 +5,16 @@ pub mod external;
 pub mod cron;
 
 pub use db::Database;
-pub use repositories::{PostgresUserRepository, PostgresKycRepository, PostgresMessageRepository};
-pub use services::{AuthServiceImpl, BlockchainService, EvmBlockchainService};
+pub use repositories::{
+    PostgresUserRepository, PostgresKycRepository, PostgresMessageRepository, PostgresSyncRepository,
+    PostgresConversationRepository, PostgresGroupInviteRepository, PostgresPreKeyRepository,
+    PostgresKeyLogRepository, PostgresSenderKeyRepository, PostgresBackupRepository,
+    PostgresCallRepository, PostgresGroupCallRepository, PostgresLiveLocationRepository,
+    PostgresSubscriptionRepository, RedisGeoUserRepository,
+};
+pub use services::{
+    AuthServiceImpl, BlockchainService, EvmBlockchainService, CoturnIceServerProvider, LiveKitSfuProvider,
+    StripePaymentProvider, FakePaymentProvider,
+};
 pub use external::{S3Service, RedisService, FcmService};
-pub use cron::MessageCleanupJob;
+pub use cron::{MessageCleanupJob, CallTimeoutJob, LocationExpiryJob, LiveLocationExpiryJob, LocationFlushJob, SubscriptionExpiryJob, GroupCallSweepJob};
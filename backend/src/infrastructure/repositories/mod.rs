@@ -1,7 +1,31 @@
 pub mod postgres_user_repository;
 pub mod postgres_kyc_repository;
 pub mod postgres_message_repository;
+pub mod postgres_sync_repository;
+pub mod postgres_conversation_repository;
+pub mod postgres_group_invite_repository;
+pub mod postgres_prekey_repository;
+pub mod postgres_key_log_repository;
+pub mod postgres_sender_key_repository;
+pub mod postgres_backup_repository;
+pub mod postgres_call_repository;
+pub mod postgres_group_call_repository;
+pub mod postgres_live_location_repository;
+pub mod postgres_subscription_repository;
+pub mod redis_geo_user_repository;
 
 pub use postgres_user_repository::PostgresUserRepository;
 pub use postgres_kyc_repository::PostgresKycRepository;
 pub use postgres_message_repository::PostgresMessageRepository;
+pub use postgres_sync_repository::PostgresSyncRepository;
+pub use postgres_conversation_repository::PostgresConversationRepository;
+pub use postgres_group_invite_repository::PostgresGroupInviteRepository;
+pub use postgres_prekey_repository::PostgresPreKeyRepository;
+pub use postgres_key_log_repository::PostgresKeyLogRepository;
+pub use postgres_sender_key_repository::PostgresSenderKeyRepository;
+pub use postgres_backup_repository::PostgresBackupRepository;
+pub use postgres_call_repository::PostgresCallRepository;
+pub use postgres_group_call_repository::PostgresGroupCallRepository;
+pub use postgres_live_location_repository::PostgresLiveLocationRepository;
+pub use postgres_subscription_repository::PostgresSubscriptionRepository;
+pub use redis_geo_user_repository::RedisGeoUserRepository;
@@ -0,0 +1,279 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Backup, BackupVault},
+    repositories::BackupRepository,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresBackupRepository {
+    pool: PgPool,
+}
+
+impl PostgresBackupRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BackupRepository for PostgresBackupRepository {
+    async fn upsert_vault(&self, vault: &BackupVault) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO backup_vaults (user_id, access_key_hash, sealed_secret, peppered, secret, failed_attempts, max_attempts, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (user_id) DO UPDATE
+            SET access_key_hash = EXCLUDED.access_key_hash,
+                sealed_secret = EXCLUDED.sealed_secret,
+                peppered = EXCLUDED.peppered,
+                secret = EXCLUDED.secret,
+                failed_attempts = EXCLUDED.failed_attempts,
+                max_attempts = EXCLUDED.max_attempts,
+                updated_at = EXCLUDED.updated_at
+            "#,
+            vault.user_id,
+            vault.access_key_hash,
+            vault.sealed_secret,
+            vault.peppered,
+            vault.legacy_secret,
+            vault.failed_attempts,
+            vault.max_attempts,
+            vault.created_at,
+            vault.updated_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_vault(&self, user_id: Uuid) -> DomainResult<Option<BackupVault>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT user_id, access_key_hash, sealed_secret, peppered, secret, failed_attempts, max_attempts, created_at, updated_at
+            FROM backup_vaults
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| BackupVault {
+            user_id: r.user_id,
+            access_key_hash: r.access_key_hash,
+            sealed_secret: r.sealed_secret,
+            peppered: r.peppered,
+            legacy_secret: r.secret,
+            failed_attempts: r.failed_attempts,
+            max_attempts: r.max_attempts,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }))
+    }
+
+    async fn reserve_unlock_attempt(&self, user_id: Uuid) -> DomainResult<Option<BackupVault>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE backup_vaults
+            SET failed_attempts = failed_attempts + 1, updated_at = NOW()
+            WHERE user_id = $1 AND failed_attempts < max_attempts
+            RETURNING user_id, access_key_hash, sealed_secret, peppered, secret, failed_attempts, max_attempts, created_at, updated_at
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| BackupVault {
+            user_id: r.user_id,
+            access_key_hash: r.access_key_hash,
+            sealed_secret: r.sealed_secret,
+            peppered: r.peppered,
+            legacy_secret: r.secret,
+            failed_attempts: r.failed_attempts,
+            max_attempts: r.max_attempts,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }))
+    }
+
+    async fn reset_unlock_attempts(&self, user_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE backup_vaults SET failed_attempts = 0, updated_at = NOW() WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn upgrade_vault(&self, user_id: Uuid, access_key_hash: &str, sealed_secret: &str) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE backup_vaults
+            SET access_key_hash = $2, sealed_secret = $3, peppered = TRUE, secret = NULL, updated_at = NOW()
+            WHERE user_id = $1
+            "#,
+            user_id,
+            access_key_hash,
+            sealed_secret
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn delete_vault(&self, user_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM backup_vaults WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn create(&self, backup: &Backup) -> DomainResult<Backup> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO backups (id, user_id, version, object_key, size_bytes, created_at, completed_at)
+            SELECT $1, $2, COALESCE(MAX(version), 0) + 1, $3, $4, $5, $6
+            FROM backups
+            WHERE user_id = $2
+            RETURNING id, user_id, version, object_key, size_bytes, created_at, completed_at
+            "#,
+            backup.id,
+            backup.user_id,
+            backup.object_key,
+            backup.size_bytes,
+            backup.created_at,
+            backup.completed_at
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                DomainError::Conflict("Another backup is being started, retry".to_string())
+            }
+            e => DomainError::InternalError(format!("Database error: {}", e)),
+        })?;
+
+        Ok(Backup {
+            id: row.id,
+            user_id: row.user_id,
+            version: row.version,
+            object_key: row.object_key,
+            size_bytes: row.size_bytes,
+            created_at: row.created_at,
+            completed_at: row.completed_at,
+        })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Backup>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, user_id, version, object_key, size_bytes, created_at, completed_at
+            FROM backups
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| Backup {
+            id: r.id,
+            user_id: r.user_id,
+            version: r.version,
+            object_key: r.object_key,
+            size_bytes: r.size_bytes,
+            created_at: r.created_at,
+            completed_at: r.completed_at,
+        }))
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> DomainResult<Vec<Backup>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, user_id, version, object_key, size_bytes, created_at, completed_at
+            FROM backups
+            WHERE user_id = $1
+            ORDER BY version DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Backup {
+                id: r.id,
+                user_id: r.user_id,
+                version: r.version,
+                object_key: r.object_key,
+                size_bytes: r.size_bytes,
+                created_at: r.created_at,
+                completed_at: r.completed_at,
+            })
+            .collect())
+    }
+
+    async fn mark_complete(&self, id: Uuid) -> DomainResult<Backup> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE backups
+            SET completed_at = COALESCE(completed_at, NOW())
+            WHERE id = $1
+            RETURNING id, user_id, version, object_key, size_bytes, created_at, completed_at
+            "#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(Backup {
+            id: row.id,
+            user_id: row.user_id,
+            version: row.version,
+            object_key: row.object_key,
+            size_bytes: row.size_bytes,
+            created_at: row.created_at,
+            completed_at: row.completed_at,
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM backups WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+}
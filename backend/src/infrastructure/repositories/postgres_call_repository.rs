@@ -0,0 +1,353 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Call, CallStatus},
+    repositories::CallRepository,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresCallRepository {
+    pool: PgPool,
+}
+
+impl PostgresCallRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn status_str(status: CallStatus) -> &'static str {
+    match status {
+        CallStatus::Ringing => "Ringing",
+        CallStatus::Accepted => "Accepted",
+        CallStatus::Rejected => "Rejected",
+        CallStatus::Busy => "Busy",
+        CallStatus::Missed => "Missed",
+        CallStatus::Ended => "Ended",
+    }
+}
+
+fn parse_status(status: &str) -> CallStatus {
+    match status {
+        "Accepted" => CallStatus::Accepted,
+        "Rejected" => CallStatus::Rejected,
+        "Busy" => CallStatus::Busy,
+        "Missed" => CallStatus::Missed,
+        "Ended" => CallStatus::Ended,
+        _ => CallStatus::Ringing,
+    }
+}
+
+#[async_trait]
+impl CallRepository for PostgresCallRepository {
+    async fn create(&self, call: &Call, caller_device_id: Option<&str>) -> DomainResult<Call> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO calls (id, conversation_id, caller_id, callee_id, is_video, status, answered_device_id, ring_expires_at, created_at, answered_at, ended_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING id, conversation_id, caller_id, callee_id, is_video, status, answered_device_id, ring_expires_at, created_at, answered_at, ended_at
+            "#,
+            call.id,
+            call.conversation_id,
+            call.caller_id,
+            call.callee_id,
+            call.is_video,
+            status_str(call.status),
+            call.answered_device_id,
+            call.ring_expires_at,
+            call.created_at,
+            call.answered_at,
+            call.ended_at
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        // The partial unique index on active parties rejects a second call for either user
+        if call.status.is_active() {
+            sqlx::query!(
+                r#"
+                INSERT INTO call_parties (call_id, user_id, device_id)
+                VALUES ($1, $2, $3), ($1, $4, NULL)
+                "#,
+                call.id,
+                call.caller_id,
+                caller_device_id,
+                call.callee_id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db) if db.is_unique_violation() => {
+                    DomainError::Conflict("Already in a call".to_string())
+                }
+                e => DomainError::InternalError(format!("Database error: {}", e)),
+            })?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(Call {
+            id: row.id,
+            conversation_id: row.conversation_id,
+            caller_id: row.caller_id,
+            callee_id: row.callee_id,
+            is_video: row.is_video,
+            status: parse_status(&row.status),
+            answered_device_id: row.answered_device_id,
+            ring_expires_at: row.ring_expires_at,
+            created_at: row.created_at,
+            answered_at: row.answered_at,
+            ended_at: row.ended_at,
+        })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Call>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, caller_id, callee_id, is_video, status, answered_device_id, ring_expires_at, created_at, answered_at, ended_at
+            FROM calls
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| Call {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            caller_id: r.caller_id,
+            callee_id: r.callee_id,
+            is_video: r.is_video,
+            status: parse_status(&r.status),
+            answered_device_id: r.answered_device_id,
+            ring_expires_at: r.ring_expires_at,
+            created_at: r.created_at,
+            answered_at: r.answered_at,
+            ended_at: r.ended_at,
+        }))
+    }
+
+    async fn find_active_by_user(&self, user_id: Uuid) -> DomainResult<Option<Call>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT c.id, c.conversation_id, c.caller_id, c.callee_id, c.is_video, c.status, c.answered_device_id,
+                   c.ring_expires_at, c.created_at, c.answered_at, c.ended_at
+            FROM call_parties p
+            JOIN calls c ON c.id = p.call_id
+            WHERE p.user_id = $1 AND p.is_active
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| Call {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            caller_id: r.caller_id,
+            callee_id: r.callee_id,
+            is_video: r.is_video,
+            status: parse_status(&r.status),
+            answered_device_id: r.answered_device_id,
+            ring_expires_at: r.ring_expires_at,
+            created_at: r.created_at,
+            answered_at: r.answered_at,
+            ended_at: r.ended_at,
+        }))
+    }
+
+    async fn transition(
+        &self,
+        id: Uuid,
+        from: CallStatus,
+        to: CallStatus,
+        answered_device_id: Option<&str>,
+    ) -> DomainResult<Option<Call>> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let row = sqlx::query!(
+            r#"
+            UPDATE calls
+            SET status = $3,
+                answered_device_id = COALESCE($4, answered_device_id),
+                answered_at = CASE WHEN $3 = 'Accepted' THEN NOW() ELSE answered_at END,
+                ended_at = CASE WHEN $3 IN ('Ringing', 'Accepted') THEN ended_at ELSE NOW() END
+            WHERE id = $1 AND status = $2
+            RETURNING id, conversation_id, caller_id, callee_id, is_video, status, answered_device_id, ring_expires_at, created_at, answered_at, ended_at
+            "#,
+            id,
+            status_str(from),
+            status_str(to),
+            answered_device_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        if let Some(r) = &row {
+            // Bind the callee to the answering device, or release both parties of a finished call
+            sqlx::query!(
+                r#"
+                UPDATE call_parties
+                SET device_id = CASE WHEN user_id = $2 AND $3 = 'Accepted' THEN $4 ELSE device_id END,
+                    is_active = $3 IN ('Ringing', 'Accepted'),
+                    last_seen_at = NOW()
+                WHERE call_id = $1
+                "#,
+                r.id,
+                r.callee_id,
+                r.status,
+                r.answered_device_id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| Call {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            caller_id: r.caller_id,
+            callee_id: r.callee_id,
+            is_video: r.is_video,
+            status: parse_status(&r.status),
+            answered_device_id: r.answered_device_id,
+            ring_expires_at: r.ring_expires_at,
+            created_at: r.created_at,
+            answered_at: r.answered_at,
+            ended_at: r.ended_at,
+        }))
+    }
+
+    async fn find_active_by_device(&self, user_id: Uuid, device_id: Option<&str>) -> DomainResult<Option<Call>> {
+        // The callee's devices all ring, so a ringing call is only bound to the caller's device
+        let row = sqlx::query!(
+            r#"
+            SELECT c.id, c.conversation_id, c.caller_id, c.callee_id, c.is_video, c.status, c.answered_device_id,
+                   c.ring_expires_at, c.created_at, c.answered_at, c.ended_at
+            FROM call_parties p
+            JOIN calls c ON c.id = p.call_id
+            WHERE p.user_id = $1
+              AND p.is_active
+              AND p.device_id IS NOT DISTINCT FROM $2
+              AND (c.status = 'Accepted' OR c.caller_id = $1)
+            "#,
+            user_id,
+            device_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| Call {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            caller_id: r.caller_id,
+            callee_id: r.callee_id,
+            is_video: r.is_video,
+            status: parse_status(&r.status),
+            answered_device_id: r.answered_device_id,
+            ring_expires_at: r.ring_expires_at,
+            created_at: r.created_at,
+            answered_at: r.answered_at,
+            ended_at: r.ended_at,
+        }))
+    }
+
+    async fn touch(&self, user_id: Uuid, device_id: Option<&str>, now: DateTime<Utc>) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE call_parties
+            SET last_seen_at = $3
+            WHERE user_id = $1 AND is_active AND device_id IS NOT DISTINCT FROM $2
+            "#,
+            user_id,
+            device_id,
+            now
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_unresponsive(&self, before: DateTime<Utc>) -> DomainResult<Vec<Call>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT c.id, c.conversation_id, c.caller_id, c.callee_id, c.is_video, c.status, c.answered_device_id,
+                   c.ring_expires_at, c.created_at, c.answered_at, c.ended_at
+            FROM call_parties p
+            JOIN calls c ON c.id = p.call_id
+            WHERE p.is_active AND p.last_seen_at < $1 AND c.status = 'Accepted'
+            "#,
+            before
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Call {
+                id: r.id,
+                conversation_id: r.conversation_id,
+                caller_id: r.caller_id,
+                callee_id: r.callee_id,
+                is_video: r.is_video,
+                status: parse_status(&r.status),
+                answered_device_id: r.answered_device_id,
+                ring_expires_at: r.ring_expires_at,
+                created_at: r.created_at,
+                answered_at: r.answered_at,
+                ended_at: r.ended_at,
+            })
+            .collect())
+    }
+
+    async fn find_ringing_expired(&self, now: DateTime<Utc>) -> DomainResult<Vec<Call>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, caller_id, callee_id, is_video, status, answered_device_id, ring_expires_at, created_at, answered_at, ended_at
+            FROM calls
+            WHERE status = 'Ringing' AND ring_expires_at <= $1
+            "#,
+            now
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Call {
+                id: r.id,
+                conversation_id: r.conversation_id,
+                caller_id: r.caller_id,
+                callee_id: r.callee_id,
+                is_video: r.is_video,
+                status: parse_status(&r.status),
+                answered_device_id: r.answered_device_id,
+                ring_expires_at: r.ring_expires_at,
+                created_at: r.created_at,
+                answered_at: r.answered_at,
+                ended_at: r.ended_at,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,557 @@
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        Conversation, ConversationType, GeoPoint, Geofence, NearbyGroup, Participant, ParticipantRole,
+        LOCATION_FUZZ_METERS, MAX_GEOFENCE_RADIUS_METERS,
+    },
+    repositories::ConversationRepository,
+    DomainError, DomainResult,
+};
+
+/// Fails with a ValidationError if the conversation already has `max_participants` members.
+/// Locks the conversation until the caller's transaction ends, so concurrent joins are counted
+/// one after another and cannot overfill it.
+pub(crate) async fn ensure_room(
+    tx: &mut Transaction<'_, Postgres>,
+    conversation_id: Uuid,
+    max_participants: usize,
+) -> DomainResult<()> {
+    sqlx::query!("SELECT id FROM conversations WHERE id = $1 FOR UPDATE", conversation_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+        .ok_or_else(|| DomainError::NotFound("Conversation not found".to_string()))?;
+
+    let participants = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!" FROM conversation_participants WHERE conversation_id = $1
+        "#,
+        conversation_id
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+    .count;
+
+    if participants as usize >= max_participants {
+        return Err(DomainError::ValidationError(format!("Group is limited to {} members", max_participants)));
+    }
+    Ok(())
+}
+
+pub struct PostgresConversationRepository {
+    pool: PgPool,
+}
+
+impl PostgresConversationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn parse_type(conversation_type: &str) -> ConversationType {
+    match conversation_type {
+        "Group" => ConversationType::Group,
+        "Channel" => ConversationType::Channel,
+        _ => ConversationType::Private,
+    }
+}
+
+fn geofence(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    radius_meters: Option<i32>,
+    remove_on_exit: bool,
+) -> Option<Geofence> {
+    Some(Geofence {
+        center: GeoPoint::new(latitude?, longitude?),
+        radius_meters: radius_meters?,
+        remove_on_exit,
+    })
+}
+
+#[async_trait]
+impl ConversationRepository for PostgresConversationRepository {
+    async fn create(&self, conversation: &Conversation, participants: &[Participant]) -> DomainResult<Conversation> {
+        let conversation_type = match conversation.conversation_type {
+            ConversationType::Private => "Private",
+            ConversationType::Group => "Group",
+            ConversationType::Channel => "Channel",
+        };
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let geofence_center = conversation.geofence.as_ref().map(|g| g.center);
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO conversations (
+                id, type, name, avatar_url, settings, created_at, updated_at,
+                geofence_center, geofence_radius_meters, geofence_remove_on_exit
+            )
+            VALUES (
+                $1, $2, $3, $4, $5, $6, $7,
+                ST_SetSRID(ST_MakePoint($8, $9), 4326)::geography, $10, $11
+            )
+            RETURNING id, type, name, avatar_url, settings, created_at, updated_at,
+                      ST_Y(geofence_center::geometry) as geofence_lat, ST_X(geofence_center::geometry) as geofence_lon,
+                      geofence_radius_meters, geofence_remove_on_exit
+            "#,
+            conversation.id,
+            conversation_type,
+            conversation.name,
+            conversation.avatar_url,
+            conversation.settings,
+            conversation.created_at,
+            conversation.updated_at,
+            geofence_center.map(|c| c.longitude),
+            geofence_center.map(|c| c.latitude),
+            conversation.geofence.as_ref().map(|g| g.radius_meters),
+            conversation.geofence.as_ref().map_or(false, |g| g.remove_on_exit)
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        for participant in participants {
+            let role = match participant.role {
+                ParticipantRole::Owner => "Owner",
+                ParticipantRole::Admin => "Admin",
+                ParticipantRole::Member => "Member",
+            };
+
+            sqlx::query!(
+                r#"
+                INSERT INTO conversation_participants (conversation_id, user_id, role, joined_at)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                row.id,
+                participant.user_id,
+                role,
+                participant.joined_at
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(Conversation {
+            id: row.id,
+            conversation_type: parse_type(&row.type_),
+            name: row.name,
+            avatar_url: row.avatar_url,
+            settings: row.settings.unwrap_or_else(|| serde_json::json!({})),
+            geofence: geofence(row.geofence_lat, row.geofence_lon, row.geofence_radius_meters, row.geofence_remove_on_exit),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Conversation>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, type, name, avatar_url, settings, created_at, updated_at,
+                   ST_Y(geofence_center::geometry) as geofence_lat, ST_X(geofence_center::geometry) as geofence_lon,
+                   geofence_radius_meters, geofence_remove_on_exit
+            FROM conversations
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| Conversation {
+            id: r.id,
+            conversation_type: parse_type(&r.type_),
+            name: r.name,
+            avatar_url: r.avatar_url,
+            settings: r.settings.unwrap_or_else(|| serde_json::json!({})),
+            geofence: geofence(r.geofence_lat, r.geofence_lon, r.geofence_radius_meters, r.geofence_remove_on_exit),
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }))
+    }
+
+    async fn is_participant(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM conversation_participants
+                WHERE conversation_id = $1 AND user_id = $2
+            ) as "exists!"
+            "#,
+            conversation_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.exists)
+    }
+
+    async fn find_ids_by_user(&self, user_id: Uuid) -> DomainResult<Vec<Uuid>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT c.id
+            FROM conversations c
+            JOIN conversation_participants p ON p.conversation_id = c.id
+            WHERE p.user_id = $1 AND c.type <> 'Channel'
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.id).collect())
+    }
+
+    async fn update_settings(&self, conversation_id: Uuid, settings: &JsonValue) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE conversations SET settings = $2 WHERE id = $1
+            "#,
+            conversation_id,
+            settings
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn update_details(&self, conversation_id: Uuid, name: Option<String>, avatar_url: Option<String>) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE conversations SET name = $2, avatar_url = $3, updated_at = NOW() WHERE id = $1
+            "#,
+            conversation_id,
+            name,
+            avatar_url
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_participant(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<Option<Participant>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT conversation_id, user_id, role, joined_at
+            FROM conversation_participants
+            WHERE conversation_id = $1 AND user_id = $2
+            "#,
+            conversation_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| Participant {
+            conversation_id: r.conversation_id,
+            user_id: r.user_id,
+            role: match r.role.as_str() {
+                "Owner" => ParticipantRole::Owner,
+                "Admin" => ParticipantRole::Admin,
+                _ => ParticipantRole::Member,
+            },
+            joined_at: r.joined_at,
+        }))
+    }
+
+    async fn find_participants(&self, conversation_id: Uuid) -> DomainResult<Vec<Participant>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT conversation_id, user_id, role, joined_at
+            FROM conversation_participants
+            WHERE conversation_id = $1
+            ORDER BY joined_at ASC
+            "#,
+            conversation_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| Participant {
+            conversation_id: r.conversation_id,
+            user_id: r.user_id,
+            role: match r.role.as_str() {
+                "Owner" => ParticipantRole::Owner,
+                "Admin" => ParticipantRole::Admin,
+                _ => ParticipantRole::Member,
+            },
+            joined_at: r.joined_at,
+        }).collect())
+    }
+
+    async fn count_participants(&self, conversation_id: Uuid) -> DomainResult<i64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM conversation_participants WHERE conversation_id = $1
+            "#,
+            conversation_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.count)
+    }
+
+    async fn add_participant(&self, participant: &Participant, max_participants: Option<usize>) -> DomainResult<()> {
+        let role = match participant.role {
+            ParticipantRole::Owner => "Owner",
+            ParticipantRole::Admin => "Admin",
+            ParticipantRole::Member => "Member",
+        };
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        if let Some(max_participants) = max_participants {
+            ensure_room(&mut tx, participant.conversation_id, max_participants).await?;
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO conversation_participants (conversation_id, user_id, role, joined_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            participant.conversation_id,
+            participant.user_id,
+            role,
+            participant.joined_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn remove_participant(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM conversation_participants WHERE conversation_id = $1 AND user_id = $2
+            "#,
+            conversation_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn update_role(&self, conversation_id: Uuid, user_id: Uuid, role: ParticipantRole) -> DomainResult<()> {
+        let role = match role {
+            ParticipantRole::Owner => "Owner",
+            ParticipantRole::Admin => "Admin",
+            ParticipantRole::Member => "Member",
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE conversation_participants SET role = $3 WHERE conversation_id = $1 AND user_id = $2
+            "#,
+            conversation_id,
+            user_id,
+            role
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn ban(&self, conversation_id: Uuid, user_id: Uuid, banned_by: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO conversation_bans (conversation_id, user_id, banned_by)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (conversation_id, user_id) DO NOTHING
+            "#,
+            conversation_id,
+            user_id,
+            banned_by
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn unban(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM conversation_bans WHERE conversation_id = $1 AND user_id = $2
+            "#,
+            conversation_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn is_banned(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM conversation_bans
+                WHERE conversation_id = $1 AND user_id = $2
+            ) as "exists!"
+            "#,
+            conversation_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.exists)
+    }
+
+    async fn transfer_ownership(&self, conversation_id: Uuid, current_owner_id: Uuid, new_owner_id: Uuid) -> DomainResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        // Demote first so the single-owner index is never violated
+        sqlx::query!(
+            r#"
+            UPDATE conversation_participants SET role = 'Admin' WHERE conversation_id = $1 AND user_id = $2
+            "#,
+            conversation_id,
+            current_owner_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        sqlx::query!(
+            r#"
+            UPDATE conversation_participants SET role = 'Owner' WHERE conversation_id = $1 AND user_id = $2
+            "#,
+            conversation_id,
+            new_owner_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_nearby_groups(&self, user_id: Uuid, lat: f64, lon: f64, limit: i64) -> DomainResult<Vec<NearbyGroup>> {
+        // The constant radius lets the spatial index narrow the candidates before each group's
+        // own radius is checked. Both are widened by how far the stored cell center can be from
+        // the user
+        let rows = sqlx::query!(
+            r#"
+            SELECT c.id, c.type, c.name, c.avatar_url, c.settings, c.created_at, c.updated_at,
+                   ST_Y(c.geofence_center::geometry) as geofence_lat, ST_X(c.geofence_center::geometry) as geofence_lon,
+                   c.geofence_radius_meters, c.geofence_remove_on_exit,
+                   (SELECT COUNT(*) FROM conversation_participants p WHERE p.conversation_id = c.id) as "member_count!",
+                   GREATEST(CEIL(ST_Distance(c.geofence_center, ST_SetSRID(ST_MakePoint($2, $3), 4326)::geography) / 1000.0), 1)::float8 as "distance_km!"
+            FROM conversations c
+            WHERE c.geofence_center IS NOT NULL
+              AND ST_DWithin(c.geofence_center, ST_SetSRID(ST_MakePoint($2, $3), 4326)::geography, $4)
+              AND ST_DWithin(c.geofence_center, ST_SetSRID(ST_MakePoint($2, $3), 4326)::geography, c.geofence_radius_meters + $6::float8)
+              AND NOT EXISTS (
+                  SELECT 1 FROM conversation_bans b WHERE b.conversation_id = c.id AND b.user_id = $1
+              )
+            ORDER BY ST_Distance(c.geofence_center, ST_SetSRID(ST_MakePoint($2, $3), 4326)::geography), c.id
+            LIMIT $5
+            "#,
+            user_id,
+            lon,
+            lat,
+            MAX_GEOFENCE_RADIUS_METERS as f64 + LOCATION_FUZZ_METERS,
+            limit,
+            LOCATION_FUZZ_METERS
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| NearbyGroup {
+                conversation: Conversation {
+                    id: r.id,
+                    conversation_type: parse_type(&r.type_),
+                    name: r.name,
+                    avatar_url: r.avatar_url,
+                    settings: r.settings.unwrap_or_else(|| serde_json::json!({})),
+                    geofence: geofence(r.geofence_lat, r.geofence_lon, r.geofence_radius_meters, r.geofence_remove_on_exit),
+                    created_at: r.created_at,
+                    updated_at: r.updated_at,
+                },
+                member_count: r.member_count,
+                distance_km: r.distance_km,
+            })
+            .collect())
+    }
+
+    async fn find_groups_left(&self, user_id: Uuid, lat: f64, lon: f64) -> DomainResult<Vec<Conversation>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT c.id, c.type, c.name, c.avatar_url, c.settings, c.created_at, c.updated_at,
+                   ST_Y(c.geofence_center::geometry) as geofence_lat, ST_X(c.geofence_center::geometry) as geofence_lon,
+                   c.geofence_radius_meters, c.geofence_remove_on_exit
+            FROM conversations c
+            JOIN conversation_participants p ON p.conversation_id = c.id
+            WHERE p.user_id = $1
+              AND p.role <> 'Owner'
+              AND c.geofence_remove_on_exit
+              AND NOT ST_DWithin(c.geofence_center, ST_SetSRID(ST_MakePoint($2, $3), 4326)::geography, c.geofence_radius_meters)
+            "#,
+            user_id,
+            lon,
+            lat
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Conversation {
+                id: r.id,
+                conversation_type: parse_type(&r.type_),
+                name: r.name,
+                avatar_url: r.avatar_url,
+                settings: r.settings.unwrap_or_else(|| serde_json::json!({})),
+                geofence: geofence(r.geofence_lat, r.geofence_lon, r.geofence_radius_meters, r.geofence_remove_on_exit),
+                created_at: r.created_at,
+                updated_at: r.updated_at,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,215 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::GroupCall,
+    repositories::GroupCallRepository,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresGroupCallRepository {
+    pool: PgPool,
+}
+
+impl PostgresGroupCallRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GroupCallRepository for PostgresGroupCallRepository {
+    async fn create(&self, call: &GroupCall) -> DomainResult<GroupCall> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO group_calls (id, conversation_id, room_name, started_by, is_video, started_at, ended_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, conversation_id, room_name, started_by, is_video, started_at, ended_at
+            "#,
+            call.id,
+            call.conversation_id,
+            call.room_name,
+            call.started_by,
+            call.is_video,
+            call.started_at,
+            call.ended_at
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                DomainError::Conflict("A call is already in progress in this group".to_string())
+            }
+            e => DomainError::InternalError(format!("Database error: {}", e)),
+        })?;
+
+        Ok(GroupCall {
+            id: row.id,
+            conversation_id: row.conversation_id,
+            room_name: row.room_name,
+            started_by: row.started_by,
+            is_video: row.is_video,
+            started_at: row.started_at,
+            ended_at: row.ended_at,
+        })
+    }
+
+    async fn find_active(&self, conversation_id: Uuid) -> DomainResult<Option<GroupCall>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, room_name, started_by, is_video, started_at, ended_at
+            FROM group_calls
+            WHERE conversation_id = $1 AND ended_at IS NULL
+            "#,
+            conversation_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| GroupCall {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            room_name: r.room_name,
+            started_by: r.started_by,
+            is_video: r.is_video,
+            started_at: r.started_at,
+            ended_at: r.ended_at,
+        }))
+    }
+
+    async fn find_all_active(&self) -> DomainResult<Vec<GroupCall>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, room_name, started_by, is_video, started_at, ended_at
+            FROM group_calls
+            WHERE ended_at IS NULL
+            ORDER BY started_at
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| GroupCall {
+                id: r.id,
+                conversation_id: r.conversation_id,
+                room_name: r.room_name,
+                started_by: r.started_by,
+                is_video: r.is_video,
+                started_at: r.started_at,
+                ended_at: r.ended_at,
+            })
+            .collect())
+    }
+
+    async fn add_participant(&self, call_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO group_call_participants (call_id, user_id, joined_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (call_id, user_id) DO UPDATE
+            SET joined_at = CASE WHEN group_call_participants.left_at IS NULL
+                                 THEN group_call_participants.joined_at ELSE NOW() END,
+                left_at = NULL
+            "#,
+            call_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn remove_participant(&self, call_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE group_call_participants
+            SET left_at = NOW()
+            WHERE call_id = $1 AND user_id = $2 AND left_at IS NULL
+            "#,
+            call_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_participants(&self, call_id: Uuid) -> DomainResult<Vec<Uuid>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_id
+            FROM group_call_participants
+            WHERE call_id = $1 AND left_at IS NULL
+            ORDER BY joined_at
+            "#,
+            call_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.user_id).collect())
+    }
+
+    async fn find_participants_joined_before(
+        &self,
+        call_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> DomainResult<Vec<Uuid>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_id
+            FROM group_call_participants
+            WHERE call_id = $1 AND left_at IS NULL AND joined_at < $2
+            ORDER BY joined_at
+            "#,
+            call_id,
+            before
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.user_id).collect())
+    }
+
+    async fn end_if_empty(&self, call_id: Uuid) -> DomainResult<Option<GroupCall>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE group_calls
+            SET ended_at = NOW()
+            WHERE id = $1
+              AND ended_at IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM group_call_participants
+                  WHERE call_id = $1 AND left_at IS NULL
+              )
+            RETURNING id, conversation_id, room_name, started_by, is_video, started_at, ended_at
+            "#,
+            call_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| GroupCall {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            room_name: r.room_name,
+            started_by: r.started_by,
+            is_video: r.is_video,
+            started_at: r.started_at,
+            ended_at: r.ended_at,
+        }))
+    }
+}
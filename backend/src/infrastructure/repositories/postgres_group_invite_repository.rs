@@ -0,0 +1,325 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::postgres_conversation_repository::ensure_room;
+use crate::domain::{
+    entities::{GroupInvite, JoinRequest, Participant, ParticipantRole},
+    repositories::GroupInviteRepository,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresGroupInviteRepository {
+    pool: PgPool,
+}
+
+impl PostgresGroupInviteRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GroupInviteRepository for PostgresGroupInviteRepository {
+    async fn create(&self, invite: &GroupInvite) -> DomainResult<GroupInvite> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO group_invites (id, conversation_id, token, created_by, expires_at, max_uses, use_count, requires_approval, revoked_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id, conversation_id, token, created_by, expires_at, max_uses, use_count, requires_approval, revoked_at, created_at
+            "#,
+            invite.id,
+            invite.conversation_id,
+            invite.token,
+            invite.created_by,
+            invite.expires_at,
+            invite.max_uses,
+            invite.use_count,
+            invite.requires_approval,
+            invite.revoked_at,
+            invite.created_at
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(GroupInvite {
+            id: row.id,
+            conversation_id: row.conversation_id,
+            token: row.token,
+            created_by: row.created_by,
+            expires_at: row.expires_at,
+            max_uses: row.max_uses,
+            use_count: row.use_count,
+            requires_approval: row.requires_approval,
+            revoked_at: row.revoked_at,
+            created_at: row.created_at,
+        })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<GroupInvite>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, token, created_by, expires_at, max_uses, use_count, requires_approval, revoked_at, created_at
+            FROM group_invites
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| GroupInvite {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            token: r.token,
+            created_by: r.created_by,
+            expires_at: r.expires_at,
+            max_uses: r.max_uses,
+            use_count: r.use_count,
+            requires_approval: r.requires_approval,
+            revoked_at: r.revoked_at,
+            created_at: r.created_at,
+        }))
+    }
+
+    async fn find_by_token(&self, token: &str) -> DomainResult<Option<GroupInvite>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, token, created_by, expires_at, max_uses, use_count, requires_approval, revoked_at, created_at
+            FROM group_invites
+            WHERE token = $1
+            "#,
+            token
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| GroupInvite {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            token: r.token,
+            created_by: r.created_by,
+            expires_at: r.expires_at,
+            max_uses: r.max_uses,
+            use_count: r.use_count,
+            requires_approval: r.requires_approval,
+            revoked_at: r.revoked_at,
+            created_at: r.created_at,
+        }))
+    }
+
+    async fn find_by_conversation(&self, conversation_id: Uuid) -> DomainResult<Vec<GroupInvite>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, token, created_by, expires_at, max_uses, use_count, requires_approval, revoked_at, created_at
+            FROM group_invites
+            WHERE conversation_id = $1 AND revoked_at IS NULL
+            ORDER BY created_at DESC
+            "#,
+            conversation_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| GroupInvite {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            token: r.token,
+            created_by: r.created_by,
+            expires_at: r.expires_at,
+            max_uses: r.max_uses,
+            use_count: r.use_count,
+            requires_approval: r.requires_approval,
+            revoked_at: r.revoked_at,
+            created_at: r.created_at,
+        }).collect())
+    }
+
+    async fn revoke(&self, id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE group_invites SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn consume(&self, id: Uuid) -> DomainResult<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE group_invites SET use_count = use_count + 1
+            WHERE id = $1
+              AND revoked_at IS NULL
+              AND (expires_at IS NULL OR expires_at > NOW())
+              AND (max_uses IS NULL OR use_count < max_uses)
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn create_join_request(&self, request: &JoinRequest) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO group_join_requests (conversation_id, user_id, invite_id, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (conversation_id, user_id) DO NOTHING
+            "#,
+            request.conversation_id,
+            request.user_id,
+            request.invite_id,
+            request.created_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_join_request(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<Option<JoinRequest>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT conversation_id, user_id, invite_id, created_at
+            FROM group_join_requests
+            WHERE conversation_id = $1 AND user_id = $2
+            "#,
+            conversation_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| JoinRequest {
+            conversation_id: r.conversation_id,
+            user_id: r.user_id,
+            invite_id: r.invite_id,
+            created_at: r.created_at,
+        }))
+    }
+
+    async fn find_join_requests(&self, conversation_id: Uuid) -> DomainResult<Vec<JoinRequest>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT conversation_id, user_id, invite_id, created_at
+            FROM group_join_requests
+            WHERE conversation_id = $1
+            ORDER BY created_at ASC
+            "#,
+            conversation_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| JoinRequest {
+            conversation_id: r.conversation_id,
+            user_id: r.user_id,
+            invite_id: r.invite_id,
+            created_at: r.created_at,
+        }).collect())
+    }
+
+    async fn delete_join_request(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM group_join_requests WHERE conversation_id = $1 AND user_id = $2
+            "#,
+            conversation_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn approve_join_request(&self, participant: &Participant, max_participants: Option<usize>) -> DomainResult<()> {
+        let role = match participant.role {
+            ParticipantRole::Owner => "Owner",
+            ParticipantRole::Admin => "Admin",
+            ParticipantRole::Member => "Member",
+        };
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        // Deleting the request first locks it against a concurrent review
+        let request = sqlx::query!(
+            r#"
+            DELETE FROM group_join_requests WHERE conversation_id = $1 AND user_id = $2
+            RETURNING user_id
+            "#,
+            participant.conversation_id,
+            participant.user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        if request.is_none() {
+            return Err(DomainError::NotFound("Join request not found".to_string()));
+        }
+
+        let banned = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM conversation_bans WHERE conversation_id = $1 AND user_id = $2
+            ) as "exists!"
+            "#,
+            participant.conversation_id,
+            participant.user_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+        .exists;
+
+        if banned {
+            return Err(DomainError::Conflict("User has been banned from this group".to_string()));
+        }
+
+        if let Some(max_participants) = max_participants {
+            ensure_room(&mut tx, participant.conversation_id, max_participants).await?;
+        }
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO conversation_participants (conversation_id, user_id, role, joined_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (conversation_id, user_id) DO NOTHING
+            "#,
+            participant.conversation_id,
+            participant.user_id,
+            role,
+            participant.joined_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::Conflict("User is already a member of this group".to_string()));
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+}
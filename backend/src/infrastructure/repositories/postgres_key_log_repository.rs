@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::KeyLogEntry,
+    repositories::KeyLogRepository,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresKeyLogRepository {
+    pool: PgPool,
+}
+
+impl PostgresKeyLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Appends `identity_key` to the user's key history unless it is already the current key of the
+/// account (`device_id` None) or device. Runs inside the caller's transaction, so the key and its
+/// log entry are stored together. Returns the entry if it replaced an earlier key.
+pub(crate) async fn append_identity_key(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    device_id: Option<&str>,
+    identity_key: &str,
+) -> DomainResult<Option<KeyLogEntry>> {
+    // Appends for one user queue up here instead of racing for the same head
+    sqlx::query!("SELECT id FROM users WHERE id = $1 FOR UPDATE", user_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+        .ok_or_else(|| DomainError::NotFound("User not found".to_string()))?;
+
+    let latest = sqlx::query!(
+        r#"
+        SELECT identity_key
+        FROM identity_key_log
+        WHERE user_id = $1 AND device_id IS NOT DISTINCT FROM $2
+        ORDER BY seq DESC
+        LIMIT 1
+        "#,
+        user_id,
+        device_id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+    if latest.as_ref().is_some_and(|r| r.identity_key == identity_key) {
+        return Ok(None);
+    }
+
+    let head = sqlx::query!(
+        r#"
+        SELECT seq, user_id, device_id, identity_key, prev_hash, entry_hash, created_at
+        FROM identity_key_log
+        WHERE user_id = $1
+        ORDER BY seq DESC
+        LIMIT 1
+        "#,
+        user_id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+    .map(|r| KeyLogEntry {
+        seq: r.seq,
+        user_id: r.user_id,
+        device_id: r.device_id,
+        identity_key: r.identity_key,
+        prev_hash: r.prev_hash,
+        entry_hash: r.entry_hash,
+        created_at: r.created_at,
+    });
+
+    let entry = KeyLogEntry::new(user_id, device_id.map(str::to_string), identity_key.to_string(), head.as_ref());
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO identity_key_log (user_id, device_id, identity_key, prev_hash, entry_hash, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING seq
+        "#,
+        entry.user_id,
+        entry.device_id,
+        entry.identity_key,
+        entry.prev_hash,
+        entry.entry_hash,
+        entry.created_at
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            DomainError::Conflict("Key history changed concurrently, retry".to_string())
+        }
+        e => DomainError::InternalError(format!("Database error: {}", e)),
+    })?;
+
+    // A first key for the account or a new device has no earlier key to be confused with
+    Ok(latest.map(|_| KeyLogEntry {
+        seq: row.seq,
+        ..entry
+    }))
+}
+
+#[async_trait]
+impl KeyLogRepository for PostgresKeyLogRepository {
+    async fn find_head(&self, user_id: Uuid) -> DomainResult<Option<KeyLogEntry>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT seq, user_id, device_id, identity_key, prev_hash, entry_hash, created_at
+            FROM identity_key_log
+            WHERE user_id = $1
+            ORDER BY seq DESC
+            LIMIT 1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| KeyLogEntry {
+            seq: r.seq,
+            user_id: r.user_id,
+            device_id: r.device_id,
+            identity_key: r.identity_key,
+            prev_hash: r.prev_hash,
+            entry_hash: r.entry_hash,
+            created_at: r.created_at,
+        }))
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> DomainResult<Vec<KeyLogEntry>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT seq, user_id, device_id, identity_key, prev_hash, entry_hash, created_at
+            FROM identity_key_log
+            WHERE user_id = $1
+            ORDER BY seq
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| KeyLogEntry {
+                seq: r.seq,
+                user_id: r.user_id,
+                device_id: r.device_id,
+                identity_key: r.identity_key,
+                prev_hash: r.prev_hash,
+                entry_hash: r.entry_hash,
+                created_at: r.created_at,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,277 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{LiveLocationSession, LocationPoint},
+    repositories::LiveLocationRepository,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresLiveLocationRepository {
+    pool: PgPool,
+}
+
+impl PostgresLiveLocationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn last_point(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    recorded_at: Option<DateTime<Utc>>,
+) -> Option<LocationPoint> {
+    Some(LocationPoint {
+        latitude: latitude?,
+        longitude: longitude?,
+        recorded_at: recorded_at?,
+    })
+}
+
+#[async_trait]
+impl LiveLocationRepository for PostgresLiveLocationRepository {
+    async fn create(&self, session: &LiveLocationSession) -> DomainResult<LiveLocationSession> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO live_location_sessions (id, conversation_id, user_id, started_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, conversation_id, user_id, started_at, expires_at, stopped_at,
+                      last_latitude, last_longitude, last_updated_at
+            "#,
+            session.id,
+            session.conversation_id,
+            session.user_id,
+            session.started_at,
+            session.expires_at
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                DomainError::Conflict("You are already sharing your live location in this conversation".to_string())
+            }
+            e => DomainError::InternalError(format!("Database error: {}", e)),
+        })?;
+
+        Ok(LiveLocationSession {
+            id: row.id,
+            conversation_id: row.conversation_id,
+            user_id: row.user_id,
+            started_at: row.started_at,
+            expires_at: row.expires_at,
+            stopped_at: row.stopped_at,
+            last_point: last_point(row.last_latitude, row.last_longitude, row.last_updated_at),
+        })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<LiveLocationSession>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, user_id, started_at, expires_at, stopped_at,
+                   last_latitude, last_longitude, last_updated_at
+            FROM live_location_sessions
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| LiveLocationSession {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            user_id: r.user_id,
+            started_at: r.started_at,
+            expires_at: r.expires_at,
+            stopped_at: r.stopped_at,
+            last_point: last_point(r.last_latitude, r.last_longitude, r.last_updated_at),
+        }))
+    }
+
+    async fn find_active_by_conversation(&self, conversation_id: Uuid) -> DomainResult<Vec<LiveLocationSession>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, user_id, started_at, expires_at, stopped_at,
+                   last_latitude, last_longitude, last_updated_at
+            FROM live_location_sessions
+            WHERE conversation_id = $1 AND stopped_at IS NULL AND expires_at > NOW()
+            ORDER BY started_at
+            "#,
+            conversation_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| LiveLocationSession {
+                id: r.id,
+                conversation_id: r.conversation_id,
+                user_id: r.user_id,
+                started_at: r.started_at,
+                expires_at: r.expires_at,
+                stopped_at: r.stopped_at,
+                last_point: last_point(r.last_latitude, r.last_longitude, r.last_updated_at),
+            })
+            .collect())
+    }
+
+    async fn record_point(&self, session_id: Uuid, point: &LocationPoint, min_interval_seconds: i64) -> DomainResult<bool> {
+        let result = sqlx::query!(
+            r#"
+            WITH updated AS (
+                UPDATE live_location_sessions
+                SET last_latitude = $2, last_longitude = $3, last_updated_at = $4
+                WHERE id = $1
+                  AND stopped_at IS NULL
+                  AND expires_at > $4
+                  AND (last_updated_at IS NULL OR last_updated_at <= $4 - make_interval(secs => $5))
+                RETURNING id
+            )
+            INSERT INTO live_location_points (session_id, latitude, longitude, recorded_at)
+            SELECT id, $2, $3, $4 FROM updated
+            "#,
+            session_id,
+            point.latitude,
+            point.longitude,
+            point.recorded_at,
+            min_interval_seconds as f64
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_points(&self, session_id: Uuid, since: DateTime<Utc>) -> DomainResult<Vec<LocationPoint>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT latitude, longitude, recorded_at
+            FROM live_location_points
+            WHERE session_id = $1 AND recorded_at >= $2
+            ORDER BY recorded_at
+            "#,
+            session_id,
+            since
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| LocationPoint {
+                latitude: r.latitude,
+                longitude: r.longitude,
+                recorded_at: r.recorded_at,
+            })
+            .collect())
+    }
+
+    async fn purge_points(&self, before: DateTime<Utc>) -> DomainResult<u64> {
+        let result = sqlx::query!(
+            "DELETE FROM live_location_points WHERE recorded_at < $1",
+            before
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn stop(&self, id: Uuid) -> DomainResult<Option<LiveLocationSession>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE live_location_sessions
+            SET stopped_at = LEAST(expires_at, NOW())
+            WHERE id = $1 AND stopped_at IS NULL
+            RETURNING id, conversation_id, user_id, started_at, expires_at, stopped_at,
+                      last_latitude, last_longitude, last_updated_at
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| LiveLocationSession {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            user_id: r.user_id,
+            started_at: r.started_at,
+            expires_at: r.expires_at,
+            stopped_at: r.stopped_at,
+            last_point: last_point(r.last_latitude, r.last_longitude, r.last_updated_at),
+        }))
+    }
+
+    async fn stop_for_participant(&self, conversation_id: Uuid, user_id: Uuid) -> DomainResult<Option<LiveLocationSession>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE live_location_sessions
+            SET stopped_at = LEAST(expires_at, NOW())
+            WHERE conversation_id = $1 AND user_id = $2 AND stopped_at IS NULL
+            RETURNING id, conversation_id, user_id, started_at, expires_at, stopped_at,
+                      last_latitude, last_longitude, last_updated_at
+            "#,
+            conversation_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| LiveLocationSession {
+            id: r.id,
+            conversation_id: r.conversation_id,
+            user_id: r.user_id,
+            started_at: r.started_at,
+            expires_at: r.expires_at,
+            stopped_at: r.stopped_at,
+            last_point: last_point(r.last_latitude, r.last_longitude, r.last_updated_at),
+        }))
+    }
+
+    async fn stop_expired(&self, now: DateTime<Utc>) -> DomainResult<Vec<LiveLocationSession>> {
+        let rows = sqlx::query!(
+            r#"
+            UPDATE live_location_sessions s
+            SET stopped_at = LEAST(s.expires_at, $1)
+            WHERE s.stopped_at IS NULL
+              AND (
+                  s.expires_at <= $1
+                  OR NOT EXISTS (
+                      SELECT 1 FROM conversation_participants p
+                      WHERE p.conversation_id = s.conversation_id AND p.user_id = s.user_id
+                  )
+              )
+            RETURNING s.id, s.conversation_id, s.user_id, s.started_at, s.expires_at, s.stopped_at,
+                      s.last_latitude, s.last_longitude, s.last_updated_at
+            "#,
+            now
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| LiveLocationSession {
+                id: r.id,
+                conversation_id: r.conversation_id,
+                user_id: r.user_id,
+                started_at: r.started_at,
+                expires_at: r.expires_at,
+                stopped_at: r.stopped_at,
+                last_point: last_point(r.last_latitude, r.last_longitude, r.last_updated_at),
+            })
+            .collect())
+    }
+}
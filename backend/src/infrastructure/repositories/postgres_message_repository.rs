@@ -1,9 +1,11 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
+use super::postgres_sync_repository::insert_event;
 use crate::domain::{
-    entities::{Message, MessageType},
+    entities::{Message, MessageType, SyncEvent, ThreadSummary},
     repositories::MessageRepository,
     DomainError, DomainResult,
 };
@@ -18,46 +20,112 @@ impl PostgresMessageRepository {
     }
 }
 
+/// Inserts a message through `executor`, so it can join a transaction.
+async fn insert_message<'e>(executor: impl PgExecutor<'e>, message: &Message) -> DomainResult<Message> {
+    let message_type = match message.message_type {
+        MessageType::Text => "Text",
+        MessageType::Image => "Image",
+        MessageType::Video => "Video",
+        MessageType::Audio => "Audio",
+        MessageType::File => "File",
+        MessageType::System => "System",
+        MessageType::CallSignal => "CallSignal",
+    };
+
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO messages (id, client_message_id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, self_destruct_seconds, self_destruct_on_read, forwarded_from_id, forward_count, sender_key_epoch, created_at, is_deleted)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+        RETURNING id, client_message_id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, self_destruct_seconds, self_destruct_on_read, forwarded_from_id, forward_count, sender_key_epoch, created_at, is_deleted
+        "#,
+        message.id,
+        message.client_message_id,
+        message.conversation_id,
+        message.sender_id,
+        message.content,
+        message_type,
+        message.is_encrypted,
+        message.reply_to_id,
+        message.self_destruct_at,
+        message.self_destruct_seconds,
+        message.self_destruct_on_read,
+        message.forwarded_from_id,
+        message.forward_count,
+        message.sender_key_epoch,
+        message.created_at,
+        message.is_deleted
+    )
+    .fetch_one(executor)
+    .await
+    .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+    Ok(Message {
+        id: row.id,
+        client_message_id: row.client_message_id,
+        conversation_id: row.conversation_id,
+        sender_id: row.sender_id,
+        content: row.content,
+        message_type: match row.type_.as_deref() {
+            Some("Image") => MessageType::Image,
+            Some("Video") => MessageType::Video,
+            Some("Audio") => MessageType::Audio,
+            Some("File") => MessageType::File,
+            Some("System") => MessageType::System,
+            Some("CallSignal") => MessageType::CallSignal,
+            _ => MessageType::Text,
+        },
+        is_encrypted: row.is_encrypted.unwrap_or(true),
+        reply_to_id: row.reply_to_id,
+        self_destruct_at: row.self_destruct_at,
+        self_destruct_seconds: row.self_destruct_seconds,
+        self_destruct_on_read: row.self_destruct_on_read,
+        forwarded_from_id: row.forwarded_from_id,
+        forward_count: row.forward_count,
+        sender_key_epoch: row.sender_key_epoch,
+        created_at: row.created_at,
+        is_deleted: row.is_deleted.unwrap_or(false),
+    })
+}
+
 #[async_trait]
 impl MessageRepository for PostgresMessageRepository {
     async fn create(&self, message: &Message) -> DomainResult<Message> {
-        let message_type = match message.message_type {
-            MessageType::Text => "Text",
-            MessageType::Image => "Image",
-            MessageType::Video => "Video",
-            MessageType::Audio => "Audio",
-            MessageType::File => "File",
-            MessageType::System => "System",
-            MessageType::CallSignal => "CallSignal",
-        };
+        insert_message(&self.pool, message).await
+    }
+
+    async fn create_with_event(&self, message: &Message, event: &SyncEvent) -> DomainResult<(Message, SyncEvent)> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
 
+        let message = insert_message(&mut *tx, message).await?;
+        let event = insert_event(&mut *tx, event).await?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok((message, event))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Message>> {
         let row = sqlx::query!(
             r#"
-            INSERT INTO messages (id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, created_at, is_deleted)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            RETURNING id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, created_at, is_deleted
+            SELECT id, client_message_id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, self_destruct_seconds, self_destruct_on_read, forwarded_from_id, forward_count, sender_key_epoch, created_at, is_deleted
+            FROM messages
+            WHERE id = $1
             "#,
-            message.id,
-            message.conversation_id,
-            message.sender_id,
-            message.content,
-            message_type,
-            message.is_encrypted,
-            message.reply_to_id,
-            message.self_destruct_at,
-            message.created_at,
-            message.is_deleted
+            id
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
         .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
 
-        Ok(Message {
-            id: row.id,
-            conversation_id: row.conversation_id,
-            sender_id: row.sender_id,
-            content: row.content,
-            message_type: match row.type_.as_deref() {
+        Ok(row.map(|r| Message {
+            id: r.id,
+            client_message_id: r.client_message_id,
+            conversation_id: r.conversation_id,
+            sender_id: r.sender_id,
+            content: r.content,
+            message_type: match r.type_.as_deref() {
                 Some("Image") => MessageType::Image,
                 Some("Video") => MessageType::Video,
                 Some("Audio") => MessageType::Audio,
@@ -66,22 +134,28 @@ impl MessageRepository for PostgresMessageRepository {
                 Some("CallSignal") => MessageType::CallSignal,
                 _ => MessageType::Text,
             },
-            is_encrypted: row.is_encrypted.unwrap_or(true),
-            reply_to_id: row.reply_to_id,
-            self_destruct_at: row.self_destruct_at,
-            created_at: row.created_at,
-            is_deleted: row.is_deleted.unwrap_or(false),
-        })
+            is_encrypted: r.is_encrypted.unwrap_or(true),
+            reply_to_id: r.reply_to_id,
+            self_destruct_at: r.self_destruct_at,
+            self_destruct_seconds: r.self_destruct_seconds,
+            self_destruct_on_read: r.self_destruct_on_read,
+            forwarded_from_id: r.forwarded_from_id,
+            forward_count: r.forward_count,
+            sender_key_epoch: r.sender_key_epoch,
+            created_at: r.created_at,
+            is_deleted: r.is_deleted.unwrap_or(false),
+        }))
     }
 
-    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Message>> {
+    async fn find_by_client_id(&self, sender_id: Uuid, client_message_id: &str) -> DomainResult<Option<Message>> {
         let row = sqlx::query!(
             r#"
-            SELECT id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, created_at, is_deleted
+            SELECT id, client_message_id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, self_destruct_seconds, self_destruct_on_read, forwarded_from_id, forward_count, sender_key_epoch, created_at, is_deleted
             FROM messages
-            WHERE id = $1
+            WHERE sender_id = $1 AND client_message_id = $2
             "#,
-            id
+            sender_id,
+            client_message_id
         )
         .fetch_optional(&self.pool)
         .await
@@ -89,6 +163,7 @@ impl MessageRepository for PostgresMessageRepository {
 
         Ok(row.map(|r| Message {
             id: r.id,
+            client_message_id: r.client_message_id,
             conversation_id: r.conversation_id,
             sender_id: r.sender_id,
             content: r.content,
@@ -104,6 +179,11 @@ impl MessageRepository for PostgresMessageRepository {
             is_encrypted: r.is_encrypted.unwrap_or(true),
             reply_to_id: r.reply_to_id,
             self_destruct_at: r.self_destruct_at,
+            self_destruct_seconds: r.self_destruct_seconds,
+            self_destruct_on_read: r.self_destruct_on_read,
+            forwarded_from_id: r.forwarded_from_id,
+            forward_count: r.forward_count,
+            sender_key_epoch: r.sender_key_epoch,
             created_at: r.created_at,
             is_deleted: r.is_deleted.unwrap_or(false),
         }))
@@ -117,7 +197,7 @@ impl MessageRepository for PostgresMessageRepository {
     ) -> DomainResult<Vec<Message>> {
         let rows = sqlx::query!(
             r#"
-            SELECT id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, created_at, is_deleted
+            SELECT id, client_message_id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, self_destruct_seconds, self_destruct_on_read, forwarded_from_id, forward_count, sender_key_epoch, created_at, is_deleted
             FROM messages
             WHERE conversation_id = $1 AND (is_deleted = false OR is_deleted IS NULL)
             ORDER BY created_at DESC
@@ -135,6 +215,138 @@ impl MessageRepository for PostgresMessageRepository {
             .into_iter()
             .map(|r| Message {
                 id: r.id,
+                client_message_id: r.client_message_id,
+                conversation_id: r.conversation_id,
+                sender_id: r.sender_id,
+                content: r.content,
+                message_type: match r.type_.as_deref() {
+                    Some("Image") => MessageType::Image,
+                    Some("Video") => MessageType::Video,
+                    Some("Audio") => MessageType::Audio,
+                    Some("File") => MessageType::File,
+                    Some("System") => MessageType::System,
+                    Some("CallSignal") => MessageType::CallSignal,
+                    _ => MessageType::Text,
+                },
+                is_encrypted: r.is_encrypted.unwrap_or(true),
+                reply_to_id: r.reply_to_id,
+                self_destruct_at: r.self_destruct_at,
+                self_destruct_seconds: r.self_destruct_seconds,
+                self_destruct_on_read: r.self_destruct_on_read,
+                forwarded_from_id: r.forwarded_from_id,
+                forward_count: r.forward_count,
+                sender_key_epoch: r.sender_key_epoch,
+                created_at: r.created_at,
+                is_deleted: r.is_deleted.unwrap_or(false),
+            })
+            .collect())
+    }
+
+    async fn find_history(
+        &self,
+        conversation_id: Uuid,
+        before: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> DomainResult<Vec<(Message, ThreadSummary)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT m.id, m.client_message_id, m.conversation_id, m.sender_id, m.content, m.type, m.is_encrypted, m.reply_to_id, m.self_destruct_at, m.self_destruct_seconds, m.self_destruct_on_read, m.forwarded_from_id, m.forward_count, m.sender_key_epoch, m.created_at, m.is_deleted,
+                   t.reply_count as "reply_count!", t.last_reply_at, t.last_replier_id
+            FROM messages m
+            LEFT JOIN LATERAL (
+                SELECT COUNT(*) AS reply_count,
+                       MAX(r.created_at) AS last_reply_at,
+                       (ARRAY_AGG(r.sender_id ORDER BY r.created_at DESC))[1] AS last_replier_id
+                FROM messages r
+                WHERE r.reply_to_id = m.id AND (r.is_deleted = false OR r.is_deleted IS NULL)
+                  AND (r.self_destruct_at IS NULL OR r.self_destruct_at > NOW())
+            ) t ON TRUE
+            WHERE m.conversation_id = $1
+              AND (m.is_deleted = false OR m.is_deleted IS NULL)
+              AND (m.self_destruct_at IS NULL OR m.self_destruct_at > NOW())
+              AND ($2::timestamptz IS NULL OR (m.created_at, m.id) < ($2, $3::uuid))
+            ORDER BY m.created_at DESC, m.id DESC
+            LIMIT $4
+            "#,
+            conversation_id,
+            before.map(|(created_at, _)| created_at),
+            before.map(|(_, id)| id),
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let message = Message {
+                    id: r.id,
+                    client_message_id: r.client_message_id,
+                    conversation_id: r.conversation_id,
+                    sender_id: r.sender_id,
+                    content: r.content,
+                    message_type: match r.type_.as_deref() {
+                        Some("Image") => MessageType::Image,
+                        Some("Video") => MessageType::Video,
+                        Some("Audio") => MessageType::Audio,
+                        Some("File") => MessageType::File,
+                        Some("System") => MessageType::System,
+                        Some("CallSignal") => MessageType::CallSignal,
+                        _ => MessageType::Text,
+                    },
+                    is_encrypted: r.is_encrypted.unwrap_or(true),
+                    reply_to_id: r.reply_to_id,
+                    self_destruct_at: r.self_destruct_at,
+                    self_destruct_seconds: r.self_destruct_seconds,
+                    self_destruct_on_read: r.self_destruct_on_read,
+                    forwarded_from_id: r.forwarded_from_id,
+                    forward_count: r.forward_count,
+                    sender_key_epoch: r.sender_key_epoch,
+                    created_at: r.created_at,
+                    is_deleted: r.is_deleted.unwrap_or(false),
+                };
+                let thread = ThreadSummary {
+                    reply_count: r.reply_count,
+                    last_reply_at: r.last_reply_at,
+                    last_replier_id: r.last_replier_id,
+                };
+                (message, thread)
+            })
+            .collect())
+    }
+
+    async fn find_replies(
+        &self,
+        root_id: Uuid,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> DomainResult<Vec<Message>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, client_message_id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, self_destruct_seconds, self_destruct_on_read, forwarded_from_id, forward_count, sender_key_epoch, created_at, is_deleted
+            FROM messages
+            WHERE reply_to_id = $1
+              AND (is_deleted = false OR is_deleted IS NULL)
+              AND (self_destruct_at IS NULL OR self_destruct_at > NOW())
+              AND ($2::timestamptz IS NULL OR (created_at, id) > ($2, $3::uuid))
+            ORDER BY created_at ASC, id ASC
+            LIMIT $4
+            "#,
+            root_id,
+            after.map(|(created_at, _)| created_at),
+            after.map(|(_, id)| id),
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Message {
+                id: r.id,
+                client_message_id: r.client_message_id,
                 conversation_id: r.conversation_id,
                 sender_id: r.sender_id,
                 content: r.content,
@@ -150,6 +362,11 @@ impl MessageRepository for PostgresMessageRepository {
                 is_encrypted: r.is_encrypted.unwrap_or(true),
                 reply_to_id: r.reply_to_id,
                 self_destruct_at: r.self_destruct_at,
+                self_destruct_seconds: r.self_destruct_seconds,
+                self_destruct_on_read: r.self_destruct_on_read,
+                forwarded_from_id: r.forwarded_from_id,
+                forward_count: r.forward_count,
+                sender_key_epoch: r.sender_key_epoch,
                 created_at: r.created_at,
                 is_deleted: r.is_deleted.unwrap_or(false),
             })
@@ -172,7 +389,7 @@ impl MessageRepository for PostgresMessageRepository {
             UPDATE messages
             SET content = $2, type = $3, is_encrypted = $4, is_deleted = $5
             WHERE id = $1
-            RETURNING id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, created_at, is_deleted
+            RETURNING id, client_message_id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, self_destruct_seconds, self_destruct_on_read, forwarded_from_id, forward_count, sender_key_epoch, created_at, is_deleted
             "#,
             message.id,
             message.content,
@@ -186,6 +403,7 @@ impl MessageRepository for PostgresMessageRepository {
 
         Ok(Message {
             id: row.id,
+            client_message_id: row.client_message_id,
             conversation_id: row.conversation_id,
             sender_id: row.sender_id,
             content: row.content,
@@ -201,6 +419,11 @@ impl MessageRepository for PostgresMessageRepository {
             is_encrypted: row.is_encrypted.unwrap_or(true),
             reply_to_id: row.reply_to_id,
             self_destruct_at: row.self_destruct_at,
+            self_destruct_seconds: row.self_destruct_seconds,
+            self_destruct_on_read: row.self_destruct_on_read,
+            forwarded_from_id: row.forwarded_from_id,
+            forward_count: row.forward_count,
+            sender_key_epoch: row.sender_key_epoch,
             created_at: row.created_at,
             is_deleted: row.is_deleted.unwrap_or(false),
         })
@@ -220,17 +443,110 @@ impl MessageRepository for PostgresMessageRepository {
         Ok(())
     }
 
-    async fn delete_expired(&self) -> DomainResult<u64> {
+    async fn delete_expired(&self, limit: i64) -> DomainResult<Vec<(Uuid, Uuid)>> {
+        let rows = sqlx::query!(
+            r#"
+            DELETE FROM messages
+            WHERE id IN (
+                SELECT id FROM messages
+                WHERE self_destruct_at <= NOW()
+                ORDER BY self_destruct_at
+                LIMIT $1
+            )
+            RETURNING id, conversation_id
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| (r.id, r.conversation_id)).collect())
+    }
+
+    async fn delete_if_expired(&self, id: Uuid) -> DomainResult<bool> {
         let result = sqlx::query!(
             r#"
-            DELETE FROM messages WHERE self_destruct_at < NOW()
-            "#
+            DELETE FROM messages WHERE id = $1 AND self_destruct_at <= NOW()
+            "#,
+            id
         )
         .execute(&self.pool)
         .await
         .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
 
-        Ok(result.rows_affected())
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn start_self_destruct(&self, id: Uuid, destruct_at: DateTime<Utc>) -> DomainResult<bool> {
+        // Only the first qualifying read arms the timer
+        let result = sqlx::query!(
+            r#"
+            UPDATE messages SET self_destruct_at = $2
+            WHERE id = $1 AND self_destruct_at IS NULL
+            "#,
+            id,
+            destruct_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_scheduled_for_destruction(
+        &self,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> DomainResult<Vec<Message>> {
+        let (after_at, after_id) = after.unzip();
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, client_message_id, conversation_id, sender_id, content, type, is_encrypted, reply_to_id, self_destruct_at, self_destruct_seconds, self_destruct_on_read, forwarded_from_id, forward_count, sender_key_epoch, created_at, is_deleted
+            FROM messages
+            WHERE self_destruct_at IS NOT NULL
+              AND ($1::timestamptz IS NULL OR (self_destruct_at, id) > ($1, $2))
+            ORDER BY self_destruct_at ASC, id ASC
+            LIMIT $3
+            "#,
+            after_at,
+            after_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Message {
+                id: r.id,
+                client_message_id: r.client_message_id,
+                conversation_id: r.conversation_id,
+                sender_id: r.sender_id,
+                content: r.content,
+                message_type: match r.type_.as_deref() {
+                    Some("Image") => MessageType::Image,
+                    Some("Video") => MessageType::Video,
+                    Some("Audio") => MessageType::Audio,
+                    Some("File") => MessageType::File,
+                    Some("System") => MessageType::System,
+                    Some("CallSignal") => MessageType::CallSignal,
+                    _ => MessageType::Text,
+                },
+                is_encrypted: r.is_encrypted.unwrap_or(true),
+                reply_to_id: r.reply_to_id,
+                self_destruct_at: r.self_destruct_at,
+                self_destruct_seconds: r.self_destruct_seconds,
+                self_destruct_on_read: r.self_destruct_on_read,
+                forwarded_from_id: r.forwarded_from_id,
+                forward_count: r.forward_count,
+                sender_key_epoch: r.sender_key_epoch,
+                created_at: r.created_at,
+                is_deleted: r.is_deleted.unwrap_or(false),
+            })
+            .collect())
     }
 
     async fn mark_as_read(&self, message_id: Uuid, user_id: Uuid) -> DomainResult<()> {
@@ -250,6 +566,261 @@ impl MessageRepository for PostgresMessageRepository {
         Ok(())
     }
 
+    async fn record_channel_view(&self, conversation_id: Uuid, user_id: Uuid, read_up_to: DateTime<Utc>) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            WITH cursor AS (
+                UPDATE conversation_participants p
+                SET last_read_at = $3
+                FROM (
+                    SELECT COALESCE(last_read_at, joined_at) AS previous
+                    FROM conversation_participants
+                    WHERE conversation_id = $1 AND user_id = $2
+                ) old
+                WHERE p.conversation_id = $1 AND p.user_id = $2
+                  AND (p.last_read_at IS NULL OR p.last_read_at < $3)
+                RETURNING old.previous
+            )
+            UPDATE messages SET view_count = view_count + 1
+            FROM cursor
+            WHERE messages.conversation_id = $1
+              AND messages.created_at <= $3
+              AND messages.created_at > cursor.previous
+            "#,
+            conversation_id,
+            user_id,
+            read_up_to
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_view_counts(&self, conversation_id: Uuid, message_ids: &[Uuid]) -> DomainResult<Vec<(Uuid, i64)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, view_count FROM messages
+            WHERE conversation_id = $1 AND id = ANY($2)
+            "#,
+            conversation_id,
+            message_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| (r.id, r.view_count)).collect())
+    }
+
+    async fn pin(&self, conversation_id: Uuid, message_id: Uuid, pinned_by: Uuid, max_pinned: i64) -> DomainResult<bool> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        // Locking the conversation serializes concurrent pins, so the limit check holds
+        sqlx::query!(
+            r#"
+            SELECT id FROM conversations WHERE id = $1 FOR UPDATE
+            "#,
+            conversation_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let pinned = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM pinned_messages WHERE conversation_id = $1
+            "#,
+            conversation_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+        .count;
+
+        if pinned >= max_pinned {
+            return Err(DomainError::ValidationError(format!(
+                "A conversation can have at most {} pinned messages",
+                max_pinned
+            )));
+        }
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO pinned_messages (message_id, conversation_id, pinned_by, pinned_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (message_id) DO NOTHING
+            "#,
+            message_id,
+            conversation_id,
+            pinned_by
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn unpin(&self, message_id: Uuid) -> DomainResult<bool> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM pinned_messages WHERE message_id = $1
+            "#,
+            message_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_pinned(&self, conversation_id: Uuid) -> DomainResult<Vec<Message>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT m.id, m.client_message_id, m.conversation_id, m.sender_id, m.content, m.type, m.is_encrypted, m.reply_to_id, m.self_destruct_at, m.self_destruct_seconds, m.self_destruct_on_read, m.forwarded_from_id, m.forward_count, m.sender_key_epoch, m.created_at, m.is_deleted
+            FROM pinned_messages p
+            JOIN messages m ON m.id = p.message_id
+            WHERE p.conversation_id = $1 AND (m.is_deleted = false OR m.is_deleted IS NULL)
+            ORDER BY p.pinned_at DESC
+            "#,
+            conversation_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Message {
+                id: r.id,
+                client_message_id: r.client_message_id,
+                conversation_id: r.conversation_id,
+                sender_id: r.sender_id,
+                content: r.content,
+                message_type: match r.type_.as_deref() {
+                    Some("Image") => MessageType::Image,
+                    Some("Video") => MessageType::Video,
+                    Some("Audio") => MessageType::Audio,
+                    Some("File") => MessageType::File,
+                    Some("System") => MessageType::System,
+                    Some("CallSignal") => MessageType::CallSignal,
+                    _ => MessageType::Text,
+                },
+                is_encrypted: r.is_encrypted.unwrap_or(true),
+                reply_to_id: r.reply_to_id,
+                self_destruct_at: r.self_destruct_at,
+                self_destruct_seconds: r.self_destruct_seconds,
+                self_destruct_on_read: r.self_destruct_on_read,
+                forwarded_from_id: r.forwarded_from_id,
+                forward_count: r.forward_count,
+                sender_key_epoch: r.sender_key_epoch,
+                created_at: r.created_at,
+                is_deleted: r.is_deleted.unwrap_or(false),
+            })
+            .collect())
+    }
+
+    async fn star(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO starred_messages (user_id, message_id, starred_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (user_id, message_id) DO NOTHING
+            "#,
+            user_id,
+            message_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn unstar(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM starred_messages WHERE user_id = $1 AND message_id = $2
+            "#,
+            user_id,
+            message_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_starred(
+        &self,
+        user_id: Uuid,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> DomainResult<Vec<(Message, DateTime<Utc>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT m.id, m.client_message_id, m.conversation_id, m.sender_id, m.content, m.type, m.is_encrypted, m.reply_to_id, m.self_destruct_at, m.self_destruct_seconds, m.self_destruct_on_read, m.forwarded_from_id, m.forward_count, m.sender_key_epoch, m.created_at, m.is_deleted,
+                   s.starred_at
+            FROM starred_messages s
+            JOIN messages m ON m.id = s.message_id
+            JOIN conversation_participants cp ON cp.conversation_id = m.conversation_id AND cp.user_id = s.user_id
+            WHERE s.user_id = $1
+              AND (m.is_deleted = false OR m.is_deleted IS NULL)
+              AND ($2::timestamptz IS NULL OR s.starred_at < $2)
+            ORDER BY s.starred_at DESC
+            LIMIT $3
+            "#,
+            user_id,
+            before,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let starred_at = r.starred_at;
+                let message = Message {
+                    id: r.id,
+                    client_message_id: r.client_message_id,
+                    conversation_id: r.conversation_id,
+                    sender_id: r.sender_id,
+                    content: r.content,
+                    message_type: match r.type_.as_deref() {
+                        Some("Image") => MessageType::Image,
+                        Some("Video") => MessageType::Video,
+                        Some("Audio") => MessageType::Audio,
+                        Some("File") => MessageType::File,
+                        Some("System") => MessageType::System,
+                        Some("CallSignal") => MessageType::CallSignal,
+                        _ => MessageType::Text,
+                    },
+                    is_encrypted: r.is_encrypted.unwrap_or(true),
+                    reply_to_id: r.reply_to_id,
+                    self_destruct_at: r.self_destruct_at,
+                    self_destruct_seconds: r.self_destruct_seconds,
+                    self_destruct_on_read: r.self_destruct_on_read,
+                    forwarded_from_id: r.forwarded_from_id,
+                    forward_count: r.forward_count,
+                    sender_key_epoch: r.sender_key_epoch,
+                    created_at: r.created_at,
+                    is_deleted: r.is_deleted.unwrap_or(false),
+                };
+                (message, starred_at)
+            })
+            .collect())
+    }
+
     async fn add_reaction(&self, message_id: Uuid, user_id: Uuid, reaction: &str) -> DomainResult<()> {
         sqlx::query!(
             r#"
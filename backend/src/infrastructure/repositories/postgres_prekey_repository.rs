@@ -0,0 +1,252 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::postgres_key_log_repository::append_identity_key;
+use crate::domain::{
+    entities::{DeviceKeys, KeyLogEntry, OneTimePreKey, PreKeyBundle, SignedPreKey},
+    repositories::PreKeyRepository,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresPreKeyRepository {
+    pool: PgPool,
+}
+
+impl PostgresPreKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PreKeyRepository for PostgresPreKeyRepository {
+    async fn upsert_device(&self, keys: &DeviceKeys) -> DomainResult<Option<KeyLogEntry>> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM one_time_prekeys o
+            USING device_keys d
+            WHERE d.user_id = $1 AND d.device_id = $2 AND d.identity_key <> $3
+              AND o.user_id = d.user_id AND o.device_id = d.device_id
+            "#,
+            keys.user_id,
+            keys.device_id,
+            keys.identity_key
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO device_keys (user_id, device_id, identity_key, signed_prekey_id, signed_prekey, signed_prekey_signature, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (user_id, device_id) DO UPDATE
+            SET identity_key = EXCLUDED.identity_key,
+                signed_prekey_id = EXCLUDED.signed_prekey_id,
+                signed_prekey = EXCLUDED.signed_prekey,
+                signed_prekey_signature = EXCLUDED.signed_prekey_signature,
+                updated_at = EXCLUDED.updated_at
+            "#,
+            keys.user_id,
+            keys.device_id,
+            keys.identity_key,
+            keys.signed_prekey.key_id,
+            keys.signed_prekey.public_key,
+            keys.signed_prekey.signature,
+            keys.created_at,
+            keys.updated_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let entry = append_identity_key(&mut tx, keys.user_id, Some(&keys.device_id), &keys.identity_key).await?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(entry)
+    }
+
+    async fn find_device(&self, user_id: Uuid, device_id: &str) -> DomainResult<Option<DeviceKeys>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT user_id, device_id, identity_key, signed_prekey_id, signed_prekey, signed_prekey_signature, created_at, updated_at
+            FROM device_keys
+            WHERE user_id = $1 AND device_id = $2
+            "#,
+            user_id,
+            device_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| DeviceKeys {
+            user_id: r.user_id,
+            device_id: r.device_id,
+            identity_key: r.identity_key,
+            signed_prekey: SignedPreKey {
+                key_id: r.signed_prekey_id,
+                public_key: r.signed_prekey,
+                signature: r.signed_prekey_signature,
+            },
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }))
+    }
+
+    async fn add_one_time_prekeys(&self, user_id: Uuid, device_id: &str, keys: &[OneTimePreKey], max_keys: i64) -> DomainResult<bool> {
+        let key_ids: Vec<i32> = keys.iter().map(|key| key.key_id).collect();
+        let public_keys: Vec<String> = keys.iter().map(|key| key.public_key.clone()).collect();
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        // Locking the device serializes concurrent uploads, so the count below stays accurate
+        sqlx::query!(
+            "SELECT user_id FROM device_keys WHERE user_id = $1 AND device_id = $2 FOR UPDATE",
+            user_id,
+            device_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+        .ok_or_else(|| DomainError::NotFound("Device keys not registered".to_string()))?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO one_time_prekeys (user_id, device_id, key_id, public_key)
+            SELECT $1, $2, k.key_id, k.public_key
+            FROM UNNEST($3::INTEGER[], $4::TEXT[]) AS k(key_id, public_key)
+            ON CONFLICT (user_id, device_id, key_id) DO NOTHING
+            "#,
+            user_id,
+            device_id,
+            &key_ids,
+            &public_keys
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        if result.rows_affected() > 0 {
+            let stored = sqlx::query!(
+                r#"
+                SELECT COUNT(*) as "count!" FROM one_time_prekeys WHERE user_id = $1 AND device_id = $2
+                "#,
+                user_id,
+                device_id
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+            // Dropping the transaction rolls the insert back
+            if stored.count > max_keys {
+                return Ok(false);
+            }
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(true)
+    }
+
+    async fn count_one_time_prekeys(&self, user_id: Uuid, device_id: &str) -> DomainResult<i64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM one_time_prekeys WHERE user_id = $1 AND device_id = $2
+            "#,
+            user_id,
+            device_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.count)
+    }
+
+    async fn claim_bundles(&self, user_id: Uuid) -> DomainResult<Vec<PreKeyBundle>> {
+        // SKIP LOCKED lets concurrent claims for the same device each take a different key
+        let rows = sqlx::query!(
+            r#"
+            WITH claimed AS (
+                DELETE FROM one_time_prekeys o
+                WHERE (o.user_id, o.device_id, o.key_id) IN (
+                    SELECT p.user_id, p.device_id, p.key_id
+                    FROM device_keys d
+                    CROSS JOIN LATERAL (
+                        SELECT user_id, device_id, key_id
+                        FROM one_time_prekeys
+                        WHERE user_id = d.user_id AND device_id = d.device_id
+                        ORDER BY key_id
+                        LIMIT 1
+                        FOR UPDATE SKIP LOCKED
+                    ) p
+                    WHERE d.user_id = $1
+                )
+                RETURNING o.device_id, o.key_id, o.public_key
+            )
+            SELECT d.user_id, d.device_id, d.identity_key, d.signed_prekey_id, d.signed_prekey, d.signed_prekey_signature,
+                   c.key_id AS "one_time_key_id?", c.public_key AS "one_time_public_key?"
+            FROM device_keys d
+            LEFT JOIN claimed c ON c.device_id = d.device_id
+            WHERE d.user_id = $1
+            ORDER BY d.device_id
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| PreKeyBundle {
+            user_id: r.user_id,
+            device_id: r.device_id,
+            identity_key: r.identity_key,
+            signed_prekey: SignedPreKey {
+                key_id: r.signed_prekey_id,
+                public_key: r.signed_prekey,
+                signature: r.signed_prekey_signature,
+            },
+            one_time_prekey: match (r.one_time_key_id, r.one_time_public_key) {
+                (Some(key_id), Some(public_key)) => Some(OneTimePreKey { key_id, public_key }),
+                _ => None,
+            },
+        }).collect())
+    }
+
+    async fn record_claim(&self, claimer_id: Uuid, target_id: Uuid, window_seconds: i64) -> DomainResult<i64> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO prekey_claim_limits (claimer_id, target_id, window_started_at, claims)
+            VALUES ($1, $2, NOW(), 1)
+            ON CONFLICT (claimer_id, target_id) DO UPDATE SET
+                claims = CASE
+                    WHEN prekey_claim_limits.window_started_at <= NOW() - make_interval(secs => $3) THEN 1
+                    ELSE prekey_claim_limits.claims + 1
+                END,
+                window_started_at = CASE
+                    WHEN prekey_claim_limits.window_started_at <= NOW() - make_interval(secs => $3) THEN NOW()
+                    ELSE prekey_claim_limits.window_started_at
+                END
+            RETURNING claims
+            "#,
+            claimer_id,
+            target_id,
+            window_seconds as f64
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.claims as i64)
+    }
+}
@@ -0,0 +1,156 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{SenderKeyEnvelope, SENDER_KEY_RETENTION_DAYS},
+    repositories::SenderKeyRepository,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresSenderKeyRepository {
+    pool: PgPool,
+}
+
+impl PostgresSenderKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SenderKeyRepository for PostgresSenderKeyRepository {
+    async fn current_epoch(&self, conversation_id: Uuid) -> DomainResult<i32> {
+        let row = sqlx::query!(
+            r#"
+            SELECT epoch FROM sender_key_epochs WHERE conversation_id = $1
+            "#,
+            conversation_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map_or(0, |r| r.epoch))
+    }
+
+    async fn rotate(&self, conversation_id: Uuid) -> DomainResult<i32> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO sender_key_epochs (conversation_id, epoch, rotated_at)
+            VALUES ($1, 1, NOW())
+            ON CONFLICT (conversation_id) DO UPDATE
+            SET epoch = sender_key_epochs.epoch + 1, rotated_at = NOW()
+            RETURNING epoch
+            "#,
+            conversation_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        sqlx::query!(
+            r#"
+            UPDATE sender_key_envelopes SET superseded_at = NOW()
+            WHERE conversation_id = $1 AND epoch < $2 AND superseded_at IS NULL
+            "#,
+            conversation_id,
+            row.epoch
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM sender_key_envelopes WHERE conversation_id = $1 AND superseded_at < $2
+            "#,
+            conversation_id,
+            Utc::now() - Duration::days(SENDER_KEY_RETENTION_DAYS)
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.epoch)
+    }
+
+    async fn store(&self, envelopes: &[SenderKeyEnvelope]) -> DomainResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        for envelope in envelopes {
+            sqlx::query!(
+                r#"
+                INSERT INTO sender_key_envelopes (id, conversation_id, sender_id, sender_device_id, recipient_id, recipient_device_id, epoch, ciphertext, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT (conversation_id, epoch, recipient_id, recipient_device_id, sender_id, sender_device_id) DO UPDATE
+                SET ciphertext = EXCLUDED.ciphertext, created_at = EXCLUDED.created_at
+                "#,
+                envelope.id,
+                envelope.conversation_id,
+                envelope.sender_id,
+                envelope.sender_device_id,
+                envelope.recipient_id,
+                envelope.recipient_device_id,
+                envelope.epoch,
+                envelope.ciphertext,
+                envelope.created_at
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_for_recipient(
+        &self,
+        conversation_id: Uuid,
+        epoch: i32,
+        recipient_id: Uuid,
+        recipient_device_id: &str,
+    ) -> DomainResult<Vec<SenderKeyEnvelope>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, conversation_id, sender_id, sender_device_id, recipient_id, recipient_device_id, epoch, ciphertext, created_at
+            FROM sender_key_envelopes
+            WHERE conversation_id = $1 AND epoch = $2 AND recipient_id = $3 AND recipient_device_id = $4
+            ORDER BY created_at
+            "#,
+            conversation_id,
+            epoch,
+            recipient_id,
+            recipient_device_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| SenderKeyEnvelope {
+                id: r.id,
+                conversation_id: r.conversation_id,
+                sender_id: r.sender_id,
+                sender_device_id: r.sender_device_id,
+                recipient_id: r.recipient_id,
+                recipient_device_id: r.recipient_device_id,
+                epoch: r.epoch,
+                ciphertext: r.ciphertext,
+                created_at: r.created_at,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,395 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        Subscription, SubscriptionCheckout, SubscriptionStatus, SubscriptionTier, SUBSCRIPTION_GRACE_PERIOD_HOURS,
+    },
+    repositories::SubscriptionRepository,
+    services::PaymentEvent,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresSubscriptionRepository {
+    pool: PgPool,
+}
+
+impl PostgresSubscriptionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn tier_str(tier: SubscriptionTier) -> &'static str {
+    match tier {
+        SubscriptionTier::Free => "Free",
+        SubscriptionTier::Monthly => "Monthly",
+        SubscriptionTier::Yearly => "Yearly",
+    }
+}
+
+fn parse_tier(tier: &str) -> SubscriptionTier {
+    match tier {
+        "Monthly" => SubscriptionTier::Monthly,
+        "Yearly" => SubscriptionTier::Yearly,
+        _ => SubscriptionTier::Free,
+    }
+}
+
+fn status_str(status: SubscriptionStatus) -> &'static str {
+    match status {
+        SubscriptionStatus::Active => "Active",
+        SubscriptionStatus::PastDue => "PastDue",
+        SubscriptionStatus::Canceled => "Canceled",
+        SubscriptionStatus::Expired => "Expired",
+    }
+}
+
+fn parse_status(status: &str) -> SubscriptionStatus {
+    match status {
+        "Active" => SubscriptionStatus::Active,
+        "PastDue" => SubscriptionStatus::PastDue,
+        "Canceled" => SubscriptionStatus::Canceled,
+        _ => SubscriptionStatus::Expired,
+    }
+}
+
+/// Stores the subscription and mirrors its entitlement into the user's tier. Replacing the
+/// user's previous subscription keeps its id and creation time.
+async fn upsert(tx: &mut Transaction<'_, Postgres>, subscription: &Subscription) -> DomainResult<Subscription> {
+    let user_tier = if subscription.is_entitled(Utc::now()) {
+        subscription.tier
+    } else {
+        SubscriptionTier::Free
+    };
+
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO subscriptions (
+            id, user_id, tier, status, provider_subscription_id,
+            current_period_start, current_period_end, cancel_at_period_end, created_at, updated_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
+        ON CONFLICT (user_id) DO UPDATE SET
+            tier = EXCLUDED.tier,
+            status = EXCLUDED.status,
+            provider_subscription_id = EXCLUDED.provider_subscription_id,
+            current_period_start = EXCLUDED.current_period_start,
+            current_period_end = EXCLUDED.current_period_end,
+            cancel_at_period_end = EXCLUDED.cancel_at_period_end,
+            updated_at = NOW()
+        RETURNING id, user_id, tier, status, provider_subscription_id,
+                  current_period_start, current_period_end, cancel_at_period_end, created_at, updated_at
+        "#,
+        subscription.id,
+        subscription.user_id,
+        tier_str(subscription.tier),
+        status_str(subscription.status),
+        subscription.provider_subscription_id,
+        subscription.current_period_start,
+        subscription.current_period_end,
+        subscription.cancel_at_period_end,
+        subscription.created_at
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+    sqlx::query!(
+        "UPDATE users SET subscription_tier = $2, updated_at = NOW() WHERE id = $1",
+        subscription.user_id,
+        tier_str(user_tier)
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+    Ok(Subscription {
+        id: row.id,
+        user_id: row.user_id,
+        tier: parse_tier(&row.tier),
+        status: parse_status(&row.status),
+        provider_subscription_id: row.provider_subscription_id,
+        current_period_start: row.current_period_start,
+        current_period_end: row.current_period_end,
+        cancel_at_period_end: row.cancel_at_period_end,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+#[async_trait]
+impl SubscriptionRepository for PostgresSubscriptionRepository {
+    async fn save(&self, subscription: &Subscription) -> DomainResult<Subscription> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let saved = upsert(&mut tx, subscription).await?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(saved)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> DomainResult<Option<Subscription>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, user_id, tier, status, provider_subscription_id,
+                   current_period_start, current_period_end, cancel_at_period_end, created_at, updated_at
+            FROM subscriptions
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| Subscription {
+            id: r.id,
+            user_id: r.user_id,
+            tier: parse_tier(&r.tier),
+            status: parse_status(&r.status),
+            provider_subscription_id: r.provider_subscription_id,
+            current_period_start: r.current_period_start,
+            current_period_end: r.current_period_end,
+            cancel_at_period_end: r.cancel_at_period_end,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }))
+    }
+
+    async fn reserve_checkout(&self, checkout: &SubscriptionCheckout, stale_after_seconds: i64) -> DomainResult<SubscriptionCheckout> {
+        // Mirrors `Subscription::access_ends_at`, like `expire_lapsed`
+        let lapsed_before = checkout.started_at - Duration::hours(SUBSCRIPTION_GRACE_PERIOD_HOURS);
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        // Saving a subscription updates the user row too, so locking it orders this check after
+        // any upgrade completing concurrently
+        sqlx::query!("SELECT id FROM users WHERE id = $1 FOR UPDATE", checkout.user_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+            .ok_or_else(|| DomainError::NotFound("User not found".to_string()))?;
+
+        let entitled = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM subscriptions
+                WHERE user_id = $1
+                  AND status <> 'Expired'
+                  AND current_period_end > $3
+                  AND (status <> 'Canceled' OR current_period_end > $2)
+            ) as "exists!"
+            "#,
+            checkout.user_id,
+            checkout.started_at,
+            lapsed_before
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        if entitled.exists {
+            return Err(DomainError::Conflict("You already have an active subscription".to_string()));
+        }
+
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO subscription_checkouts (id, user_id, tier, payment_token, started_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id) DO UPDATE SET started_at = EXCLUDED.started_at
+            WHERE subscription_checkouts.started_at <= $5 - make_interval(secs => $6)
+              AND subscription_checkouts.tier = EXCLUDED.tier
+              AND subscription_checkouts.payment_token = EXCLUDED.payment_token
+            RETURNING id, user_id, tier, payment_token, started_at
+            "#,
+            checkout.id,
+            checkout.user_id,
+            tier_str(checkout.tier),
+            checkout.payment_token,
+            checkout.started_at,
+            stale_after_seconds as f64
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+        .ok_or_else(|| DomainError::Conflict("An upgrade is already in progress".to_string()))?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(SubscriptionCheckout {
+            id: row.id,
+            user_id: row.user_id,
+            tier: parse_tier(&row.tier),
+            payment_token: row.payment_token,
+            started_at: row.started_at,
+        })
+    }
+
+    async fn find_checkout(&self, user_id: Uuid) -> DomainResult<Option<SubscriptionCheckout>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, user_id, tier, payment_token, started_at
+            FROM subscription_checkouts
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| SubscriptionCheckout {
+            id: r.id,
+            user_id: r.user_id,
+            tier: parse_tier(&r.tier),
+            payment_token: r.payment_token,
+            started_at: r.started_at,
+        }))
+    }
+
+    async fn complete_checkout(&self, checkout_id: Uuid, subscription: &Subscription) -> DomainResult<Subscription> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let saved = upsert(&mut tx, subscription).await?;
+
+        sqlx::query!("DELETE FROM subscription_checkouts WHERE id = $1", checkout_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(saved)
+    }
+
+    async fn release_checkout(&self, checkout_id: Uuid) -> DomainResult<()> {
+        sqlx::query!("DELETE FROM subscription_checkouts WHERE id = $1", checkout_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn apply_webhook(&self, event_id: &str, event: Option<&PaymentEvent>) -> DomainResult<bool> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        // A concurrent delivery of the same event blocks here until this one committed or
+        // rolled back, so each event is applied exactly once
+        let recorded = sqlx::query!(
+            "INSERT INTO payment_webhook_events (event_id) VALUES ($1) ON CONFLICT DO NOTHING",
+            event_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        if recorded.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        if let Some(event) = event {
+            // Locked so events about the same subscription apply one after another
+            let row = sqlx::query!(
+                r#"
+                SELECT id, user_id, tier, status, provider_subscription_id,
+                       current_period_start, current_period_end, cancel_at_period_end, created_at, updated_at
+                FROM subscriptions
+                WHERE provider_subscription_id = $1
+                FOR UPDATE
+                "#,
+                event.subscription_id()
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?
+            // The first payment can be reported before the upgrade stored the subscription; the
+            // error rolls back the delivery record and makes the provider retry it later
+            .ok_or_else(|| DomainError::NotFound("Subscription not found".to_string()))?;
+
+            let mut subscription = Subscription {
+                id: row.id,
+                user_id: row.user_id,
+                tier: parse_tier(&row.tier),
+                status: parse_status(&row.status),
+                provider_subscription_id: row.provider_subscription_id,
+                current_period_start: row.current_period_start,
+                current_period_end: row.current_period_end,
+                cancel_at_period_end: row.cancel_at_period_end,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            };
+            subscription.apply(event);
+
+            upsert(&mut tx, &subscription).await?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(true)
+    }
+
+    async fn expire_lapsed(&self, now: DateTime<Utc>) -> DomainResult<Vec<Subscription>> {
+        // Mirrors `Subscription::access_ends_at`: canceled subscriptions get no grace period
+        let lapsed_before = now - Duration::hours(SUBSCRIPTION_GRACE_PERIOD_HOURS);
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let rows = sqlx::query!(
+            r#"
+            UPDATE subscriptions
+            SET status = 'Expired', updated_at = NOW()
+            WHERE status <> 'Expired'
+              AND (current_period_end <= $2 OR (status = 'Canceled' AND current_period_end <= $1))
+            RETURNING id, user_id, tier, status, provider_subscription_id,
+                      current_period_start, current_period_end, cancel_at_period_end, created_at, updated_at
+            "#,
+            now,
+            lapsed_before
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        let user_ids: Vec<Uuid> = rows.iter().map(|r| r.user_id).collect();
+        sqlx::query!(
+            "UPDATE users SET subscription_tier = 'Free', updated_at = NOW() WHERE id = ANY($1)",
+            &user_ids
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Subscription {
+                id: r.id,
+                user_id: r.user_id,
+                tier: parse_tier(&r.tier),
+                status: parse_status(&r.status),
+                provider_subscription_id: r.provider_subscription_id,
+                current_period_start: r.current_period_start,
+                current_period_end: r.current_period_end,
+                cancel_at_period_end: r.cancel_at_period_end,
+                created_at: r.created_at,
+                updated_at: r.updated_at,
+            })
+            .collect())
+    }
+}
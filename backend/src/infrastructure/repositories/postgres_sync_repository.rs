@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{SyncEvent, SyncEventType},
+    repositories::SyncRepository,
+    DomainError, DomainResult,
+};
+
+pub struct PostgresSyncRepository {
+    pool: PgPool,
+}
+
+impl PostgresSyncRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Stores an event in the sync log. Takes any executor so other repositories can record the
+/// event inside the transaction that made the change. Events are stamped with the database
+/// clock, which membership periods are recorded with too.
+///
+/// Seqs come from a counter row that stays locked until the transaction commits, so events
+/// become visible in seq order and a device that has seen `n` can never later find an event
+/// below it. Callers should record the event last to keep the lock short. The returned event lists the
+/// conversation's members as seen by the inserting transaction, so live delivery matches the
+/// membership the change was made under.
+pub(crate) async fn insert_event<'e>(executor: impl PgExecutor<'e>, event: &SyncEvent) -> DomainResult<SyncEvent> {
+    let event_type = match event.event_type {
+        SyncEventType::MessageCreated => "MessageCreated",
+        SyncEventType::MessageEdited => "MessageEdited",
+        SyncEventType::MessageDeleted => "MessageDeleted",
+        SyncEventType::MessageRead => "MessageRead",
+        SyncEventType::MessageExpired => "MessageExpired",
+        SyncEventType::KeyChanged => "KeyChanged",
+        SyncEventType::SenderKeyRotated => "SenderKeyRotated",
+        SyncEventType::SenderKeyDistributed => "SenderKeyDistributed",
+        SyncEventType::GroupCallUpdated => "GroupCallUpdated",
+        SyncEventType::LiveLocationStarted => "LiveLocationStarted",
+        SyncEventType::LiveLocationStopped => "LiveLocationStopped",
+    };
+
+    let row = sqlx::query!(
+        r#"
+        WITH next AS (
+            UPDATE sync_event_counter SET last_seq = last_seq + 1
+            RETURNING last_seq
+        )
+        INSERT INTO sync_events (seq, conversation_id, event_type, payload, created_at)
+        SELECT last_seq, $1, $2, $3, NOW() FROM next
+        RETURNING seq, created_at, ARRAY(
+            SELECT user_id FROM conversation_participants WHERE conversation_id = $1
+        ) as "recipients!"
+        "#,
+        event.conversation_id,
+        event_type,
+        event.payload
+    )
+    .fetch_one(executor)
+    .await
+    .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+    Ok(SyncEvent {
+        seq: row.seq,
+        created_at: row.created_at,
+        recipients: row.recipients,
+        ..event.clone()
+    })
+}
+
+#[async_trait]
+impl SyncRepository for PostgresSyncRepository {
+    async fn append(&self, event: &SyncEvent) -> DomainResult<SyncEvent> {
+        insert_event(&self.pool, event).await
+    }
+
+    async fn find_since(&self, user_id: Uuid, after_seq: i64, limit: i64) -> DomainResult<Vec<SyncEvent>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT e.seq, e.conversation_id, e.event_type, e.payload, e.created_at
+            FROM sync_events e
+            JOIN conversation_memberships m
+              ON m.conversation_id = e.conversation_id
+             AND e.created_at >= m.joined_at
+             AND (m.left_at IS NULL OR e.created_at < m.left_at)
+            WHERE m.user_id = $1 AND e.seq > $2
+            ORDER BY e.seq ASC
+            LIMIT $3
+            "#,
+            user_id,
+            after_seq,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| SyncEvent {
+                seq: r.seq,
+                conversation_id: r.conversation_id,
+                event_type: match r.event_type.as_str() {
+                    "MessageEdited" => SyncEventType::MessageEdited,
+                    "MessageDeleted" => SyncEventType::MessageDeleted,
+                    "MessageRead" => SyncEventType::MessageRead,
+                    "MessageExpired" => SyncEventType::MessageExpired,
+                    "KeyChanged" => SyncEventType::KeyChanged,
+                    "SenderKeyRotated" => SyncEventType::SenderKeyRotated,
+                    "SenderKeyDistributed" => SyncEventType::SenderKeyDistributed,
+                    "GroupCallUpdated" => SyncEventType::GroupCallUpdated,
+                    "LiveLocationStarted" => SyncEventType::LiveLocationStarted,
+                    "LiveLocationStopped" => SyncEventType::LiveLocationStopped,
+                    _ => SyncEventType::MessageCreated,
+                },
+                payload: r.payload,
+                created_at: r.created_at,
+                recipients: Vec::new(),
+            })
+            .collect())
+    }
+
+    async fn redact_message(&self, message_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE sync_events SET payload = payload - 'content'
+            WHERE payload ? 'content'
+              AND COALESCE(payload->>'id', payload->>'message_id') = $1::text
+            "#,
+            message_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn get_cursor(&self, user_id: Uuid, device_id: &str) -> DomainResult<Option<i64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT last_seq FROM device_sync_cursors
+            WHERE user_id = $1 AND device_id = $2
+            "#,
+            user_id,
+            device_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| r.last_seq))
+    }
+
+    async fn update_cursor(&self, user_id: Uuid, device_id: &str, seq: i64) -> DomainResult<()> {
+        // Cursors only move forward so a late ack cannot rewind a device
+        sqlx::query!(
+            r#"
+            INSERT INTO device_sync_cursors (user_id, device_id, last_seq, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (user_id, device_id)
+            DO UPDATE SET last_seq = GREATEST(device_sync_cursors.last_seq, $3), updated_at = NOW()
+            "#,
+            user_id,
+            device_id,
+            seq
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    async fn conversation(pool: &PgPool) -> Uuid {
+        sqlx::query_scalar("INSERT INTO conversations (type) VALUES ('Private') RETURNING id")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn later_seq_waits_for_earlier_commit(pool: PgPool) {
+        let conversation_id = conversation(&pool).await;
+        let event = SyncEvent::new(conversation_id, SyncEventType::MessageRead, serde_json::json!({}));
+
+        let mut first_tx = pool.begin().await.unwrap();
+        let first = insert_event(&mut *first_tx, &event).await.unwrap();
+
+        let second = tokio::spawn({
+            let pool = pool.clone();
+            let event = event.clone();
+            async move { insert_event(&pool, &event).await.unwrap() }
+        });
+
+        // The second insert cannot take the next seq while the first one is uncommitted
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!second.is_finished());
+        let visible: Vec<i64> = sqlx::query_scalar("SELECT seq FROM sync_events WHERE conversation_id = $1")
+            .bind(conversation_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert!(visible.is_empty());
+
+        first_tx.commit().await.unwrap();
+        let second = second.await.unwrap();
+        assert_eq!(second.seq, first.seq + 1);
+    }
+}
@@ -1,9 +1,11 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use super::postgres_key_log_repository::append_identity_key;
 use crate::domain::{
-    entities::{SubscriptionTier, User},
+    entities::{KeyLogEntry, NearbySearch, PublicKey, SubscriptionTier, User},
     repositories::UserRepository,
     DomainError, DomainResult,
 };
@@ -16,6 +18,140 @@ impl PostgresUserRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    pub async fn is_discoverable(&self, user_id: Uuid) -> DomainResult<bool> {
+        let row = sqlx::query!("SELECT discoverable FROM users WHERE id = $1", user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.map(|r| r.discoverable).unwrap_or(false))
+    }
+
+    /// Writes a batch of `(user_id, lat, lon, updated_at)` locations in one statement. Users who
+    /// turned discoverability off in the meantime are skipped.
+    pub async fn update_locations(&self, locations: &[(Uuid, f64, f64, DateTime<Utc>)]) -> DomainResult<()> {
+        let ids: Vec<Uuid> = locations.iter().map(|l| l.0).collect();
+        let lats: Vec<f64> = locations.iter().map(|l| l.1).collect();
+        let lons: Vec<f64> = locations.iter().map(|l| l.2).collect();
+        let updated_at: Vec<DateTime<Utc>> = locations.iter().map(|l| l.3).collect();
+
+        sqlx::query!(
+            r#"
+            UPDATE users u
+            SET location = ST_SetSRID(ST_MakePoint(v.lon, v.lat), 4326)::geography,
+                location_updated_at = v.updated_at
+            FROM UNNEST($1::uuid[], $2::float8[], $3::float8[], $4::timestamptz[]) AS v(id, lat, lon, updated_at)
+            WHERE u.id = v.id AND u.discoverable
+            "#,
+            &ids,
+            &lats,
+            &lons,
+            &updated_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// The subset of `ids` a nearby search may show to the requester: discoverable, not blocked in
+    /// either direction and matching the verified/online filters.
+    pub async fn find_discoverable_by_ids(
+        &self,
+        requester_id: Uuid,
+        ids: &[Uuid],
+        verified_only: bool,
+        online_only: bool,
+    ) -> DomainResult<Vec<User>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT u.id, u.phone_number, u.password_hash, u.name, u.username, u.bio, u.avatar_url,
+                   u.is_verified, u.is_online, u.last_seen, u.subscription_tier, u.created_at, u.updated_at,
+                   ST_Y(u.location::geometry) as lat, ST_X(u.location::geometry) as lon,
+                   u.public_key_x25519
+            FROM users u
+            WHERE u.id = ANY($2)
+              AND u.discoverable
+              AND u.id <> $1
+              AND ($3 = FALSE OR u.is_verified IS TRUE)
+              AND ($4 = FALSE OR u.is_online IS TRUE)
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b
+                  WHERE (b.blocker_id = $1 AND b.blocked_id = u.id)
+                     OR (b.blocker_id = u.id AND b.blocked_id = $1)
+              )
+            "#,
+            requester_id,
+            ids,
+            verified_only,
+            online_only
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| User {
+                id: r.id,
+                phone_number: r.phone_number,
+                password_hash: r.password_hash,
+                name: r.name,
+                username: r.username,
+                bio: r.bio,
+                avatar_url: r.avatar_url,
+                location: r.lat.and_then(|lat| r.lon.map(|lon| (lat, lon))),
+                public_key: r.public_key_x25519,
+                is_verified: r.is_verified,
+                is_online: r.is_online,
+                last_seen: r.last_seen,
+                subscription_tier: match r.subscription_tier.as_str() {
+                    "Monthly" => SubscriptionTier::Monthly,
+                    "Yearly" => SubscriptionTier::Yearly,
+                    _ => SubscriptionTier::Free,
+                },
+                created_at: r.created_at,
+                updated_at: r.updated_at,
+            })
+            .collect())
+    }
+
+    /// One page of discoverable users ordered by id, with their stored `(lat, lon, updated_at)`
+    /// location if they have one.
+    pub async fn find_discoverable_locations(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> DomainResult<Vec<(Uuid, Option<(f64, f64, DateTime<Utc>)>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, ST_Y(location::geometry) as lat, ST_X(location::geometry) as lon, location_updated_at
+            FROM users
+            WHERE discoverable
+              AND ($1::uuid IS NULL OR id > $1)
+            ORDER BY id
+            LIMIT $2
+            "#,
+            after,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let location = match (r.lat, r.lon, r.location_updated_at) {
+                    (Some(lat), Some(lon), Some(updated_at)) => Some((lat, lon, updated_at)),
+                    _ => None,
+                };
+                (r.id, location)
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -189,6 +325,48 @@ impl UserRepository for PostgresUserRepository {
         }))
     }
 
+    async fn find_public_key_owners(&self, public_key: &PublicKey) -> DomainResult<Vec<Uuid>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM users
+            WHERE public_key_x25519 = ANY($1)
+            "#,
+            &public_key.encodings()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(rows.into_iter().map(|r| r.id).collect())
+    }
+
+    async fn record_otp_attempt(&self, user_id: Uuid, window_seconds: i64) -> DomainResult<i64> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO otp_attempt_limits (user_id, window_started_at, attempts)
+            VALUES ($1, NOW(), 1)
+            ON CONFLICT (user_id) DO UPDATE SET
+                attempts = CASE
+                    WHEN otp_attempt_limits.window_started_at <= NOW() - make_interval(secs => $2) THEN 1
+                    ELSE otp_attempt_limits.attempts + 1
+                END,
+                window_started_at = CASE
+                    WHEN otp_attempt_limits.window_started_at <= NOW() - make_interval(secs => $2) THEN NOW()
+                    ELSE otp_attempt_limits.window_started_at
+                END
+            RETURNING attempts
+            "#,
+            user_id,
+            window_seconds as f64
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(row.attempts as i64)
+    }
+
     async fn update(&self, user: &User) -> DomainResult<User> {
         let subscription_tier = match user.subscription_tier {
             SubscriptionTier::Free => "Free",
@@ -250,21 +428,52 @@ impl UserRepository for PostgresUserRepository {
         Ok(())
     }
 
-    async fn find_nearby(&self, lat: f64, lon: f64, radius_km: f64) -> DomainResult<Vec<User>> {
-        let radius_meters = radius_km * 1000.0;
+    async fn find_nearby(&self, search: &NearbySearch) -> DomainResult<Vec<(User, f64)>> {
+        let radius_meters = search.radius_km * 1000.0;
+        let (after_distance_km, after_id) = search.after.unzip();
 
+        // Distances are rounded up to whole kilometers before sorting, so the page cursor
+        // never reveals more than the response itself
         let rows = sqlx::query!(
             r#"
-            SELECT id, phone_number, password_hash, name, username, bio, avatar_url,
-                   is_verified, is_online, last_seen, subscription_tier, created_at, updated_at,
-                   ST_Y(location::geometry) as lat, ST_X(location::geometry) as lon
-            FROM users
-            WHERE location IS NOT NULL
-              AND ST_DWithin(location, ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography, $3)
+            SELECT u.id, u.phone_number, u.password_hash, u.name, u.username, u.bio, u.avatar_url,
+                   u.is_verified, u.is_online, u.last_seen, u.subscription_tier, u.created_at, u.updated_at,
+                   ST_Y(u.location::geometry) as lat, ST_X(u.location::geometry) as lon,
+                   u.public_key_x25519,
+                   d.distance_km as "distance_km!"
+            FROM users u
+            CROSS JOIN LATERAL (
+                SELECT GREATEST(
+                    CEIL(ST_Distance(u.location, ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography) / 1000.0),
+                    1
+                )::float8 AS distance_km
+            ) d
+            WHERE u.location IS NOT NULL
+              AND u.discoverable
+              AND u.id <> $4
+              AND u.location_updated_at > $5
+              AND ST_DWithin(u.location, ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography, $3)
+              AND ($6 = FALSE OR u.is_verified IS TRUE)
+              AND ($7 = FALSE OR u.is_online IS TRUE)
+              AND ($8::float8 IS NULL OR (d.distance_km, u.id) > ($8, $9))
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b
+                  WHERE (b.blocker_id = $4 AND b.blocked_id = u.id)
+                     OR (b.blocker_id = u.id AND b.blocked_id = $4)
+              )
+            ORDER BY d.distance_km, u.id
+            LIMIT $10
             "#,
-            lon,
-            lat,
-            radius_meters
+            search.origin.longitude,
+            search.origin.latitude,
+            radius_meters,
+            search.requester_id,
+            search.updated_after,
+            search.verified_only,
+            search.online_only,
+            after_distance_km,
+            after_id,
+            search.limit
         )
         .fetch_all(&self.pool)
         .await
@@ -272,7 +481,7 @@ impl UserRepository for PostgresUserRepository {
 
         Ok(rows
             .into_iter()
-            .map(|r| User {
+            .map(|r| (User {
                 id: r.id,
                 phone_number: r.phone_number,
                 password_hash: r.password_hash,
@@ -292,16 +501,17 @@ impl UserRepository for PostgresUserRepository {
                 },
                 created_at: r.created_at,
                 updated_at: r.updated_at,
-            })
+            }, r.distance_km))
             .collect())
     }
 
-    async fn update_location(&self, user_id: Uuid, lat: f64, lon: f64) -> DomainResult<()> {
-        sqlx::query!(
+    async fn update_location(&self, user_id: Uuid, lat: f64, lon: f64) -> DomainResult<bool> {
+        let result = sqlx::query!(
             r#"
             UPDATE users
-            SET location = ST_SetSRID(ST_MakePoint($2, $3), 4326)::geography
-            WHERE id = $1
+            SET location = ST_SetSRID(ST_MakePoint($2, $3), 4326)::geography,
+                location_updated_at = NOW()
+            WHERE id = $1 AND discoverable
             "#,
             user_id,
             lon,
@@ -311,6 +521,72 @@ impl UserRepository for PostgresUserRepository {
         .await
         .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
 
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_discoverable(&self, user_id: Uuid, discoverable: bool) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET discoverable = $2,
+                location = CASE WHEN $2 THEN location ELSE NULL END,
+                location_updated_at = CASE WHEN $2 THEN location_updated_at ELSE NULL END
+            WHERE id = $1
+            "#,
+            user_id,
+            discoverable
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn expire_locations(&self, updated_before: DateTime<Utc>) -> DomainResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET location = NULL, location_updated_at = NULL
+            WHERE location IS NOT NULL
+              AND (location_updated_at IS NULL OR location_updated_at <= $1)
+            "#,
+            updated_before
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn block_user(&self, blocker_id: Uuid, blocked_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_blocks (blocker_id, blocked_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            blocker_id,
+            blocked_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn unblock_user(&self, blocker_id: Uuid, blocked_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            "DELETE FROM user_blocks WHERE blocker_id = $1 AND blocked_id = $2",
+            blocker_id,
+            blocked_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
         Ok(())
     }
 
@@ -331,7 +607,10 @@ impl UserRepository for PostgresUserRepository {
         Ok(())
     }
 
-    async fn update_public_key(&self, user_id: Uuid, public_key: String) -> DomainResult<()> {
+    async fn update_public_key(&self, user_id: Uuid, public_key: String) -> DomainResult<Option<KeyLogEntry>> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
         sqlx::query!(
             r#"
             UPDATE users
@@ -341,10 +620,20 @@ impl UserRepository for PostgresUserRepository {
             user_id,
             public_key
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                DomainError::Conflict("Public key is already registered to another user".to_string())
+            }
+            e => DomainError::InternalError(format!("Database error: {}", e)),
+        })?;
 
-        Ok(())
+        let entry = append_identity_key(&mut tx, user_id, None, &public_key).await?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(entry)
     }
 }
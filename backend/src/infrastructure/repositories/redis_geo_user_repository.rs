@@ -0,0 +1,407 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::aio::ConnectionManager;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use super::PostgresUserRepository;
+use crate::infrastructure::RedisService;
+use crate::domain::{
+    entities::{KeyLogEntry, NearbySearch, PublicKey, User},
+    repositories::UserRepository,
+    DomainError, DomainResult,
+};
+
+/// GEO set holding the latest (already fuzzed) position of every discoverable user.
+const GEO_KEY: &str = "geo:users";
+/// Users known to be discoverable, so location pings don't have to ask Postgres.
+const DISCOVERABLE_KEY: &str = "geo:discoverable";
+/// Upper bound of users a single `GEOSEARCH` looks at. Pages beyond them are served by PostGIS.
+const MAX_GEO_CANDIDATES: usize = 1000;
+/// Users loaded from Postgres per round trip while filling the index.
+const BACKFILL_BATCH_SIZE: i64 = 1000;
+/// Redis only indexes latitudes within this range.
+const MAX_GEO_LATITUDE: f64 = 85.05112878;
+
+/// Indexes a location only while the user is in the discoverable set, atomically, so a ping
+/// racing an opt-out cannot put them back after `forget_location` removed them.
+/// KEYS: discoverable set, GEO set, presence key. ARGV: user id, longitude, latitude, TTL.
+const INDEX_LOCATION_SCRIPT: &str = r#"
+if redis.call('SISMEMBER', KEYS[1], ARGV[1]) == 0 then
+    return 0
+end
+redis.call('GEOADD', KEYS[2], ARGV[2], ARGV[3], ARGV[1])
+redis.call('SET', KEYS[3], 1, 'EX', ARGV[4])
+return 1
+"#;
+
+fn presence_key(user_id: Uuid) -> String {
+    format!("geo:presence:{}", user_id)
+}
+
+fn redis_error(e: redis::RedisError) -> DomainError {
+    DomainError::InternalError(format!("Redis error: {}", e))
+}
+
+/// Serves location pings and nearby searches from a Redis GEO index and persists locations to
+/// Postgres in batches. Each location has a presence key that expires after the location TTL,
+/// which drops stale users from results. Everything else is delegated to Postgres.
+pub struct RedisGeoUserRepository {
+    postgres: Arc<PostgresUserRepository>,
+    redis: ConnectionManager,
+    location_ttl: chrono::Duration,
+    index_location: redis::Script,
+    /// Latest location per user that has not been written to Postgres yet.
+    pending: Mutex<HashMap<Uuid, (f64, f64, DateTime<Utc>)>>,
+}
+
+impl RedisGeoUserRepository {
+    pub fn new(
+        postgres: Arc<PostgresUserRepository>,
+        redis_service: &RedisService,
+        location_ttl: chrono::Duration,
+    ) -> Self {
+        Self {
+            postgres,
+            redis: redis_service.connection(),
+            location_ttl,
+            index_location: redis::Script::new(INDEX_LOCATION_SCRIPT),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Indexes the discoverable users and unexpired locations stored in Postgres, so they are
+    /// found again after a deploy or a Redis flush. Locations with a presence key were pinged
+    /// since and are left alone.
+    pub async fn backfill(&self) -> DomainResult<usize> {
+        let mut redis = self.redis.clone();
+        let mut after = None;
+        let mut indexed = 0;
+
+        loop {
+            let page = self.postgres.find_discoverable_locations(after, BACKFILL_BATCH_SIZE).await?;
+            let Some((last_id, _)) = page.last() else {
+                break;
+            };
+            after = Some(*last_id);
+
+            let ids: Vec<String> = page.iter().map(|(user_id, _)| user_id.to_string()).collect();
+            redis::cmd("SADD")
+                .arg(DISCOVERABLE_KEY)
+                .arg(&ids)
+                .query_async::<_, ()>(&mut redis)
+                .await
+                .map_err(redis_error)?;
+
+            let now = Utc::now();
+            let located: Vec<(Uuid, f64, f64, i64)> = page
+                .iter()
+                .filter_map(|(user_id, location)| {
+                    let (lat, lon, updated_at) = (*location)?;
+                    let remaining = (updated_at + self.location_ttl - now).num_seconds();
+                    (remaining > 0).then_some((*user_id, lat, lon, remaining))
+                })
+                .collect();
+
+            if !located.is_empty() {
+                let presence_keys: Vec<String> = located.iter().map(|(user_id, ..)| presence_key(*user_id)).collect();
+                let presence: Vec<Option<String>> = redis::cmd("MGET")
+                    .arg(&presence_keys)
+                    .query_async(&mut redis)
+                    .await
+                    .map_err(redis_error)?;
+
+                let mut pipe = redis::pipe();
+                for ((user_id, lat, lon, remaining), present) in located.into_iter().zip(presence) {
+                    if present.is_some() {
+                        continue;
+                    }
+                    pipe.cmd("GEOADD")
+                        .arg(GEO_KEY)
+                        .arg(lon)
+                        .arg(lat.clamp(-MAX_GEO_LATITUDE, MAX_GEO_LATITUDE))
+                        .arg(user_id.to_string())
+                        .ignore()
+                        .cmd("SET")
+                        .arg(presence_key(user_id))
+                        .arg(1)
+                        .arg("EX")
+                        .arg(remaining)
+                        .ignore();
+                    indexed += 1;
+                }
+                pipe.query_async::<_, ()>(&mut redis).await.map_err(redis_error)?;
+            }
+
+            if (page.len() as i64) < BACKFILL_BATCH_SIZE {
+                break;
+            }
+        }
+
+        Ok(indexed)
+    }
+
+    /// Writes the locations received since the last flush to Postgres. On failure they are kept
+    /// for the next attempt unless a newer ping replaced them. Each row is only written while
+    /// the user is still discoverable, so a ping queued just before an opt-out is dropped.
+    pub async fn flush_pending_locations(&self) -> DomainResult<usize> {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let locations: Vec<(Uuid, f64, f64, DateTime<Utc>)> = pending
+            .iter()
+            .map(|(user_id, (lat, lon, updated_at))| (*user_id, *lat, *lon, *updated_at))
+            .collect();
+
+        if let Err(e) = self.postgres.update_locations(&locations).await {
+            let mut queue = self.pending.lock().unwrap();
+            for (user_id, location) in pending {
+                queue.entry(user_id).or_insert(location);
+            }
+            return Err(e);
+        }
+
+        Ok(locations.len())
+    }
+
+    /// Runs `INDEX_LOCATION_SCRIPT`. Returns false if the user is not in the discoverable set.
+    async fn index_location(&self, user_id: Uuid, lat: f64, lon: f64) -> DomainResult<bool> {
+        let mut redis = self.redis.clone();
+        self.index_location
+            .key(DISCOVERABLE_KEY)
+            .key(GEO_KEY)
+            .key(presence_key(user_id))
+            .arg(user_id.to_string())
+            .arg(lon)
+            .arg(lat.clamp(-MAX_GEO_LATITUDE, MAX_GEO_LATITUDE))
+            .arg(self.location_ttl.num_seconds().max(1))
+            .invoke_async(&mut redis)
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn forget_location(&self, user_id: Uuid) -> DomainResult<()> {
+        self.pending.lock().unwrap().remove(&user_id);
+
+        let mut redis = self.redis.clone();
+        redis::pipe()
+            .atomic()
+            .cmd("SREM").arg(DISCOVERABLE_KEY).arg(user_id.to_string()).ignore()
+            .cmd("ZREM").arg(GEO_KEY).arg(user_id.to_string()).ignore()
+            .cmd("DEL").arg(presence_key(user_id)).ignore()
+            .query_async::<_, ()>(&mut redis)
+            .await
+            .map_err(redis_error)
+    }
+}
+
+#[async_trait]
+impl UserRepository for RedisGeoUserRepository {
+    async fn create(&self, user: &User) -> DomainResult<User> {
+        self.postgres.create(user).await
+    }
+
+    /// Includes a location that has not been flushed to Postgres yet.
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<User>> {
+        let pending = self.pending.lock().unwrap().get(&id).copied();
+
+        Ok(self.postgres.find_by_id(id).await?.map(|mut user| {
+            if let Some((lat, lon, _)) = pending {
+                user.location = Some((lat, lon));
+            }
+            user
+        }))
+    }
+
+    async fn find_by_phone(&self, phone_number: &str) -> DomainResult<Option<User>> {
+        self.postgres.find_by_phone(phone_number).await
+    }
+
+    async fn find_by_username(&self, username: &str) -> DomainResult<Option<User>> {
+        self.postgres.find_by_username(username).await
+    }
+
+    async fn find_public_key_owners(&self, public_key: &PublicKey) -> DomainResult<Vec<Uuid>> {
+        self.postgres.find_public_key_owners(public_key).await
+    }
+
+    async fn record_otp_attempt(&self, user_id: Uuid, window_seconds: i64) -> DomainResult<i64> {
+        self.postgres.record_otp_attempt(user_id, window_seconds).await
+    }
+
+    async fn update(&self, user: &User) -> DomainResult<User> {
+        self.postgres.update(user).await
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        self.forget_location(id).await?;
+        self.postgres.delete(id).await
+    }
+
+    /// Presence keys take the place of `updated_after`: they expire after the same TTL.
+    async fn find_nearby(&self, search: &NearbySearch) -> DomainResult<Vec<(User, f64)>> {
+        let mut redis = self.redis.clone();
+
+        let hits: Vec<(String, f64)> = redis::cmd("GEOSEARCH")
+            .arg(GEO_KEY)
+            .arg("FROMLONLAT")
+            .arg(search.origin.longitude)
+            .arg(search.origin.latitude.clamp(-MAX_GEO_LATITUDE, MAX_GEO_LATITUDE))
+            .arg("BYRADIUS")
+            .arg(search.radius_km)
+            .arg("km")
+            .arg("ASC")
+            .arg("COUNT")
+            .arg(MAX_GEO_CANDIDATES)
+            .arg("WITHDIST")
+            .query_async(&mut redis)
+            .await
+            .map_err(redis_error)?;
+
+        // With the candidates cut off, anyone past the last one may still belong on this page
+        let cutoff_km = hits
+            .last()
+            .filter(|_| hits.len() == MAX_GEO_CANDIDATES)
+            .map(|(_, distance)| distance.ceil().max(1.0));
+
+        let hits: Vec<(Uuid, f64)> = hits
+            .into_iter()
+            .filter_map(|(member, distance)| Some((Uuid::parse_str(&member).ok()?, distance)))
+            .collect();
+
+        if hits.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let presence_keys: Vec<String> = hits.iter().map(|(user_id, _)| presence_key(*user_id)).collect();
+        let presence: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(&presence_keys)
+            .query_async(&mut redis)
+            .await
+            .map_err(redis_error)?;
+
+        let (fresh, stale): (Vec<_>, Vec<_>) = hits
+            .into_iter()
+            .zip(presence)
+            .partition(|(_, present)| present.is_some());
+
+        // Stale members are pruned lazily, whenever a search runs into them
+        if !stale.is_empty() {
+            let members: Vec<String> = stale.into_iter().map(|((user_id, _), _)| user_id.to_string()).collect();
+            if let Err(e) = redis::cmd("ZREM").arg(GEO_KEY).arg(&members).query_async::<_, ()>(&mut redis).await {
+                tracing::warn!("Failed to prune stale locations from the geo index: {}", e);
+            }
+        }
+
+        // Same whole-kilometer distances and cursor order as the PostGIS query
+        let distances: HashMap<Uuid, f64> = fresh
+            .into_iter()
+            .map(|((user_id, distance), _)| (user_id, distance.ceil().max(1.0)))
+            .filter(|(user_id, distance_km)| {
+                *user_id != search.requester_id
+                    && search.after.map_or(true, |after| (*distance_km, *user_id) > after)
+            })
+            .collect();
+
+        if distances.is_empty() && cutoff_km.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<Uuid> = distances.keys().copied().collect();
+        let users = self
+            .postgres
+            .find_discoverable_by_ids(search.requester_id, &ids, search.verified_only, search.online_only)
+            .await?;
+
+        let mut nearby: Vec<(User, f64)> = users
+            .into_iter()
+            .map(|user| {
+                let distance_km = distances[&user.id];
+                (user, distance_km)
+            })
+            .collect();
+        nearby.sort_by(|(a, a_km), (b, b_km)| a_km.total_cmp(b_km).then(a.id.cmp(&b.id)));
+
+        // Users in the last candidate's kilometer may sort before it, so only closer ones are
+        // certain. If they don't fill the page, PostGIS has the full picture.
+        if let Some(cutoff_km) = cutoff_km {
+            nearby.retain(|(_, distance_km)| *distance_km < cutoff_km);
+            if (nearby.len() as i64) < search.limit {
+                return self.postgres.find_nearby(search).await;
+            }
+        }
+        nearby.truncate(search.limit.max(0) as usize);
+
+        Ok(nearby)
+    }
+
+    /// Indexes the location in Redis right away; Postgres catches up on the next flush.
+    async fn update_location(&self, user_id: Uuid, lat: f64, lon: f64) -> DomainResult<bool> {
+        if !self.index_location(user_id, lat, lon).await? {
+            // Not cached as discoverable: ask Postgres, then index as usual
+            if !self.postgres.is_discoverable(user_id).await? {
+                return Ok(false);
+            }
+            let mut redis = self.redis.clone();
+            redis::cmd("SADD")
+                .arg(DISCOVERABLE_KEY)
+                .arg(user_id.to_string())
+                .query_async::<_, ()>(&mut redis)
+                .await
+                .map_err(redis_error)?;
+            if !self.index_location(user_id, lat, lon).await? {
+                return Ok(false);
+            }
+
+            // An opt-out may have landed between the check and the SADD. It writes Postgres
+            // before forgetting, so if it is not visible now its own cleanup runs after ours
+            if !self.postgres.is_discoverable(user_id).await? {
+                self.forget_location(user_id).await?;
+                return Ok(false);
+            }
+        }
+
+        self.pending.lock().unwrap().insert(user_id, (lat, lon, Utc::now()));
+
+        Ok(true)
+    }
+
+    async fn set_discoverable(&self, user_id: Uuid, discoverable: bool) -> DomainResult<()> {
+        self.postgres.set_discoverable(user_id, discoverable).await?;
+
+        if discoverable {
+            let mut redis = self.redis.clone();
+            redis::cmd("SADD")
+                .arg(DISCOVERABLE_KEY)
+                .arg(user_id.to_string())
+                .query_async::<_, ()>(&mut redis)
+                .await
+                .map_err(redis_error)
+        } else {
+            self.forget_location(user_id).await
+        }
+    }
+
+    async fn expire_locations(&self, updated_before: DateTime<Utc>) -> DomainResult<u64> {
+        self.postgres.expire_locations(updated_before).await
+    }
+
+    async fn block_user(&self, blocker_id: Uuid, blocked_id: Uuid) -> DomainResult<()> {
+        self.postgres.block_user(blocker_id, blocked_id).await
+    }
+
+    async fn unblock_user(&self, blocker_id: Uuid, blocked_id: Uuid) -> DomainResult<()> {
+        self.postgres.unblock_user(blocker_id, blocked_id).await
+    }
+
+    async fn update_online_status(&self, user_id: Uuid, is_online: bool) -> DomainResult<()> {
+        self.postgres.update_online_status(user_id, is_online).await
+    }
+
+    async fn update_public_key(&self, user_id: Uuid, public_key: String) -> DomainResult<Option<KeyLogEntry>> {
+        self.postgres.update_public_key(user_id, public_key).await
+    }
+}
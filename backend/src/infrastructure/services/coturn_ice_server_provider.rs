@@ -0,0 +1,54 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::Utc;
+use ring::hmac;
+use uuid::Uuid;
+
+use crate::domain::services::{IceServer, IceServerProvider};
+
+/// Issues TURN credentials following the coturn REST API convention (`use-auth-secret`): the
+/// username is `<expiry unix timestamp>:<user id>` and the password is the base64 HMAC-SHA1 of
+/// the username under the secret shared with coturn. coturn rejects the credentials once the
+/// timestamp has passed, so nothing has to be stored or revoked.
+pub struct CoturnIceServerProvider {
+    stun_urls: Vec<String>,
+    turn_urls: Vec<String>,
+    key: Option<hmac::Key>,
+}
+
+impl CoturnIceServerProvider {
+    /// Without a shared secret only the STUN servers are handed out.
+    pub fn new(stun_urls: Vec<String>, turn_urls: Vec<String>, shared_secret: Option<&str>) -> Self {
+        Self {
+            stun_urls,
+            turn_urls,
+            key: shared_secret.map(|secret| hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret.as_bytes())),
+        }
+    }
+}
+
+impl IceServerProvider for CoturnIceServerProvider {
+    fn ice_servers(&self, user_id: Uuid, ttl_seconds: i64) -> Vec<IceServer> {
+        let mut servers = Vec::new();
+
+        if !self.stun_urls.is_empty() {
+            servers.push(IceServer {
+                urls: self.stun_urls.clone(),
+                username: None,
+                credential: None,
+            });
+        }
+
+        if let Some(key) = self.key.as_ref().filter(|_| !self.turn_urls.is_empty()) {
+            let username = format!("{}:{}", Utc::now().timestamp() + ttl_seconds, user_id);
+            let credential = BASE64.encode(hmac::sign(key, username.as_bytes()).as_ref());
+
+            servers.push(IceServer {
+                urls: self.turn_urls.clone(),
+                username: Some(username),
+                credential: Some(credential),
+            });
+        }
+
+        servers
+    }
+}
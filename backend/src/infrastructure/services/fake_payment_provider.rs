@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use ring::hmac;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::SubscriptionTier,
+    services::{PaymentEvent, PaymentProvider, PaymentWebhook, ProviderSubscription},
+    DomainError, DomainResult,
+};
+
+/// Payment token the fake declines, named after the Stripe test token with the same effect.
+pub const DECLINED_PAYMENT_TOKEN: &str = "tok_chargeDeclined";
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum FakeEvent {
+    Renewed {
+        subscription_id: String,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    },
+    PaymentFailed { subscription_id: String },
+    Canceled { subscription_id: String, ended_at: DateTime<Utc> },
+}
+
+#[derive(Debug, Deserialize)]
+struct FakeWebhook {
+    id: String,
+    #[serde(flatten)]
+    event: FakeEvent,
+}
+
+/// `PaymentProvider` that charges nothing, for local development and tests. Every payment token
+/// but `DECLINED_PAYMENT_TOKEN` is accepted and billing periods are 30 or 365 days. Webhooks
+/// are JSON bodies like `{"id": "evt_1", "type": "payment_failed", "subscription_id": "..."}`
+/// signed with the hex HMAC-SHA256 of the body under the webhook secret.
+pub struct FakePaymentProvider {
+    webhook_key: hmac::Key,
+}
+
+impl FakePaymentProvider {
+    pub fn new(webhook_secret: &str) -> Self {
+        Self {
+            webhook_key: hmac::Key::new(hmac::HMAC_SHA256, webhook_secret.as_bytes()),
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for FakePaymentProvider {
+    async fn create_subscription(
+        &self,
+        _user_id: Uuid,
+        tier: SubscriptionTier,
+        payment_token: &str,
+        idempotency_key: &str,
+    ) -> DomainResult<ProviderSubscription> {
+        let period = match tier {
+            SubscriptionTier::Monthly => Duration::days(30),
+            SubscriptionTier::Yearly => Duration::days(365),
+            SubscriptionTier::Free => {
+                return Err(DomainError::ValidationError("The Free tier is not billed".to_string()))
+            }
+        };
+        if payment_token == DECLINED_PAYMENT_TOKEN {
+            return Err(DomainError::ValidationError("Payment declined: Your card was declined.".to_string()));
+        }
+
+        let now = Utc::now();
+        Ok(ProviderSubscription {
+            id: format!("fake_sub_{}", idempotency_key),
+            current_period_start: now,
+            current_period_end: now + period,
+        })
+    }
+
+    async fn find_subscription(&self, _idempotency_key: &str) -> DomainResult<Option<ProviderSubscription>> {
+        // Subscriptions are not kept, and creating one never fails midway
+        Ok(None)
+    }
+
+    async fn cancel_subscription(&self, _subscription_id: &str) -> DomainResult<()> {
+        Ok(())
+    }
+
+    async fn refund_subscription(&self, _subscription_id: &str) -> DomainResult<()> {
+        Ok(())
+    }
+
+    fn signature_header(&self) -> &'static str {
+        "X-Fake-Signature"
+    }
+
+    fn parse_webhook(&self, payload: &[u8], signature: &str) -> DomainResult<PaymentWebhook> {
+        let signature = hex::decode(signature)
+            .map_err(|_| DomainError::AuthenticationError("Invalid webhook signature".to_string()))?;
+        hmac::verify(&self.webhook_key, payload, &signature)
+            .map_err(|_| DomainError::AuthenticationError("Invalid webhook signature".to_string()))?;
+
+        let webhook: FakeWebhook = serde_json::from_slice(payload)
+            .map_err(|e| DomainError::ValidationError(format!("Malformed webhook: {}", e)))?;
+
+        let event = match webhook.event {
+            FakeEvent::Renewed { subscription_id, period_start, period_end } => PaymentEvent::Renewed {
+                subscription_id,
+                period_start,
+                period_end,
+            },
+            FakeEvent::PaymentFailed { subscription_id } => PaymentEvent::PaymentFailed { subscription_id },
+            FakeEvent::Canceled { subscription_id, ended_at } => PaymentEvent::Canceled { subscription_id, ended_at },
+        };
+
+        Ok(PaymentWebhook {
+            event_id: webhook.id,
+            event: Some(event),
+        })
+    }
+}
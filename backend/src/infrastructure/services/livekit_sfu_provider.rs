@@ -0,0 +1,223 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use reqwest::Client;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::domain::{
+    services::{SfuGrants, SfuProvider},
+    DomainError, DomainResult,
+};
+
+/// Seconds an empty room stays open before LiveKit closes it on its own. Rooms are reopened on
+/// every join, so this only has to outlast the time between minting a join token and connecting.
+const EMPTY_ROOM_TIMEOUT_SECONDS: u32 = 300;
+/// Lifetime of the tokens used to call the LiveKit server API.
+const ADMIN_TOKEN_TTL_SECONDS: i64 = 60;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VideoGrant {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    room: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    room_join: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    room_create: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    room_admin: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    can_publish: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    can_publish_data: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    can_subscribe: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListParticipantsResponse {
+    #[serde(default)]
+    participants: Vec<ParticipantInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParticipantInfo {
+    identity: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AccessTokenClaims {
+    iss: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub: Option<String>,
+    nbf: i64,
+    exp: i64,
+    video: VideoGrant,
+}
+
+/// `SfuProvider` backed by a LiveKit server. Join tokens are LiveKit access tokens (HS256 JWTs
+/// signed with the API secret); rooms are managed through the Twirp `RoomService` API.
+pub struct LiveKitSfuProvider {
+    url: String,
+    api_url: String,
+    api_key: String,
+    api_secret: String,
+    client: Client,
+}
+
+impl LiveKitSfuProvider {
+    /// `url` is the WebSocket address clients connect to; the server API is served on the same
+    /// host over HTTP(S).
+    pub fn new(url: &str, api_key: &str, api_secret: &str) -> Self {
+        let api_url = url
+            .replacen("wss://", "https://", 1)
+            .replacen("ws://", "http://", 1)
+            .trim_end_matches('/')
+            .to_string();
+
+        Self {
+            url: url.to_string(),
+            api_url,
+            api_key: api_key.to_string(),
+            api_secret: api_secret.to_string(),
+            client: Client::new(),
+        }
+    }
+
+    fn sign(&self, sub: Option<String>, video: VideoGrant, ttl_seconds: i64) -> DomainResult<String> {
+        let now = Utc::now().timestamp();
+        let claims = AccessTokenClaims {
+            iss: self.api_key.clone(),
+            sub,
+            nbf: now,
+            exp: now + ttl_seconds,
+            video,
+        };
+
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(self.api_secret.as_bytes()))
+            .map_err(|e| DomainError::InternalError(format!("Failed to sign SFU token: {}", e)))
+    }
+
+    /// Calls a `RoomService` method. Methods acting inside a room pass its name and get a
+    /// `roomAdmin` token for it; creating and deleting rooms only needs `roomCreate`. Returns
+    /// None when the room or participant does not exist.
+    async fn room_service(
+        &self,
+        method: &str,
+        admin_room: Option<&str>,
+        body: serde_json::Value,
+    ) -> DomainResult<Option<serde_json::Value>> {
+        let token = self.sign(
+            None,
+            VideoGrant {
+                room: admin_room.map(str::to_string),
+                room_join: false,
+                room_create: admin_room.is_none(),
+                room_admin: admin_room.is_some(),
+                can_publish: None,
+                can_publish_data: None,
+                can_subscribe: None,
+            },
+            ADMIN_TOKEN_TTL_SECONDS,
+        )?;
+
+        let response = self.client
+            .post(format!("{}/twirp/livekit.RoomService/{}", self.api_url, method))
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| DomainError::InternalError(format!("SFU request failed: {}", e)))?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error = response.text().await.unwrap_or_default();
+            return Err(DomainError::InternalError(format!("SFU {} failed ({}): {}", method, status, error)));
+        }
+
+        let body = response
+            .json()
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Invalid SFU {} response: {}", method, e)))?;
+
+        Ok(Some(body))
+    }
+}
+
+#[async_trait]
+impl SfuProvider for LiveKitSfuProvider {
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    async fn create_room(&self, room_name: &str) -> DomainResult<()> {
+        self.room_service(
+            "CreateRoom",
+            None,
+            json!({ "name": room_name, "empty_timeout": EMPTY_ROOM_TIMEOUT_SECONDS }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_room(&self, room_name: &str) -> DomainResult<()> {
+        self.room_service("DeleteRoom", None, json!({ "room": room_name })).await?;
+        Ok(())
+    }
+
+    async fn list_participants(&self, room_name: &str) -> DomainResult<Option<Vec<Uuid>>> {
+        let Some(body) = self.room_service("ListParticipants", Some(room_name), json!({ "room": room_name })).await? else {
+            return Ok(None);
+        };
+
+        let response: ListParticipantsResponse = serde_json::from_value(body)
+            .map_err(|e| DomainError::InternalError(format!("Invalid SFU ListParticipants response: {}", e)))?;
+
+        // Identities are the user ids tokens were minted for
+        Ok(Some(
+            response.participants
+                .iter()
+                .filter_map(|participant| Uuid::parse_str(&participant.identity).ok())
+                .collect(),
+        ))
+    }
+
+    async fn remove_participant(&self, room_name: &str, user_id: Uuid) -> DomainResult<()> {
+        self.room_service(
+            "RemoveParticipant",
+            Some(room_name),
+            json!({ "room": room_name, "identity": user_id.to_string() }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    fn join_token(
+        &self,
+        room_name: &str,
+        user_id: Uuid,
+        grants: SfuGrants,
+        ttl_seconds: i64,
+    ) -> DomainResult<String> {
+        self.sign(
+            Some(user_id.to_string()),
+            VideoGrant {
+                room: Some(room_name.to_string()),
+                room_join: true,
+                room_create: false,
+                room_admin: false,
+                can_publish: Some(grants.can_publish),
+                can_publish_data: Some(grants.can_publish),
+                can_subscribe: Some(grants.can_subscribe),
+            },
+            ttl_seconds,
+        )
+    }
+}
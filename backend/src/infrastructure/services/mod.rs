@@ -4,3 +4,15 @@ pub use auth_service_impl::AuthServiceImpl;
 
 pub mod blockchain_service;
 pub use blockchain_service::{BlockchainService, EvmBlockchainService};
+
+pub mod coturn_ice_server_provider;
+pub use coturn_ice_server_provider::CoturnIceServerProvider;
+
+pub mod livekit_sfu_provider;
+pub use livekit_sfu_provider::LiveKitSfuProvider;
+
+pub mod stripe_payment_provider;
+pub use stripe_payment_provider::StripePaymentProvider;
+
+pub mod fake_payment_provider;
+pub use fake_payment_provider::FakePaymentProvider;
@@ -0,0 +1,358 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method};
+use ring::hmac;
+use serde::{de::DeserializeOwned, Deserialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::SubscriptionTier,
+    services::{PaymentEvent, PaymentProvider, PaymentWebhook, ProviderSubscription},
+    DomainError, DomainResult,
+};
+
+const API_URL: &str = "https://api.stripe.com/v1";
+/// Pinned so subscription periods stay top-level fields of the subscription object.
+const API_VERSION: &str = "2024-06-20";
+/// Deliveries signed longer ago than this are rejected as possible replays.
+const SIGNATURE_TOLERANCE_SECONDS: i64 = 300;
+
+#[derive(Debug, Deserialize)]
+struct StripeObject {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeSubscription {
+    id: String,
+    current_period_start: i64,
+    current_period_end: i64,
+    ended_at: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeSubscriptionInvoice {
+    latest_invoice: Option<StripeLatestInvoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeLatestInvoice {
+    payment_intent: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeInvoice {
+    subscription: Option<String>,
+    lines: StripeList<StripeInvoiceLine>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeList<T> {
+    data: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeInvoiceLine {
+    period: StripePeriod,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripePeriod {
+    start: i64,
+    end: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEvent {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    data: StripeEventData,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEventData {
+    object: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeErrorBody {
+    error: StripeError,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeError {
+    message: Option<String>,
+}
+
+fn timestamp(secs: i64) -> DomainResult<DateTime<Utc>> {
+    DateTime::from_timestamp(secs, 0)
+        .ok_or_else(|| DomainError::ValidationError(format!("Invalid timestamp from payment provider: {}", secs)))
+}
+
+fn parse_object<T: DeserializeOwned>(object: serde_json::Value) -> DomainResult<T> {
+    serde_json::from_value(object)
+        .map_err(|e| DomainError::ValidationError(format!("Malformed webhook object: {}", e)))
+}
+
+/// `PaymentProvider` backed by Stripe. The payment token is a PaymentMethod id collected by
+/// the client (Stripe.js or the mobile SDKs); each tier maps to a recurring Price.
+pub struct StripePaymentProvider {
+    secret_key: String,
+    webhook_key: hmac::Key,
+    monthly_price_id: String,
+    yearly_price_id: String,
+    client: Client,
+}
+
+impl StripePaymentProvider {
+    pub fn new(secret_key: &str, webhook_secret: &str, monthly_price_id: &str, yearly_price_id: &str) -> Self {
+        Self {
+            secret_key: secret_key.to_string(),
+            webhook_key: hmac::Key::new(hmac::HMAC_SHA256, webhook_secret.as_bytes()),
+            monthly_price_id: monthly_price_id.to_string(),
+            yearly_price_id: yearly_price_id.to_string(),
+            client: Client::new(),
+        }
+    }
+
+    async fn post<T: DeserializeOwned>(&self, path: &str, form: &[(&str, &str)]) -> DomainResult<T> {
+        self.request(Method::POST, path, form, None).await
+    }
+
+    /// Sends an API request. Requests repeated with the same `idempotency_key` are answered
+    /// with the result of the first one instead of being executed again.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        params: &[(&str, &str)],
+        idempotency_key: Option<&str>,
+    ) -> DomainResult<T> {
+        let mut request = self.client
+            .request(method.clone(), format!("{}{}", API_URL, path))
+            .basic_auth(&self.secret_key, None::<&str>)
+            .header("Stripe-Version", API_VERSION);
+        request = if method == Method::POST {
+            request.form(params)
+        } else {
+            request.query(params)
+        };
+        if let Some(key) = idempotency_key {
+            request = request.header("Idempotency-Key", key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Payment request failed: {}", e)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return response
+                .json()
+                .await
+                .map_err(|e| DomainError::InternalError(format!("Malformed payment response: {}", e)));
+        }
+
+        let message = response
+            .json::<StripeErrorBody>()
+            .await
+            .ok()
+            .and_then(|body| body.error.message)
+            .unwrap_or_default();
+
+        // 402 is a card error (declined, expired, ...); the user can retry with another card
+        if status == reqwest::StatusCode::PAYMENT_REQUIRED {
+            return Err(DomainError::ValidationError(format!("Payment declined: {}", message)));
+        }
+        Err(DomainError::InternalError(format!("Payment request {} failed ({}): {}", path, status, message)))
+    }
+
+    /// Checks a `Stripe-Signature` header (`t=<timestamp>,v1=<hex hmac>,...`) against the
+    /// HMAC-SHA256 of `<timestamp>.<payload>` under the endpoint's signing secret.
+    fn verify_signature(&self, payload: &[u8], header: &str) -> DomainResult<()> {
+        let mut signed_at = None;
+        let mut signatures = Vec::new();
+        for part in header.split(',') {
+            match part.trim().split_once('=') {
+                Some(("t", value)) => signed_at = value.parse::<i64>().ok(),
+                Some(("v1", value)) => signatures.extend(hex::decode(value).ok()),
+                _ => {}
+            }
+        }
+
+        let signed_at = signed_at
+            .ok_or_else(|| DomainError::AuthenticationError("Missing webhook timestamp".to_string()))?;
+        if (Utc::now().timestamp() - signed_at).abs() > SIGNATURE_TOLERANCE_SECONDS {
+            return Err(DomainError::AuthenticationError("Webhook timestamp outside tolerance".to_string()));
+        }
+
+        let mut signed_payload = format!("{}.", signed_at).into_bytes();
+        signed_payload.extend_from_slice(payload);
+
+        if signatures
+            .iter()
+            .any(|signature| hmac::verify(&self.webhook_key, &signed_payload, signature).is_ok())
+        {
+            Ok(())
+        } else {
+            Err(DomainError::AuthenticationError("Invalid webhook signature".to_string()))
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for StripePaymentProvider {
+    async fn create_subscription(
+        &self,
+        user_id: Uuid,
+        tier: SubscriptionTier,
+        payment_token: &str,
+        idempotency_key: &str,
+    ) -> DomainResult<ProviderSubscription> {
+        let price_id = match tier {
+            SubscriptionTier::Monthly => &self.monthly_price_id,
+            SubscriptionTier::Yearly => &self.yearly_price_id,
+            SubscriptionTier::Free => {
+                return Err(DomainError::ValidationError("The Free tier is not billed".to_string()))
+            }
+        };
+        let user_id = user_id.to_string();
+
+        let customer: StripeObject = self
+            .request(
+                Method::POST,
+                "/customers",
+                &[
+                    ("payment_method", payment_token),
+                    ("invoice_settings[default_payment_method]", payment_token),
+                    ("metadata[user_id]", &user_id),
+                ],
+                Some(&format!("{}-customer", idempotency_key)),
+            )
+            .await?;
+
+        // Fail right away instead of leaving an incomplete subscription when the first charge fails
+        let subscription: StripeSubscription = self
+            .request(
+                Method::POST,
+                "/subscriptions",
+                &[
+                    ("customer", &customer.id),
+                    ("items[0][price]", price_id),
+                    ("payment_behavior", "error_if_incomplete"),
+                    ("metadata[user_id]", &user_id),
+                    ("metadata[checkout_id]", idempotency_key),
+                ],
+                Some(&format!("{}-subscription", idempotency_key)),
+            )
+            .await?;
+
+        Ok(ProviderSubscription {
+            id: subscription.id,
+            current_period_start: timestamp(subscription.current_period_start)?,
+            current_period_end: timestamp(subscription.current_period_end)?,
+        })
+    }
+
+    async fn find_subscription(&self, idempotency_key: &str) -> DomainResult<Option<ProviderSubscription>> {
+        // Search results lag writes by up to a minute, far less than a checkout takes to go stale
+        let query = format!("metadata['checkout_id']:'{}'", idempotency_key);
+        let found: StripeList<StripeSubscription> = self
+            .request(Method::GET, "/subscriptions/search", &[("query", &query)], None)
+            .await?;
+
+        found.data
+            .into_iter()
+            .next()
+            .map(|subscription| {
+                Ok(ProviderSubscription {
+                    id: subscription.id,
+                    current_period_start: timestamp(subscription.current_period_start)?,
+                    current_period_end: timestamp(subscription.current_period_end)?,
+                })
+            })
+            .transpose()
+    }
+
+    async fn cancel_subscription(&self, subscription_id: &str) -> DomainResult<()> {
+        let _: StripeObject = self
+            .post(
+                &format!("/subscriptions/{}", subscription_id),
+                &[("cancel_at_period_end", "true")],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn refund_subscription(&self, subscription_id: &str) -> DomainResult<()> {
+        let path = format!("/subscriptions/{}", subscription_id);
+        let subscription: StripeSubscriptionInvoice = self
+            .request(Method::GET, &path, &[("expand[]", "latest_invoice")], None)
+            .await?;
+
+        if let Some(payment_intent) = subscription.latest_invoice.and_then(|invoice| invoice.payment_intent) {
+            let _: StripeObject = self
+                .request(
+                    Method::POST,
+                    "/refunds",
+                    &[("payment_intent", &payment_intent)],
+                    Some(&format!("{}-refund", subscription_id)),
+                )
+                .await?;
+        }
+
+        let _: StripeObject = self.request(Method::DELETE, &path, &[], None).await?;
+
+        Ok(())
+    }
+
+    fn signature_header(&self) -> &'static str {
+        "Stripe-Signature"
+    }
+
+    fn parse_webhook(&self, payload: &[u8], signature: &str) -> DomainResult<PaymentWebhook> {
+        self.verify_signature(payload, signature)?;
+
+        let event: StripeEvent = serde_json::from_slice(payload)
+            .map_err(|e| DomainError::ValidationError(format!("Malformed webhook: {}", e)))?;
+
+        let parsed = match event.event_type.as_str() {
+            "invoice.paid" => {
+                let invoice: StripeInvoice = parse_object(event.data.object)?;
+                match (invoice.subscription, invoice.lines.data.first()) {
+                    (Some(subscription_id), Some(line)) => Some(PaymentEvent::Renewed {
+                        subscription_id,
+                        period_start: timestamp(line.period.start)?,
+                        period_end: timestamp(line.period.end)?,
+                    }),
+                    _ => None,
+                }
+            }
+            "invoice.payment_failed" => {
+                let invoice: StripeInvoice = parse_object(event.data.object)?;
+                invoice
+                    .subscription
+                    .map(|subscription_id| PaymentEvent::PaymentFailed { subscription_id })
+            }
+            "customer.subscription.deleted" => {
+                let subscription: StripeSubscription = parse_object(event.data.object)?;
+                Some(PaymentEvent::Canceled {
+                    ended_at: match subscription.ended_at {
+                        Some(ended_at) => timestamp(ended_at)?,
+                        None => Utc::now(),
+                    },
+                    subscription_id: subscription.id,
+                })
+            }
+            _ => None,
+        };
+
+        Ok(PaymentWebhook {
+            event_id: event.id,
+            event: parsed,
+        })
+    }
+}
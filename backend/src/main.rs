@@ -8,13 +8,37 @@ use api::{create_router, AppState};
 use application::{
     LoginUser, RegisterUser, VerifyOtp, UploadPublicKey, GetPublicKey, 
     GetUploadUrl, SubmitKyc, ReviewKyc, 
-    SendMessage, UpdateLocation, FindNearbyUsers, UpgradeSubscription, RegisterDeviceToken
+    SendMessage, EditMessage, DeleteMessage, MarkAsRead, GetMissedEvents, AcknowledgeSync,
+    SetDisappearingMessages, GetMessageHistory, GetThread,
+    PinMessage, UnpinMessage, GetPinnedMessages, StarMessage, UnstarMessage, GetStarredMessages,
+    ForwardMessage,
+    CreateGroup, GetGroup, AddGroupMembers, RemoveGroupMember, ChangeMemberRole,
+    TransferGroupOwnership, UpdateGroupInfo, UpdateGroupSettings,
+    CreateGroupInvite, ListGroupInvites, RevokeGroupInvite, PreviewGroupInvite,
+    JoinGroupViaInvite, ListJoinRequests, ReviewJoinRequest, FindNearbyGroups, JoinNearbyGroup, EnforceGeofences,
+    CreateChannel, SubscribeChannel, UnsubscribeChannel, GetChannelViews,
+    RegisterDeviceKeys, UploadOneTimePreKeys, GetPreKeyCount, ClaimPreKeyBundles, GetKeyHistory,
+    DistributeSenderKey, GetSenderKeys, AuthorizeKeyRotation,
+    SetupBackupVault, UnlockBackupVault, CreateBackup, CompleteBackup, ListBackups, RestoreBackup,
+    DeleteBackup, DisableBackups,
+    StartCall, AnswerCall, EndCall, GetActiveCall, TrackCallPresence, GetIceServers,
+    JoinGroupCall, LeaveGroupCall, GetGroupCall, SweepGroupCalls, ExpireCalls,
+    UpdateLocation, FindNearbyUsers, SetDiscoverability, BlockUser, UnblockUser,
+    StartLiveLocation, UpdateLiveLocation, StopLiveLocation, GetLiveLocations, ExpireLiveLocations,
+    UpgradeSubscription, GetSubscription, CancelSubscription, HandlePaymentWebhook, RegisterDeviceToken
 };
 use infrastructure::{
     AuthServiceImpl, Database, PostgresUserRepository, PostgresKycRepository, 
-    PostgresMessageRepository, S3Service, MessageCleanupJob, RedisService,
-    EvmBlockchainService
+    PostgresMessageRepository, PostgresSyncRepository, PostgresConversationRepository,
+    PostgresGroupInviteRepository, PostgresPreKeyRepository, PostgresKeyLogRepository,
+    PostgresSenderKeyRepository, PostgresBackupRepository, PostgresCallRepository,
+    PostgresGroupCallRepository, PostgresLiveLocationRepository, PostgresSubscriptionRepository,
+    RedisGeoUserRepository, S3Service,
+    MessageCleanupJob, CallTimeoutJob, LocationExpiryJob, LiveLocationExpiryJob, LocationFlushJob,
+    SubscriptionExpiryJob, GroupCallSweepJob, RedisService,
+    EvmBlockchainService, CoturnIceServerProvider, LiveKitSfuProvider, StripePaymentProvider, FakePaymentProvider
 };
+use domain::services::PaymentProvider;
 use tokio::sync::broadcast;
 use anyhow::Context;
 
@@ -40,6 +64,13 @@ async fn main() -> anyhow::Result<()> {
     let s3_secret_key = std::env::var("S3_SECRET_KEY").ok();
     let s3_region = std::env::var("S3_REGION").ok();
 
+    // Stored locations are forgotten once they have not been refreshed for this long
+    let location_ttl_hours: i64 = std::env::var("LOCATION_TTL_HOURS")
+        .unwrap_or_else(|_| "24".to_string())
+        .parse()
+        .context("LOCATION_TTL_HOURS must be a number")?;
+    let location_ttl = chrono::Duration::hours(location_ttl_hours);
+
     // Redis Config
     let redis_url = std::env::var("REDIS_URL").context("REDIS_URL must be set")?;
 
@@ -51,6 +82,17 @@ async fn main() -> anyhow::Result<()> {
     let user_repo = Arc::new(PostgresUserRepository::new(db.pool().clone()));
     let kyc_repo = Arc::new(PostgresKycRepository::new(db.pool().clone()));
     let message_repo = Arc::new(PostgresMessageRepository::new(db.pool().clone()));
+    let sync_repo = Arc::new(PostgresSyncRepository::new(db.pool().clone()));
+    let conversation_repo = Arc::new(PostgresConversationRepository::new(db.pool().clone()));
+    let group_invite_repo = Arc::new(PostgresGroupInviteRepository::new(db.pool().clone()));
+    let prekey_repo = Arc::new(PostgresPreKeyRepository::new(db.pool().clone()));
+    let key_log_repo = Arc::new(PostgresKeyLogRepository::new(db.pool().clone()));
+    let sender_key_repo = Arc::new(PostgresSenderKeyRepository::new(db.pool().clone()));
+    let backup_repo = Arc::new(PostgresBackupRepository::new(db.pool().clone()));
+    let call_repo = Arc::new(PostgresCallRepository::new(db.pool().clone()));
+    let group_call_repo = Arc::new(PostgresGroupCallRepository::new(db.pool().clone()));
+    let live_location_repo = Arc::new(PostgresLiveLocationRepository::new(db.pool().clone()));
+    let subscription_repo = Arc::new(PostgresSubscriptionRepository::new(db.pool().clone()));
 
     // Initialize services
     let auth_service = Arc::new(AuthServiceImpl::new(jwt_secret, jwt_expiration));
@@ -73,39 +115,288 @@ async fn main() -> anyhow::Result<()> {
     };
     
     let redis_service = Arc::new(RedisService::new(&redis_url).await?);
+
+    // Location pings and nearby searches go through a Redis GEO index in front of Postgres
+    let geo_user_repo = Arc::new(RedisGeoUserRepository::new(user_repo.clone(), &redis_service, location_ttl));
     
     // Initialize Blockchain Service
     let rpc_url = std::env::var("RPC_URL").context("RPC_URL must be set")?;
     let blockchain_service = Arc::new(EvmBlockchainService::new(&rpc_url)?);
 
+    // ICE servers for calls; TURN needs the secret shared with coturn (`static-auth-secret`)
+    let ice_urls = |name: &str| -> Vec<String> {
+        std::env::var(name)
+            .unwrap_or_default()
+            .split(',')
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .collect()
+    };
+    let turn_secret = std::env::var("TURN_SECRET").ok().filter(|secret| !secret.is_empty());
+    if turn_secret.is_none() {
+        tracing::warn!("TURN_SECRET not configured. Calls will only get STUN servers.");
+    }
+    let ice_server_provider = Arc::new(CoturnIceServerProvider::new(
+        ice_urls("STUN_URLS"),
+        ice_urls("TURN_URLS"),
+        turn_secret.as_deref(),
+    ));
+
+    let required_env = |name: &str| -> anyhow::Result<String> {
+        std::env::var(name)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .with_context(|| format!("{} must be set", name))
+    };
+
+    // SFU for group calls; join tokens are signed with the API secret, so there is no fallback
+    let sfu_provider = Arc::new(LiveKitSfuProvider::new(
+        &required_env("LIVEKIT_URL")?,
+        &required_env("LIVEKIT_API_KEY")?,
+        &required_env("LIVEKIT_API_SECRET")?,
+    ));
+
+    // Subscription billing - Stripe unless the fake provider, which charges nothing, is asked for
+    let payment_provider: Arc<dyn PaymentProvider> = match std::env::var("PAYMENT_PROVIDER").as_deref() {
+        Ok("fake") => {
+            tracing::warn!("Using fake payment provider; subscriptions are free.");
+            Arc::new(FakePaymentProvider::new(&required_env("FAKE_PAYMENT_WEBHOOK_SECRET")?))
+        }
+        Ok("stripe") | Err(_) => Arc::new(StripePaymentProvider::new(
+            &required_env("STRIPE_SECRET_KEY")?,
+            &required_env("STRIPE_WEBHOOK_SECRET")?,
+            &required_env("STRIPE_PRICE_MONTHLY")?,
+            &required_env("STRIPE_PRICE_YEARLY")?,
+        )),
+        Ok(other) => anyhow::bail!("Unknown PAYMENT_PROVIDER {:?}; use stripe or fake", other),
+    };
+
+    // Initialize broadcast channel for WebSockets
+    let (tx, _rx) = broadcast::channel(100);
+
     // Initialize background jobs
-    let cleanup_job = MessageCleanupJob::new(message_repo.clone());
+    let cleanup_job = MessageCleanupJob::new(message_repo.clone(), sync_repo.clone(), tx.clone());
+    let expiry_scheduler = cleanup_job.scheduler();
     tokio::spawn(async move {
         cleanup_job.run().await;
     });
 
+    let expire_calls = Arc::new(ExpireCalls::new(call_repo.clone(), message_repo.clone()));
+    let call_timeout_job = CallTimeoutJob::new(expire_calls, tx.clone());
+    tokio::spawn(async move {
+        call_timeout_job.run().await;
+    });
+
+    let location_expiry_job = LocationExpiryJob::new(user_repo.clone(), location_ttl);
+    tokio::spawn(async move {
+        location_expiry_job.run().await;
+    });
+
+    let location_flush_job = LocationFlushJob::new(geo_user_repo.clone());
+    tokio::spawn(async move {
+        location_flush_job.run().await;
+    });
+
+    let subscription_expiry_job = SubscriptionExpiryJob::new(subscription_repo.clone());
+    tokio::spawn(async move {
+        subscription_expiry_job.run().await;
+    });
+
+    let sweep_group_calls = Arc::new(SweepGroupCalls::new(
+        group_call_repo.clone(),
+        message_repo.clone(),
+        sync_repo.clone(),
+        sfu_provider.clone(),
+    ));
+    let group_call_sweep_job = GroupCallSweepJob::new(sweep_group_calls, tx.clone());
+    tokio::spawn(async move {
+        group_call_sweep_job.run().await;
+    });
+
+    let expire_live_locations = Arc::new(ExpireLiveLocations::new(live_location_repo.clone(), sync_repo.clone()));
+    let live_location_expiry_job = LiveLocationExpiryJob::new(expire_live_locations, tx.clone());
+    tokio::spawn(async move {
+        live_location_expiry_job.run().await;
+    });
+
     // Initialize use cases
     let register_user = Arc::new(RegisterUser::new(user_repo.clone(), auth_service.clone()));
     let login_user = Arc::new(LoginUser::new(user_repo.clone(), auth_service.clone()));
     let verify_otp = Arc::new(VerifyOtp::new(auth_service.clone()));
-    let upload_public_key = Arc::new(UploadPublicKey::new(user_repo.clone()));
+    let authorize_key_rotation = Arc::new(AuthorizeKeyRotation::new(
+        user_repo.clone(),
+        key_log_repo.clone(),
+        auth_service.clone(),
+    ));
+    let upload_public_key = Arc::new(UploadPublicKey::new(
+        user_repo.clone(),
+        authorize_key_rotation.clone(),
+        conversation_repo.clone(),
+        sync_repo.clone(),
+    ));
     let get_public_key = Arc::new(GetPublicKey::new(user_repo.clone()));
     
     let get_upload_url = Arc::new(GetUploadUrl::new(s3_service.clone()));
     let submit_kyc = Arc::new(SubmitKyc::new(kyc_repo.clone()));
     let review_kyc = Arc::new(ReviewKyc::new(kyc_repo.clone()));
     
-    let send_message = Arc::new(SendMessage::new(message_repo.clone()));
+    let send_message = Arc::new(SendMessage::new(
+        message_repo.clone(),
+        conversation_repo.clone(),
+        sender_key_repo.clone(),
+        expiry_scheduler.clone(),
+    ));
+    let edit_message = Arc::new(EditMessage::new(message_repo.clone(), sync_repo.clone()));
+    let delete_message = Arc::new(DeleteMessage::new(message_repo.clone(), sync_repo.clone()));
+    let mark_as_read = Arc::new(MarkAsRead::new(
+        message_repo.clone(),
+        conversation_repo.clone(),
+        sync_repo.clone(),
+        expiry_scheduler.clone(),
+    ));
+    let get_missed_events = Arc::new(GetMissedEvents::new(sync_repo.clone()));
+    let acknowledge_sync = Arc::new(AcknowledgeSync::new(sync_repo.clone()));
+    let set_disappearing_messages = Arc::new(SetDisappearingMessages::new(conversation_repo.clone()));
+    let get_message_history = Arc::new(GetMessageHistory::new(message_repo.clone(), conversation_repo.clone()));
+    let get_thread = Arc::new(GetThread::new(message_repo.clone(), conversation_repo.clone()));
+    let pin_message = Arc::new(PinMessage::new(message_repo.clone(), conversation_repo.clone()));
+    let unpin_message = Arc::new(UnpinMessage::new(message_repo.clone(), conversation_repo.clone()));
+    let get_pinned_messages = Arc::new(GetPinnedMessages::new(message_repo.clone(), conversation_repo.clone()));
+    let star_message = Arc::new(StarMessage::new(message_repo.clone(), conversation_repo.clone()));
+    let unstar_message = Arc::new(UnstarMessage::new(message_repo.clone()));
+    let get_starred_messages = Arc::new(GetStarredMessages::new(message_repo.clone()));
+    let forward_message = Arc::new(ForwardMessage::new(message_repo.clone(), conversation_repo.clone(), send_message.clone()));
+
+    let create_group = Arc::new(CreateGroup::new(conversation_repo.clone(), user_repo.clone(), message_repo.clone()));
+    let get_group = Arc::new(GetGroup::new(conversation_repo.clone()));
+    let add_group_members = Arc::new(AddGroupMembers::new(
+        conversation_repo.clone(),
+        user_repo.clone(),
+        message_repo.clone(),
+        sync_repo.clone(),
+        sender_key_repo.clone(),
+    ));
+    let remove_group_member = Arc::new(RemoveGroupMember::new(
+        conversation_repo.clone(),
+        message_repo.clone(),
+        sync_repo.clone(),
+        sender_key_repo.clone(),
+        live_location_repo.clone(),
+        group_call_repo.clone(),
+        sfu_provider.clone(),
+    ));
+    let change_member_role = Arc::new(ChangeMemberRole::new(conversation_repo.clone(), message_repo.clone()));
+    let transfer_group_ownership = Arc::new(TransferGroupOwnership::new(conversation_repo.clone(), message_repo.clone()));
+    let update_group_info = Arc::new(UpdateGroupInfo::new(conversation_repo.clone(), message_repo.clone()));
+    let update_group_settings = Arc::new(UpdateGroupSettings::new(conversation_repo.clone(), message_repo.clone()));
+    let create_group_invite = Arc::new(CreateGroupInvite::new(conversation_repo.clone(), group_invite_repo.clone()));
+    let list_group_invites = Arc::new(ListGroupInvites::new(conversation_repo.clone(), group_invite_repo.clone()));
+    let revoke_group_invite = Arc::new(RevokeGroupInvite::new(conversation_repo.clone(), group_invite_repo.clone()));
+    let preview_group_invite = Arc::new(PreviewGroupInvite::new(conversation_repo.clone(), group_invite_repo.clone()));
+    let join_group_via_invite = Arc::new(JoinGroupViaInvite::new(
+        conversation_repo.clone(),
+        group_invite_repo.clone(),
+        user_repo.clone(),
+        message_repo.clone(),
+        sync_repo.clone(),
+        sender_key_repo.clone(),
+    ));
+    let list_join_requests = Arc::new(ListJoinRequests::new(conversation_repo.clone(), group_invite_repo.clone()));
+    let review_join_request = Arc::new(ReviewJoinRequest::new(
+        conversation_repo.clone(),
+        group_invite_repo.clone(),
+        user_repo.clone(),
+        message_repo.clone(),
+        sync_repo.clone(),
+        sender_key_repo.clone(),
+    ));
+    let find_nearby_groups = Arc::new(FindNearbyGroups::new(conversation_repo.clone(), geo_user_repo.clone()));
+    let join_nearby_group = Arc::new(JoinNearbyGroup::new(
+        conversation_repo.clone(),
+        geo_user_repo.clone(),
+        message_repo.clone(),
+        sync_repo.clone(),
+        sender_key_repo.clone(),
+    ));
+    let enforce_geofences = Arc::new(EnforceGeofences::new(
+        conversation_repo.clone(),
+        message_repo.clone(),
+        sync_repo.clone(),
+        sender_key_repo.clone(),
+        live_location_repo.clone(),
+    ));
+
+    let create_channel = Arc::new(CreateChannel::new(conversation_repo.clone()));
+    let subscribe_channel = Arc::new(SubscribeChannel::new(conversation_repo.clone()));
+    let unsubscribe_channel = Arc::new(UnsubscribeChannel::new(conversation_repo.clone()));
+    let get_channel_views = Arc::new(GetChannelViews::new(conversation_repo.clone(), message_repo.clone()));
+
+    let register_device_keys = Arc::new(RegisterDeviceKeys::new(
+        prekey_repo.clone(),
+        user_repo.clone(),
+        authorize_key_rotation.clone(),
+        conversation_repo.clone(),
+        sync_repo.clone(),
+    ));
+    let upload_one_time_prekeys = Arc::new(UploadOneTimePreKeys::new(prekey_repo.clone()));
+    let get_prekey_count = Arc::new(GetPreKeyCount::new(prekey_repo.clone()));
+    let claim_prekey_bundles = Arc::new(ClaimPreKeyBundles::new(prekey_repo.clone(), user_repo.clone()));
+    let get_key_history = Arc::new(GetKeyHistory::new(key_log_repo.clone(), user_repo.clone()));
+    let distribute_sender_key = Arc::new(DistributeSenderKey::new(sender_key_repo.clone(), conversation_repo.clone(), sync_repo.clone()));
+    let get_sender_keys = Arc::new(GetSenderKeys::new(sender_key_repo.clone(), conversation_repo.clone()));
+
+    let backup_vault_pepper = std::env::var("BACKUP_VAULT_PEPPER").context("BACKUP_VAULT_PEPPER must be set")?;
+    let unlock_backup_vault = Arc::new(UnlockBackupVault::new(
+        backup_repo.clone(),
+        auth_service.clone(),
+        &backup_vault_pepper,
+    ));
+    let setup_backup_vault = Arc::new(SetupBackupVault::new(
+        backup_repo.clone(),
+        user_repo.clone(),
+        auth_service.clone(),
+        s3_service.clone(),
+        unlock_backup_vault.clone(),
+    ));
+    let create_backup = Arc::new(CreateBackup::new(backup_repo.clone(), s3_service.clone()));
+    let complete_backup = Arc::new(CompleteBackup::new(backup_repo.clone(), s3_service.clone()));
+    let list_backups = Arc::new(ListBackups::new(backup_repo.clone()));
+    let restore_backup = Arc::new(RestoreBackup::new(backup_repo.clone(), s3_service.clone()));
+    let delete_backup = Arc::new(DeleteBackup::new(backup_repo.clone(), s3_service.clone()));
+    let disable_backups = Arc::new(DisableBackups::new(backup_repo.clone(), s3_service.clone()));
+
+    let start_call = Arc::new(StartCall::new(call_repo.clone(), conversation_repo.clone(), message_repo.clone()));
+    let answer_call = Arc::new(AnswerCall::new(call_repo.clone()));
+    let end_call = Arc::new(EndCall::new(call_repo.clone(), message_repo.clone()));
+    let get_active_call = Arc::new(GetActiveCall::new(call_repo.clone()));
+    let track_call_presence = Arc::new(TrackCallPresence::new(call_repo.clone(), message_repo.clone()));
+    let get_ice_servers = Arc::new(GetIceServers::new(user_repo.clone(), ice_server_provider.clone()));
+    let join_group_call = Arc::new(JoinGroupCall::new(
+        group_call_repo.clone(),
+        conversation_repo.clone(),
+        message_repo.clone(),
+        sync_repo.clone(),
+        sfu_provider.clone(),
+    ));
+    let leave_group_call = Arc::new(LeaveGroupCall::new(group_call_repo.clone(), message_repo.clone(), sync_repo.clone(), sfu_provider.clone()));
+    let get_group_call = Arc::new(GetGroupCall::new(group_call_repo.clone(), conversation_repo.clone()));
     
-    let update_location = Arc::new(UpdateLocation::new(user_repo.clone()));
-    let find_nearby_users = Arc::new(FindNearbyUsers::new(user_repo.clone()));
+    let update_location = Arc::new(UpdateLocation::new(geo_user_repo.clone()));
+    let find_nearby_users = Arc::new(FindNearbyUsers::new(geo_user_repo.clone(), location_ttl));
+    let set_discoverability = Arc::new(SetDiscoverability::new(geo_user_repo.clone()));
+    let block_user = Arc::new(BlockUser::new(user_repo.clone()));
+    let unblock_user = Arc::new(UnblockUser::new(user_repo.clone()));
+    let start_live_location = Arc::new(StartLiveLocation::new(conversation_repo.clone(), live_location_repo.clone(), sync_repo.clone()));
+    let update_live_location = Arc::new(UpdateLiveLocation::new(conversation_repo.clone(), live_location_repo.clone()));
+    let stop_live_location = Arc::new(StopLiveLocation::new(live_location_repo.clone(), sync_repo.clone()));
+    let get_live_locations = Arc::new(GetLiveLocations::new(conversation_repo.clone(), live_location_repo.clone()));
     
-    let upgrade_subscription = Arc::new(UpgradeSubscription::new(user_repo.clone()));
+    let upgrade_subscription = Arc::new(UpgradeSubscription::new(subscription_repo.clone(), payment_provider.clone()));
+    let get_subscription = Arc::new(GetSubscription::new(subscription_repo.clone()));
+    let cancel_subscription = Arc::new(CancelSubscription::new(subscription_repo.clone(), payment_provider.clone()));
+    let handle_payment_webhook = Arc::new(HandlePaymentWebhook::new(subscription_repo.clone(), payment_provider.clone()));
     let register_device_token = Arc::new(RegisterDeviceToken::new(user_repo.clone()));
 
-    // Initialize broadcast channel for WebSockets
-    let (tx, _rx) = broadcast::channel(100);
-
     // Create app state
     let app_state = Arc::new(AppState {
         register_user,
@@ -117,9 +408,80 @@ async fn main() -> anyhow::Result<()> {
         submit_kyc,
         review_kyc,
         send_message,
+        edit_message,
+        delete_message,
+        mark_as_read,
+        get_missed_events,
+        acknowledge_sync,
+        set_disappearing_messages,
+        get_message_history,
+        get_thread,
+        pin_message,
+        unpin_message,
+        get_pinned_messages,
+        star_message,
+        unstar_message,
+        get_starred_messages,
+        forward_message,
+        create_group,
+        get_group,
+        add_group_members,
+        remove_group_member,
+        change_member_role,
+        transfer_group_ownership,
+        update_group_info,
+        update_group_settings,
+        create_group_invite,
+        list_group_invites,
+        revoke_group_invite,
+        preview_group_invite,
+        join_group_via_invite,
+        list_join_requests,
+        review_join_request,
+        find_nearby_groups,
+        join_nearby_group,
+        enforce_geofences,
+        create_channel,
+        subscribe_channel,
+        unsubscribe_channel,
+        get_channel_views,
+        register_device_keys,
+        upload_one_time_prekeys,
+        get_prekey_count,
+        claim_prekey_bundles,
+        get_key_history,
+        distribute_sender_key,
+        get_sender_keys,
+        setup_backup_vault,
+        unlock_backup_vault,
+        create_backup,
+        complete_backup,
+        list_backups,
+        restore_backup,
+        delete_backup,
+        disable_backups,
+        start_call,
+        answer_call,
+        end_call,
+        get_active_call,
+        track_call_presence,
+        get_ice_servers,
+        join_group_call,
+        leave_group_call,
+        get_group_call,
         update_location,
         find_nearby_users,
+        set_discoverability,
+        block_user,
+        unblock_user,
+        start_live_location,
+        update_live_location,
+        stop_live_location,
+        get_live_locations,
         upgrade_subscription,
+        get_subscription,
+        cancel_subscription,
+        handle_payment_webhook,
         register_device_token,
         tx,
     });